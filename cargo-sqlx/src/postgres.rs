@@ -1,4 +1,3 @@
-use sqlx::pool::PoolConnection;
 use sqlx::postgres::PgRow;
 use sqlx::Connect;
 use sqlx::Executor;
@@ -158,7 +157,7 @@ impl DatabaseMigrator for Postgres {
 }
 
 pub struct PostgresMigration {
-    transaction: sqlx::Transaction<PoolConnection<PgConnection>>,
+    transaction: sqlx::Transaction<'static, sqlx::Postgres>,
 }
 
 #[async_trait]
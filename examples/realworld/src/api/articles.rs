@@ -1,10 +1,9 @@
 use chrono::{DateTime, Utc};
-use futures::TryFutureExt;
 use heck::KebabCase;
 use log::*;
 use serde::{Deserialize, Serialize};
 use sqlx::pool::PoolConnection;
-use sqlx::{Connect, Connection};
+use sqlx::{Connect, Connection, Database};
 use tide::{Error, IntoResponse, Request, Response, ResultExt};
 
 use crate::api::model::*;
@@ -110,17 +109,16 @@ struct MultipleCommentsResponseBody {
 /// Retrieve all articles
 ///
 /// [List Articles](https://github.com/gothinkster/realworld/tree/master/api#list-articles)
-pub async fn list_articles(
-    req: Request<impl Db<Conn = PoolConnection<impl Connect + ProvideData>>>,
-) -> Response {
+pub async fn list_articles<C>(req: Request<impl Db<Conn = PoolConnection<C>>>) -> Response
+where
+    C: Connect + ProvideData,
+    C::Database: Database<Connection = C>,
+{
     async move {
         let state = req.state();
 
-        let mut tx = state
-            .conn()
-            .and_then(Connection::begin)
-            .await
-            .server_err()?;
+        let mut conn = state.conn().await.server_err()?;
+        let mut tx = conn.begin().await.server_err()?;
 
         let authenticated = optionally_auth(&req).transpose()?;
 
@@ -152,18 +150,17 @@ pub async fn list_articles(
 /// Get Article
 ///
 /// https://github.com/gothinkster/realworld/tree/master/api#get-article
-pub async fn get_article(
-    req: Request<impl Db<Conn = PoolConnection<impl Connect + ProvideData>>>,
-) -> Response {
+pub async fn get_article<C>(req: Request<impl Db<Conn = PoolConnection<C>>>) -> Response
+where
+    C: Connect + ProvideData,
+    C::Database: Database<Connection = C>,
+{
     async move {
         let authenticated = optionally_auth(&req).transpose()?;
 
         let state = req.state();
-        let mut tx = state
-            .conn()
-            .and_then(Connection::begin)
-            .await
-            .server_err()?;
+        let mut conn = state.conn().await.server_err()?;
+        let mut tx = conn.begin().await.server_err()?;
         let slug = req.param::<String>("slug").client_err()?;
 
         let article = tx.get_article_by_slug(&slug).await?;
@@ -192,9 +189,11 @@ pub async fn get_article(
 /// Create Article
 ///
 /// https://github.com/gothinkster/realworld/tree/master/api#create-article
-pub async fn create_article(
-    mut req: Request<impl Db<Conn = PoolConnection<impl Connect + ProvideData>>>,
-) -> Response {
+pub async fn create_article<C>(mut req: Request<impl Db<Conn = PoolConnection<C>>>) -> Response
+where
+    C: Connect + ProvideData,
+    C::Database: Database<Connection = C>,
+{
     async move {
         #[derive(Deserialize)]
         struct ArticleRequestBody {
@@ -222,11 +221,8 @@ pub async fn create_article(
 
         let state = req.state();
 
-        let mut tx = state
-            .conn()
-            .and_then(Connection::begin)
-            .await
-            .server_err()?;
+        let mut conn = state.conn().await.server_err()?;
+        let mut tx = conn.begin().await.server_err()?;
 
         let (article, profile) = {
             let ArticleRequestBody {
@@ -268,20 +264,19 @@ pub async fn create_article(
 /// Delete Article
 ///
 /// https://github.com/gothinkster/realworld/tree/master/api#delete-article
-pub async fn delete_article(
-    req: Request<impl Db<Conn = PoolConnection<impl Connect + ProvideData>>>,
-) -> Response {
+pub async fn delete_article<C>(req: Request<impl Db<Conn = PoolConnection<C>>>) -> Response
+where
+    C: Connect + ProvideData,
+    C::Database: Database<Connection = C>,
+{
     async move {
         let (user_id, _) = extract_and_validate_token(&req)?;
 
         let slug = req.param::<String>("slug").client_err()?;
 
         let state = req.state();
-        let mut tx = state
-            .conn()
-            .and_then(Connection::begin)
-            .await
-            .server_err()?;
+        let mut conn = state.conn().await.server_err()?;
+        let mut tx = conn.begin().await.server_err()?;
 
         let article = tx.get_article_by_slug(&slug).await?;
 
@@ -302,9 +297,11 @@ pub async fn delete_article(
 /// Update the title, description, and/or body of an Article
 ///
 /// [Update Article](https://github.com/gothinkster/realworld/tree/master/api#update-article)
-pub async fn update_article(
-    mut req: Request<impl Db<Conn = PoolConnection<impl Connect + ProvideData>>>,
-) -> Response {
+pub async fn update_article<C>(mut req: Request<impl Db<Conn = PoolConnection<C>>>) -> Response
+where
+    C: Connect + ProvideData,
+    C::Database: Database<Connection = C>,
+{
     async move {
         #[derive(Deserialize)]
         struct UpdateArticleBody {
@@ -324,11 +321,8 @@ pub async fn update_article(
         let body: UpdateArticleBody = req.body_json().await.client_err()?;
 
         let state = req.state();
-        let mut tx = state
-            .conn()
-            .and_then(Connection::begin)
-            .await
-            .server_err()?;
+        let mut conn = state.conn().await.server_err()?;
+        let mut tx = conn.begin().await.server_err()?;
 
         let existing = tx.get_article_by_slug(&slug).await?;
 
@@ -378,9 +372,11 @@ pub async fn update_article(
 /// Add a comment to an an article
 ///
 /// [Add Comments to an Article](https://github.com/gothinkster/realworld/tree/master/api#add-comments-to-an-article)
-pub async fn add_comment(
-    mut req: Request<impl Db<Conn = PoolConnection<impl Connect + ProvideData>>>,
-) -> Response {
+pub async fn add_comment<C>(mut req: Request<impl Db<Conn = PoolConnection<C>>>) -> Response
+where
+    C: Connect + ProvideData,
+    C::Database: Database<Connection = C>,
+{
     async move {
         #[derive(Deserialize)]
         struct CommentRequestBody {
@@ -397,11 +393,8 @@ pub async fn add_comment(
         let req_body: CommentRequestBody = req.body_json().await.client_err()?;
 
         let state = req.state();
-        let mut tx = state
-            .conn()
-            .and_then(Connection::begin)
-            .await
-            .server_err()?;
+        let mut conn = state.conn().await.server_err()?;
+        let mut tx = conn.begin().await.server_err()?;
 
         let _article = tx.get_article_by_slug(&slug).await?;
 
@@ -428,9 +421,11 @@ pub async fn add_comment(
 /// Get the comments placed on an article
 ///
 /// [Get Comments from an Article](https://github.com/gothinkster/realworld/tree/master/api#get-comments-from-an-article)
-pub async fn get_comments(
-    req: Request<impl Db<Conn = PoolConnection<impl Connect + ProvideData>>>,
-) -> Response {
+pub async fn get_comments<C>(req: Request<impl Db<Conn = PoolConnection<C>>>) -> Response
+where
+    C: Connect + ProvideData,
+    C::Database: Database<Connection = C>,
+{
     async move {
         let authenticated = optionally_auth(&req).transpose()?;
 
@@ -438,11 +433,8 @@ pub async fn get_comments(
 
         let state = req.state();
 
-        let mut db = state
-            .conn()
-            .and_then(Connection::begin)
-            .await
-            .server_err()?;
+        let mut conn = state.conn().await.server_err()?;
+        let mut db = conn.begin().await.server_err()?;
 
         let leader_ids: HashSet<EntityId> = if let Some((user_id, _)) = authenticated {
             HashSet::from_iter(db.get_following(user_id).await?)
@@ -464,9 +456,11 @@ pub async fn get_comments(
     .unwrap_or_else(IntoResponse::into_response)
 }
 
-pub async fn delete_comment(
-    req: Request<impl Db<Conn = PoolConnection<impl Connect + ProvideData>>>,
-) -> Response {
+pub async fn delete_comment<C>(req: Request<impl Db<Conn = PoolConnection<C>>>) -> Response
+where
+    C: Connect + ProvideData,
+    C::Database: Database<Connection = C>,
+{
     async move {
         let (user_id, _) = extract_and_validate_token(&req)?;
 
@@ -474,11 +468,8 @@ pub async fn delete_comment(
         let comment_id = req.param::<EntityId>("comment_id").client_err()?;
 
         let state = req.state();
-        let mut db = state
-            .conn()
-            .and_then(Connection::begin)
-            .await
-            .server_err()?;
+        let mut conn = state.conn().await.server_err()?;
+        let mut db = conn.begin().await.server_err()?;
 
         let comment = db.get_comment(&slug, comment_id).await?;
 
@@ -499,9 +490,11 @@ pub async fn delete_comment(
 /// Favorite Article
 ///
 /// https://github.com/gothinkster/realworld/tree/master/api#favorite-article
-pub async fn favorite_article(
-    req: Request<impl Db<Conn = PoolConnection<impl Connect + ProvideData>>>,
-) -> Response {
+pub async fn favorite_article<C>(req: Request<impl Db<Conn = PoolConnection<C>>>) -> Response
+where
+    C: Connect + ProvideData,
+    C::Database: Database<Connection = C>,
+{
     should_favorite(req, true)
         .await
         .unwrap_or_else(IntoResponse::into_response)
@@ -510,27 +503,30 @@ pub async fn favorite_article(
 /// Unfavorite Article
 ///
 /// https://github.com/gothinkster/realworld/tree/master/api#favorite-article
-pub async fn unfavorite_article(
-    req: Request<impl Db<Conn = PoolConnection<impl Connect + ProvideData>>>,
-) -> Response {
+pub async fn unfavorite_article<C>(req: Request<impl Db<Conn = PoolConnection<C>>>) -> Response
+where
+    C: Connect + ProvideData,
+    C::Database: Database<Connection = C>,
+{
     should_favorite(req, false)
         .await
         .unwrap_or_else(IntoResponse::into_response)
 }
 
-async fn should_favorite(
-    req: Request<impl Db<Conn = PoolConnection<impl Connect + ProvideData>>>,
+async fn should_favorite<C>(
+    req: Request<impl Db<Conn = PoolConnection<C>>>,
     should_favorite: bool,
-) -> tide::Result<Response> {
+) -> tide::Result<Response>
+where
+    C: Connect + ProvideData,
+    C::Database: Database<Connection = C>,
+{
     let (user_id, _) = extract_and_validate_token(&req)?;
     let slug = req.param::<String>("slug").client_err()?;
 
     let state = req.state();
-    let mut tx = state
-        .conn()
-        .and_then(Connection::begin)
-        .await
-        .server_err()?;
+    let mut conn = state.conn().await.server_err()?;
+    let mut tx = conn.begin().await.server_err()?;
 
     match should_favorite {
         true => tx.create_favorite(user_id, &slug),
@@ -1,8 +1,7 @@
-use futures::TryFutureExt;
 use log::*;
 use serde::Serialize;
 use sqlx::pool::PoolConnection;
-use sqlx::{Connect, Connection};
+use sqlx::{Connect, Connection, Database};
 use tide::{Error, IntoResponse, Request, Response, ResultExt};
 
 use crate::api::model::*;
@@ -24,9 +23,11 @@ impl From<Profile> for ProfileResponseBody {
 /// Retrieve a profile by username
 ///
 /// [Get Profile](https://github.com/gothinkster/realworld/tree/master/api#get-profile)
-pub async fn get_profile(
-    req: Request<impl Db<Conn = PoolConnection<impl Connect + ProvideData>>>,
-) -> Response {
+pub async fn get_profile<C>(req: Request<impl Db<Conn = PoolConnection<C>>>) -> Response
+where
+    C: Connect + ProvideData,
+    C::Database: Database<Connection = C>,
+{
     async move {
         let authenticated = optionally_auth(&req).transpose()?;
 
@@ -34,11 +35,8 @@ pub async fn get_profile(
         debug!("Searching for profile {}", leader_username);
 
         let state = req.state();
-        let mut tx = state
-            .conn()
-            .and_then(Connection::begin)
-            .await
-            .server_err()?;
+        let mut conn = state.conn().await.server_err()?;
+        let mut tx = conn.begin().await.server_err()?;
 
         let leader = tx.get_profile_by_username(&leader_username).await?;
 
@@ -63,9 +61,11 @@ pub async fn get_profile(
 /// Follow a user
 ///
 /// [Follow User](https://github.com/gothinkster/realworld/tree/master/api#follow-user)
-pub async fn follow_user(
-    req: Request<impl Db<Conn = PoolConnection<impl Connect + ProvideData>>>,
-) -> Response {
+pub async fn follow_user<C>(req: Request<impl Db<Conn = PoolConnection<C>>>) -> Response
+where
+    C: Connect + ProvideData,
+    C::Database: Database<Connection = C>,
+{
     should_follow(req, true)
         .await
         .unwrap_or_else(IntoResponse::into_response)
@@ -74,29 +74,32 @@ pub async fn follow_user(
 /// Stop following a user
 ///
 /// [Unfollow User](https://github.com/gothinkster/realworld/tree/master/api#unfollow-user)
-pub async fn unfollow_user(
-    req: Request<impl Db<Conn = PoolConnection<impl Connect + ProvideData>>>,
-) -> Response {
+pub async fn unfollow_user<C>(req: Request<impl Db<Conn = PoolConnection<C>>>) -> Response
+where
+    C: Connect + ProvideData,
+    C::Database: Database<Connection = C>,
+{
     should_follow(req, false)
         .await
         .unwrap_or_else(IntoResponse::into_response)
 }
 
 /// Adds or removes a following relationship
-async fn should_follow(
-    req: Request<impl Db<Conn = PoolConnection<impl Connect + ProvideData>>>,
+async fn should_follow<C>(
+    req: Request<impl Db<Conn = PoolConnection<C>>>,
     should_follow: bool,
-) -> tide::Result<Response> {
+) -> tide::Result<Response>
+where
+    C: Connect + ProvideData,
+    C::Database: Database<Connection = C>,
+{
     let (user_id, _) = extract_and_validate_token(&req)?;
 
     let leader_username = req.param::<String>("username").client_err()?;
 
     let state = req.state();
-    let mut tx = state
-        .conn()
-        .and_then(Connection::begin)
-        .await
-        .server_err()?;
+    let mut conn = state.conn().await.server_err()?;
+    let mut tx = conn.begin().await.server_err()?;
 
     let leader_ent = tx.get_profile_by_username(&leader_username).await?;
 
@@ -1,12 +1,11 @@
 use std::default::Default;
 
 use chrono::{Duration, Utc};
-use futures::TryFutureExt;
 use log::*;
 use rand::{thread_rng, RngCore};
 use serde::{Deserialize, Deserializer, Serialize};
 use sqlx::pool::PoolConnection;
-use sqlx::{Connect, Connection};
+use sqlx::{Connect, Connection, Database};
 use tide::{Error, IntoResponse, Request, Response, ResultExt};
 
 use crate::api::util::{extract_and_validate_token, to_json_response, TokenClaims, SECRET_KEY};
@@ -272,9 +271,11 @@ pub async fn login(
 /// Update a user's email, bio, or image
 ///
 /// [Update User](https://github.com/gothinkster/realworld/tree/master/api#update-user)
-pub async fn update_user(
-    mut req: Request<impl Db<Conn = PoolConnection<impl Connect + ProvideAuthn>>>,
-) -> Response {
+pub async fn update_user<C>(mut req: Request<impl Db<Conn = PoolConnection<C>>>) -> Response
+where
+    C: Connect + ProvideAuthn,
+    C::Database: Database<Connection = C>,
+{
     async move {
         #[derive(Deserialize)]
         struct UpdateRequestBody {
@@ -294,11 +295,8 @@ pub async fn update_user(
         let body = req.body_json().await.server_err()?;
 
         let state = req.state();
-        let mut tx = state
-            .conn()
-            .and_then(Connection::begin)
-            .await
-            .server_err()?;
+        let mut conn = state.conn().await.server_err()?;
+        let mut tx = conn.begin().await.server_err()?;
 
         let updated = {
             let UpdateRequestBody {
@@ -0,0 +1,140 @@
+//! Cancelling a query while it runs.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures_util::future::{self, Either};
+use futures_util::task::AtomicWaker;
+
+/// A handle to cancel a query that is running, from another task; see `Query::cancellable`.
+///
+/// Once [`cancel`] is called, the query stops at the next message that the database sends for it,
+/// or sooner as the database is asked to cancel it as well, and its future resolves with
+/// [`Error::Cancelled`]. The database is asked with:
+///
+///  * Postgres: a cancel request, sent on a connection of its own. The connection of the query
+///    can be used right away afterwards.
+///
+///  * MySQL: `KILL QUERY`, run on a connection of its own with the same URL. The connection of
+///    the query can be used right away afterwards.
+///
+///  * SQLite: `sqlite3_interrupt`. The connection can be used right away afterwards, but if the
+///    query was in an explicit transaction, SQLite may have rolled the transaction back.
+///
+/// ```rust,ignore
+/// let (query, handle) = sqlx::query("SELECT * FROM events").cancellable();
+///
+/// task::spawn(async move {
+///     cancel_clicked.await;
+///     handle.cancel();
+/// });
+///
+/// match query.map(Event::from_row).fetch_all(&mut conn).await {
+///     Err(sqlx::Error::Cancelled) => println!("the export was cancelled"),
+///     result => export(result?),
+/// }
+/// ```
+///
+/// A connection that was opened with `connect_with_stream` is asked to cancel the query through
+/// a connection to the address of its URL. If that fails, the query still stops at the next
+/// message that the database sends for it.
+///
+/// [`cancel`]: CancelHandle::cancel
+/// [`Error::Cancelled`]: crate::Error::Cancelled
+#[derive(Debug, Clone, Default)]
+pub struct CancelHandle(Arc<Shared>);
+
+#[derive(Debug, Default)]
+struct Shared {
+    cancelled: AtomicBool,
+    // the database has been asked to cancel the query, which is only done once
+    requested: AtomicBool,
+    waker: AtomicWaker,
+}
+
+impl CancelHandle {
+    /// Cancel the query of this handle. This does nothing if the query already completed or
+    /// was cancelled before.
+    pub fn cancel(&self) {
+        self.0.cancelled.store(true, Ordering::SeqCst);
+        self.0.waker.wake();
+    }
+
+    /// Returns `true` if [`cancel`](CancelHandle::cancel) has been called.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.cancelled.load(Ordering::SeqCst)
+    }
+
+    fn cancelled(&self) -> Cancelled<'_> {
+        Cancelled(self)
+    }
+}
+
+// Resolves once the handle is cancelled
+struct Cancelled<'h>(&'h CancelHandle);
+
+impl Future for Cancelled<'_> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.0 .0.waker.register(cx.waker());
+
+        if self.0.is_cancelled() {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+// Reads the next result of a cursor with [next], asking the database to cancel the query with
+// [request] if [handle] is cancelled first.
+//
+// [next] is still awaited after that, so that the stream is left between two messages (where
+// the cleanup of a query that was not read to the end can pick up from); whatever it returns,
+// the query resolves with [Error::Cancelled].
+pub(crate) async fn next_cancellable<T, N, R>(
+    handle: &CancelHandle,
+    next: N,
+    request: R,
+) -> crate::Result<T>
+where
+    N: Future<Output = crate::Result<T>>,
+    R: Future<Output = crate::Result<()>>,
+{
+    if handle.is_cancelled() {
+        request_once(handle, request).await;
+
+        return Err(crate::Error::Cancelled);
+    }
+
+    futures_util::pin_mut!(next);
+
+    match future::select(next, handle.cancelled()).await {
+        Either::Left((result, _)) => result,
+
+        Either::Right(((), next)) => {
+            request_once(handle, request).await;
+
+            let _ = next.await;
+
+            Err(crate::Error::Cancelled)
+        }
+    }
+}
+
+async fn request_once<R>(handle: &CancelHandle, request: R)
+where
+    R: Future<Output = crate::Result<()>>,
+{
+    if handle.0.requested.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    if let Err(error) = request.await {
+        log::warn!("failed to ask the database to cancel a query: {}", error);
+    }
+}
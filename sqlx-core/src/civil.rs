@@ -0,0 +1,368 @@
+//! Calendar arithmetic for [`SystemTime`] so that it can be used without `chrono` or `time`.
+//!
+//! All times are in UTC and use the proleptic Gregorian calendar, with year `0` being 1 BC.
+
+use std::convert::TryFrom;
+use std::fmt::{self, Display};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Returns the seconds since the UNIX epoch, which are negative for times before it, and
+/// the nanoseconds into that second; or `None` if the seconds do not fit in an `i64`.
+pub(crate) fn unix_timestamp(time: SystemTime) -> Option<(i64, u32)> {
+    let (secs, nanos) = match time.duration_since(UNIX_EPOCH) {
+        Ok(since) => (since.as_secs() as i128, since.subsec_nanos()),
+        Err(err) => match (
+            err.duration().as_secs() as i128,
+            err.duration().subsec_nanos(),
+        ) {
+            (secs, 0) => (-secs, 0),
+            // 0.25s before the epoch is 0.75s into the second that starts 1s before it
+            (secs, nanos) => (-secs - 1, 1_000_000_000 - nanos),
+        },
+    };
+
+    Some((i64::try_from(secs).ok()?, nanos))
+}
+
+/// The inverse of [`unix_timestamp`].
+pub(crate) fn from_unix_timestamp(secs: i64, nanos: u32) -> crate::Result<SystemTime> {
+    let time = if secs >= 0 {
+        UNIX_EPOCH.checked_add(Duration::new(secs as u64, nanos))
+    } else {
+        UNIX_EPOCH
+            .checked_sub(Duration::from_secs(-(secs as i128) as u64))
+            .and_then(|time| time.checked_add(Duration::from_nanos(nanos as u64)))
+    };
+
+    time.ok_or_else(|| {
+        decode_err!(
+            "{}s and {}ns from the UNIX epoch is out of range for SystemTime",
+            secs,
+            nanos
+        )
+    })
+}
+
+/// A broken-down UTC date and time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct DateTime {
+    pub(crate) year: i32,
+    pub(crate) month: u8,
+    pub(crate) day: u8,
+    pub(crate) hour: u8,
+    pub(crate) minute: u8,
+    pub(crate) second: u8,
+    pub(crate) nanosecond: u32,
+}
+
+impl DateTime {
+    /// Returns `None` if the year does not fit in an `i32`.
+    pub(crate) fn from_unix_timestamp(secs: i64, nanosecond: u32) -> Option<Self> {
+        let days = secs.div_euclid(SECONDS_PER_DAY);
+        let seconds = secs.rem_euclid(SECONDS_PER_DAY);
+
+        let (year, month, day) = civil_from_days(days);
+
+        Some(Self {
+            year: i32::try_from(year).ok()?,
+            month,
+            day,
+            hour: (seconds / 3600) as u8,
+            minute: (seconds / 60 % 60) as u8,
+            second: (seconds % 60) as u8,
+            nanosecond,
+        })
+    }
+
+    pub(crate) fn unix_timestamp(&self) -> (i64, u32) {
+        let days = days_from_civil(self.year, self.month, self.day);
+        let seconds = self.hour as i64 * 3600 + self.minute as i64 * 60 + self.second as i64;
+
+        (days * SECONDS_PER_DAY + seconds, self.nanosecond)
+    }
+
+    /// Parse an ISO 8601 timestamp such as `2020-03-01 12:30:00.25+01:00`.
+    ///
+    /// The date and time may be separated by a `T` instead of a space, the time and the offset
+    /// may both be left out and a trailing ` BC` (as used by Postgres) is understood. The result
+    /// is converted to UTC.
+    pub(crate) fn parse(s: &str) -> crate::Result<Self> {
+        let invalid = || decode_err!("invalid timestamp: {:?}", s);
+
+        let (rest, is_bc) = match s.strip_suffix(" BC") {
+            Some(rest) => (rest, true),
+            None => (s, false),
+        };
+
+        let (date, time) = match rest.find(&[' ', 'T'][..]) {
+            Some(at) => (&rest[..at], &rest[at + 1..]),
+            None => (rest, ""),
+        };
+
+        // the year may have more than 4 digits and may be negative
+        let (year_sign, date) = match date.strip_prefix('-') {
+            Some(date) => (-1, date),
+            None => (1, date),
+        };
+
+        let mut parts = date.splitn(3, '-');
+        let year: i32 = parse_number(parts.next()).ok_or_else(invalid)?;
+        let month: u8 = parse_number(parts.next()).ok_or_else(invalid)?;
+        let day: u8 = parse_number(parts.next()).ok_or_else(invalid)?;
+
+        let year = if is_bc { 1 - year } else { year_sign * year };
+
+        if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+            return Err(invalid());
+        }
+
+        let (time, offset) = match time.find(&['+', '-', 'Z'][..]) {
+            Some(at) => (&time[..at], parse_offset(&time[at..]).ok_or_else(invalid)?),
+            None => (time, 0),
+        };
+
+        let (clock, fraction) = match time.find('.') {
+            Some(at) => (&time[..at], Some(&time[at + 1..])),
+            None => (time, None),
+        };
+
+        let (hour, minute, second) = if clock.is_empty() {
+            (0, 0, 0)
+        } else {
+            let mut parts = clock.splitn(3, ':');
+
+            (
+                parse_number(parts.next()).ok_or_else(invalid)?,
+                parse_number(parts.next()).ok_or_else(invalid)?,
+                parse_number(parts.next()).ok_or_else(invalid)?,
+            )
+        };
+
+        if hour > 23 || minute > 59 || second > 59 {
+            return Err(invalid());
+        }
+
+        let nanosecond = match fraction {
+            Some(fraction) => parse_fraction(fraction).ok_or_else(invalid)?,
+            None => 0,
+        };
+
+        let local = Self {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            nanosecond,
+        };
+
+        let (secs, nanos) = local.unix_timestamp();
+
+        Self::from_unix_timestamp(secs - offset, nanos).ok_or_else(invalid)
+    }
+}
+
+impl Display for DateTime {
+    // 2020-03-01 12:30:00.25
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.year < 0 {
+            write!(f, "-{:04}", -(self.year as i64))?;
+        } else {
+            write!(f, "{:04}", self.year)?;
+        }
+
+        write!(
+            f,
+            "-{:02}-{:02} {:02}:{:02}:{:02}",
+            self.month, self.day, self.hour, self.minute, self.second
+        )?;
+
+        if self.nanosecond > 0 {
+            let fraction = format!("{:09}", self.nanosecond);
+
+            write!(f, ".{}", fraction.trim_end_matches('0'))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(s: Option<&str>) -> Option<T> {
+    let s = s?;
+
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    s.parse().ok()
+}
+
+// digits after the decimal point, as nanoseconds; anything past nanoseconds is truncated
+fn parse_fraction(s: &str) -> Option<u32> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let digits = &s[..s.len().min(9)];
+    let nanos: u32 = digits.parse().ok()?;
+
+    Some(nanos * 10u32.pow(9 - digits.len() as u32))
+}
+
+// `Z`, `+HH`, `-HH:MM`, `+HHMM` or `-HH:MM:SS`, in seconds east of UTC
+fn parse_offset(s: &str) -> Option<i64> {
+    if s == "Z" {
+        return Some(0);
+    }
+
+    let sign = match s.as_bytes().first()? {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+    };
+
+    let s = &s[1..];
+
+    let (hours, rest) = if s.len() >= 2 {
+        s.split_at(2)
+    } else {
+        return None;
+    };
+    let rest = rest.strip_prefix(':').unwrap_or(rest);
+
+    let (minutes, rest) = if rest.len() >= 2 {
+        rest.split_at(2)
+    } else {
+        ("0", rest)
+    };
+
+    let seconds = match rest.strip_prefix(':') {
+        Some(seconds) => seconds,
+        None if rest.is_empty() => "0",
+        None => return None,
+    };
+
+    let hours: i64 = parse_number(Some(hours))?;
+    let minutes: i64 = parse_number(Some(minutes))?;
+    let seconds: i64 = parse_number(Some(seconds))?;
+
+    Some(sign * (hours * 3600 + minutes * 60 + seconds))
+}
+
+fn is_leap_year(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// Days since 1970-01-01
+// http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: i32, month: u8, day: u8) -> i64 {
+    let year = year as i64 - (month <= 2) as i64;
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+
+    let month = month as i64;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+// http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: i64) -> (i64, u8, u8) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u8;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u8;
+    let year = year_of_era + era * 400 + (month <= 2) as i64;
+
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_round_trips_days() {
+        for &(days, ymd) in &[
+            (0, (1970, 1, 1)),
+            (-1, (1969, 12, 31)),
+            (10_957, (2000, 1, 1)),
+            (11_016, (2000, 2, 29)),
+            (-719_528, (0, 1, 1)),
+        ] {
+            assert_eq!(civil_from_days(days), ymd);
+            assert_eq!(days_from_civil(ymd.0 as i32, ymd.1, ymd.2), days);
+        }
+    }
+
+    #[test]
+    fn it_converts_times_before_the_epoch() {
+        let time = UNIX_EPOCH - Duration::from_millis(250);
+
+        assert_eq!(unix_timestamp(time), Some((-1, 750_000_000)));
+        assert_eq!(from_unix_timestamp(-1, 750_000_000).unwrap(), time);
+    }
+
+    // the range of a `SystemTime` is that of the `timespec` of the platform
+    #[test]
+    #[cfg(unix)]
+    fn it_converts_the_extremes_of_the_timestamps() {
+        let min = UNIX_EPOCH - Duration::from_secs(i64::MAX as u64) - Duration::from_secs(1);
+
+        assert_eq!(unix_timestamp(min), Some((i64::MIN, 0)));
+
+        let max = UNIX_EPOCH + Duration::new(i64::MAX as u64, 999_999_999);
+
+        assert_eq!(unix_timestamp(max), Some((i64::MAX, 999_999_999)));
+
+        // the years of these are far beyond those of an `i32`
+        assert_eq!(DateTime::from_unix_timestamp(i64::MIN, 0), None);
+        assert_eq!(DateTime::from_unix_timestamp(i64::MAX, 0), None);
+
+        let dt = DateTime::from_unix_timestamp(-62_167_219_200, 0).unwrap();
+
+        assert_eq!(dt.to_string(), "0000-01-01 00:00:00");
+    }
+
+    #[test]
+    fn it_parses_timestamps() {
+        let parse = |s| DateTime::parse(s).unwrap().to_string();
+
+        assert_eq!(parse("2020-03-01 12:30:00"), "2020-03-01 12:30:00");
+        assert_eq!(parse("2020-03-01T12:30:00.250Z"), "2020-03-01 12:30:00.25");
+        assert_eq!(
+            parse("2020-03-01 00:30:00.000001+01"),
+            "2020-02-29 23:30:00.000001"
+        );
+        assert_eq!(parse("2020-03-01 12:30:00-05:30"), "2020-03-01 18:00:00");
+        assert_eq!(parse("2020-03-01"), "2020-03-01 00:00:00");
+        assert_eq!(parse("0044-03-15 12:00:00+00 BC"), "-0043-03-15 12:00:00");
+        assert_eq!(parse("-0043-03-15 12:00:00"), "-0043-03-15 12:00:00");
+
+        assert!(DateTime::parse("2020-02-30 00:00:00").is_err());
+        assert!(DateTime::parse("2020-03-01 24:00:00").is_err());
+        assert!(DateTime::parse("yesterday").is_err());
+    }
+}
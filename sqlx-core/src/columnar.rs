@@ -0,0 +1,473 @@
+//! Column-major extraction of query results.
+//!
+//! See [`Query::fetch_columnar`](crate::query::Query::fetch_columnar).
+
+use std::fmt::Display;
+
+use futures_core::future::BoxFuture;
+
+use crate::cursor::HasCursor;
+use crate::database::Database;
+
+/// The result of a query, stored column-by-column.
+///
+/// Each column is a single contiguous, typed buffer, suitable for handing off to a
+/// columnar format such as Apache Arrow.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnarResult {
+    columns: Vec<ColumnarColumn>,
+    len: usize,
+}
+
+impl ColumnarResult {
+    /// Returns the number of rows in the result.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the result has no rows.
+    ///
+    /// Note that the columns of a result without rows are not known; such a result
+    /// also has no columns.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the columns of the result, in the order they were selected.
+    pub fn columns(&self) -> &[ColumnarColumn] {
+        &self.columns
+    }
+
+    /// Returns the first column with the given name.
+    pub fn column(&self, name: &str) -> Option<&ColumnarColumn> {
+        self.columns
+            .iter()
+            .find(|column| column.name.as_deref() == Some(name))
+    }
+
+    /// Consumes the result, returning its columns.
+    pub fn into_columns(self) -> Vec<ColumnarColumn> {
+        self.columns
+    }
+}
+
+/// A single column of a [`ColumnarResult`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnarColumn {
+    name: Option<String>,
+    values: ColumnValues,
+}
+
+impl ColumnarColumn {
+    /// Returns the name of the column, if it has one.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the values of the column.
+    pub fn values(&self) -> &ColumnValues {
+        &self.values
+    }
+
+    /// Consumes the column, returning its values.
+    pub fn into_values(self) -> ColumnValues {
+        self.values
+    }
+}
+
+/// The values of a [`ColumnarColumn`].
+///
+/// Integers of any width are widened to `i64` and floats to `f64`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnValues {
+    /// Every value in the column was `NULL`, so its type could not be determined.
+    ///
+    /// Holds the number of values.
+    Null(usize),
+
+    Bool(PrimitiveValues<bool>),
+    Int(PrimitiveValues<i64>),
+    Float(PrimitiveValues<f64>),
+    Text(Vec<Option<String>>),
+    Bytes(Vec<Option<Vec<u8>>>),
+}
+
+impl ColumnValues {
+    /// Returns the number of values in the column.
+    pub fn len(&self) -> usize {
+        match self {
+            ColumnValues::Null(len) => *len,
+            ColumnValues::Bool(values) => values.len(),
+            ColumnValues::Int(values) => values.len(),
+            ColumnValues::Float(values) => values.len(),
+            ColumnValues::Text(values) => values.len(),
+            ColumnValues::Bytes(values) => values.len(),
+        }
+    }
+
+    /// Returns `true` if the column has no values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            ColumnValues::Null(_) => "NULL",
+            ColumnValues::Bool(_) => "boolean",
+            ColumnValues::Int(_) => "integer",
+            ColumnValues::Float(_) => "float",
+            ColumnValues::Text(_) => "text",
+            ColumnValues::Bytes(_) => "bytes",
+        }
+    }
+}
+
+/// A buffer of fixed-width values with a validity bitmap.
+///
+/// The slot of a `NULL` value holds `T::default()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveValues<T> {
+    values: Vec<T>,
+    validity: Bitmap,
+}
+
+impl<T: Copy + Default> PrimitiveValues<T> {
+    fn with_nulls(len: usize) -> Self {
+        Self {
+            values: vec![T::default(); len],
+            validity: Bitmap::with_unset(len),
+        }
+    }
+
+    fn push(&mut self, value: Option<T>) {
+        self.validity.push(value.is_some());
+        self.values.push(value.unwrap_or_default());
+    }
+
+    /// Returns the value at `index`; or, `None` if it is `NULL` or out of bounds.
+    pub fn get(&self, index: usize) -> Option<T> {
+        if self.validity.is_set(index) {
+            Some(self.values[index])
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> PrimitiveValues<T> {
+    /// Returns the number of values (including `NULL`s).
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if there are no values.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the contiguous buffer of values.
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// Returns the validity bitmap; a set bit means the value is not `NULL`.
+    pub fn validity(&self) -> &Bitmap {
+        &self.validity
+    }
+
+    /// Consumes the buffer, returning the values and the validity bitmap.
+    pub fn into_parts(self) -> (Vec<T>, Bitmap) {
+        (self.values, self.validity)
+    }
+}
+
+/// A packed bitmap, least significant bit first (the layout used by Apache Arrow).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Bitmap {
+    bits: Vec<u8>,
+    len: usize,
+}
+
+impl Bitmap {
+    fn with_unset(len: usize) -> Self {
+        Self {
+            bits: vec![0; len.div_ceil(8)],
+            len,
+        }
+    }
+
+    fn push(&mut self, set: bool) {
+        let (byte, bit) = (self.len / 8, self.len % 8);
+
+        if bit == 0 {
+            self.bits.push(0);
+        }
+
+        if set {
+            self.bits[byte] |= 1 << bit;
+        }
+
+        self.len += 1;
+    }
+
+    /// Returns the number of bits.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if there are no bits.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` if the bit at `index` is set; `false` if it is unset or out of bounds.
+    pub fn is_set(&self, index: usize) -> bool {
+        index < self.len && (self.bits[index / 8] & (1 << (index % 8))) != 0
+    }
+
+    /// Returns the number of unset bits.
+    pub fn count_unset(&self) -> usize {
+        let set: u32 = self.bits.iter().map(|byte| byte.count_ones()).sum();
+
+        self.len - set as usize
+    }
+
+    /// Returns the packed bytes; the bits past [`len`](#method.len) are unset.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bits
+    }
+}
+
+/// Accumulates the values of a single column while a result is being read.
+#[doc(hidden)]
+pub struct ColumnBuilder {
+    name: Option<String>,
+    values: ColumnValues,
+}
+
+impl ColumnBuilder {
+    pub(crate) fn new(name: Option<String>) -> Self {
+        Self {
+            name,
+            values: ColumnValues::Null(0),
+        }
+    }
+
+    fn finish(self) -> ColumnarColumn {
+        ColumnarColumn {
+            name: self.name,
+            values: self.values,
+        }
+    }
+
+    fn mismatch(&self, kind: &str) -> crate::Error {
+        decode_err!(
+            "column {} changed type from {} to {} while building a columnar result",
+            self.display_name(),
+            self.values.kind(),
+            kind,
+        )
+    }
+
+    fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => format!("{:?}", name),
+            None => "<unnamed>".to_owned(),
+        }
+    }
+
+    /// The error to return for a column whose SQL type has no columnar representation.
+    pub fn unsupported(&self, type_info: impl Display) -> crate::Error {
+        decode_err!(
+            "column {} has SQL type {} which is not supported in a columnar result; \
+             supported types are booleans, integers, floats, text and bytes",
+            self.display_name(),
+            type_info,
+        )
+    }
+
+    pub fn push_null(&mut self) {
+        match &mut self.values {
+            ColumnValues::Null(len) => *len += 1,
+            ColumnValues::Bool(values) => values.push(None),
+            ColumnValues::Int(values) => values.push(None),
+            ColumnValues::Float(values) => values.push(None),
+            ColumnValues::Text(values) => values.push(None),
+            ColumnValues::Bytes(values) => values.push(None),
+        }
+    }
+
+    pub fn push_bool(&mut self, value: bool) -> crate::Result<()> {
+        if let ColumnValues::Null(len) = self.values {
+            self.values = ColumnValues::Bool(PrimitiveValues::with_nulls(len));
+        }
+
+        match &mut self.values {
+            ColumnValues::Bool(values) => values.push(Some(value)),
+            _ => return Err(self.mismatch("boolean")),
+        }
+
+        Ok(())
+    }
+
+    pub fn push_int(&mut self, value: i64) -> crate::Result<()> {
+        if let ColumnValues::Null(len) = self.values {
+            self.values = ColumnValues::Int(PrimitiveValues::with_nulls(len));
+        }
+
+        match &mut self.values {
+            ColumnValues::Int(values) => values.push(Some(value)),
+            _ => return Err(self.mismatch("integer")),
+        }
+
+        Ok(())
+    }
+
+    pub fn push_float(&mut self, value: f64) -> crate::Result<()> {
+        if let ColumnValues::Null(len) = self.values {
+            self.values = ColumnValues::Float(PrimitiveValues::with_nulls(len));
+        }
+
+        match &mut self.values {
+            ColumnValues::Float(values) => values.push(Some(value)),
+            _ => return Err(self.mismatch("float")),
+        }
+
+        Ok(())
+    }
+
+    pub fn push_text(&mut self, value: &str) -> crate::Result<()> {
+        if let ColumnValues::Null(len) = self.values {
+            self.values = ColumnValues::Text(vec![None; len]);
+        }
+
+        match &mut self.values {
+            ColumnValues::Text(values) => values.push(Some(value.to_owned())),
+            _ => return Err(self.mismatch("text")),
+        }
+
+        Ok(())
+    }
+
+    pub fn push_bytes(&mut self, value: &[u8]) -> crate::Result<()> {
+        if let ColumnValues::Null(len) = self.values {
+            self.values = ColumnValues::Bytes(vec![None; len]);
+        }
+
+        match &mut self.values {
+            ColumnValues::Bytes(values) => values.push(Some(value.to_owned())),
+            _ => return Err(self.mismatch("bytes")),
+        }
+
+        Ok(())
+    }
+}
+
+/// A database driver that can read its rows straight into a [`ColumnarResult`].
+#[doc(hidden)]
+pub trait Columnar: Database {
+    /// Reads every row of `cursor` into a [`ColumnarResult`], decoding each value straight from
+    /// the buffers of the connection instead of building a `Row` for it.
+    fn fetch_columnar<'c, 'q>(
+        cursor: <Self as HasCursor<'c, 'q>>::Cursor,
+    ) -> BoxFuture<'c, crate::Result<ColumnarResult>>
+    where
+        'q: 'c;
+}
+
+/// Builds a [`ColumnarResult`] one row at a time.
+pub(crate) struct ColumnarResultBuilder {
+    columns: Option<Vec<ColumnBuilder>>,
+    len: usize,
+}
+
+impl ColumnarResultBuilder {
+    pub(crate) fn new() -> Self {
+        Self {
+            columns: None,
+            len: 0,
+        }
+    }
+
+    /// Starts a row of `len` values, returning the builders to push them onto.
+    ///
+    /// The columns are named by `names` on the first row; every later row must have as many
+    /// values.
+    pub(crate) fn push_row(
+        &mut self,
+        len: usize,
+        names: impl FnOnce() -> Vec<Option<String>>,
+    ) -> crate::Result<&mut [ColumnBuilder]> {
+        let columns = self
+            .columns
+            .get_or_insert_with(|| names().into_iter().map(ColumnBuilder::new).collect());
+
+        if columns.len() != len {
+            return Err(decode_err!(
+                "a row of {} values followed rows of {} while building a columnar result",
+                len,
+                columns.len(),
+            ));
+        }
+
+        self.len += 1;
+
+        Ok(columns)
+    }
+
+    pub(crate) fn finish(self) -> ColumnarResult {
+        ColumnarResult {
+            columns: self
+                .columns
+                .unwrap_or_default()
+                .into_iter()
+                .map(ColumnBuilder::finish)
+                .collect(),
+            len: self.len,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_builds_validity_bitmaps() {
+        let mut column = ColumnBuilder::new(Some("id".to_owned()));
+
+        column.push_null();
+        column.push_int(10).unwrap();
+        column.push_null();
+
+        for i in 0..8 {
+            column.push_int(i).unwrap();
+        }
+
+        let values = match column.finish().into_values() {
+            ColumnValues::Int(values) => values,
+            values => panic!("unexpected column values: {:?}", values),
+        };
+
+        assert_eq!(values.len(), 11);
+        assert_eq!(values.get(0), None);
+        assert_eq!(values.get(1), Some(10));
+        assert_eq!(values.get(2), None);
+        assert_eq!(values.get(10), Some(7));
+        assert_eq!(values.values()[..3], [0, 10, 0]);
+        assert_eq!(values.validity().count_unset(), 2);
+        assert_eq!(values.validity().as_bytes(), &[0b1111_1010, 0b0000_0111]);
+    }
+
+    #[test]
+    fn it_errors_on_type_changes() {
+        let mut column = ColumnBuilder::new(Some("id".to_owned()));
+
+        column.push_int(1).unwrap();
+
+        let err = column.push_text("one").unwrap_err().to_string();
+
+        assert!(err.contains("\"id\""), "{}", err);
+        assert!(err.contains("from integer to text"), "{}", err);
+    }
+}
@@ -5,7 +5,9 @@ use std::convert::TryInto;
 use futures_core::future::BoxFuture;
 
 use crate::executor::Executor;
+use crate::logging::LogSettings;
 use crate::pool::{Pool, PoolConnection};
+use crate::statement::SqlRedaction;
 use crate::transaction::Transaction;
 use crate::url::Url;
 
@@ -22,36 +24,149 @@ where
 {
     /// Starts a new transaction.
     ///
-    /// Wraps this connection in [`Transaction`] to manage the transaction lifecycle. To get the
-    /// original connection back, explicitly [`commit`] or [`rollback`] and this connection will
-    /// be returned.
+    /// Returns a [`Transaction`] to manage the transaction lifecycle, which borrows this
+    /// connection until it is explicitly [`commit`]ted, [`rollback`]ed, or dropped (which rolls
+    /// it back).
     ///
     /// ```rust,ignore
     /// let mut tx = conn.begin().await?;
-    /// // conn is now inaccessible as its wrapped in a transaction
+    /// // conn is now borrowed by the transaction
     ///
-    /// let conn = tx.commit().await?;
-    /// // conn is back now and out of the transaction
+    /// tx.commit().await?;
+    /// // conn can be used again and is out of the transaction
     /// ```
     ///
     /// [`commit`]: crate::transaction::Transaction::commit
     /// [`rollback`]: crate::transaction::Transaction::rollback
-    fn begin(self) -> BoxFuture<'static, crate::Result<Transaction<Self>>>
+    fn begin(&mut self) -> BoxFuture<'_, crate::Result<Transaction<'_, Self::Database>>>
     where
-        Self: Sized,
-    {
-        Box::pin(Transaction::new(0, self))
-    }
+        Self: Sized;
 
     /// Explicitly close this database connection.
     ///
     /// This method is **not required** for safe and consistent operation. However, it is
     /// recommended to call it instead of letting a connection `drop` as the database server
     /// will be faster at cleaning up resources.
+    ///
+    /// A connection that is dropped still tries to tell the server that it is going away, but
+    /// only if that can be written to the socket immediately; it will never block or wait on
+    /// the runtime, which may be shutting down. Only `close().await` guarantees that the server
+    /// is told, so it does not log the disconnect as a connection reset.
     fn close(self) -> BoxFuture<'static, crate::Result<()>>;
 
     /// Checks if a connection to the database is still valid.
     fn ping(&mut self) -> BoxFuture<crate::Result<()>>;
+
+    /// Returns how this connection has used its cache of prepared statements since it was
+    /// established or the counters were last [reset](#tymethod.reset_statement_cache_metrics).
+    ///
+    /// For a [`PoolConnection`], this only counts what happened since the connection was acquired;
+    /// the counters are added to [`Pool::statement_cache_metrics`] when it is returned to the
+    /// pool.
+    ///
+    /// A connection that does not cache statements returns all zeroes (the default).
+    ///
+    /// [`Pool::statement_cache_metrics`]: crate::pool::Pool::statement_cache_metrics
+    fn statement_cache_metrics(&self) -> StatementCacheMetrics {
+        StatementCacheMetrics::default()
+    }
+
+    /// Resets all counters of [`statement_cache_metrics`](#method.statement_cache_metrics)
+    /// to zero.
+    fn reset_statement_cache_metrics(&mut self) {}
+
+    /// Replaces how the statements run on this connection are logged.
+    ///
+    /// For a [`PoolConnection`], this lasts until the connection is closed, including after it
+    /// has been returned to the pool; see [`Builder::log_settings`] to set this for every
+    /// connection of a pool instead.
+    ///
+    /// A connection that does not log statements ignores this (the default).
+    ///
+    /// [`Builder::log_settings`]: crate::pool::Builder::log_settings
+    fn set_log_settings(&mut self, settings: LogSettings) {
+        let _ = settings;
+    }
+
+    /// Replaces how much of the SQL of the statements run on this connection is kept for
+    /// [`Statement::sql`], and so for the errors that include it.
+    ///
+    /// This applies to the statements prepared from now on; those already in the cache of
+    /// prepared statements keep their SQL as it was. For a [`PoolConnection`], this lasts until
+    /// the connection is closed; see [`Builder::sql_redaction`] to set this for every connection
+    /// of a pool instead.
+    ///
+    /// A connection that does not keep the SQL of its statements ignores this (the default).
+    ///
+    /// [`Statement::sql`]: crate::statement::Statement::sql
+    /// [`Builder::sql_redaction`]: crate::pool::Builder::sql_redaction
+    fn set_sql_redaction(&mut self, redaction: SqlRedaction) {
+        let _ = redaction;
+    }
+
+    // Sends `statement` to roll back a transaction that was dropped without being finished,
+    // ahead of whatever next runs on this connection; its result is ignored.
+    //
+    // Returns `false` if this cannot be done (the default), in which case a dropped transaction
+    // closes its connection if it was acquired from a pool, or is left for the server to roll
+    // back when the connection is closed otherwise
+    #[doc(hidden)]
+    fn queue_rollback(&mut self, statement: &str) -> bool {
+        let _ = statement;
+        false
+    }
+
+    // Sends a statement queued by [queue_rollback] right away, without waiting, so it runs on
+    // the server before the connection is next used; its result is still read then.
+    //
+    // Returns `false` if it could not all be sent, which leaves the connection unusable
+    #[doc(hidden)]
+    fn flush_queued_now(&mut self) -> bool {
+        true
+    }
+}
+
+/// Counters of how a connection has used its cache of prepared statements.
+///
+/// Only queries with arguments (such as any made with [`query`] or [`query!`]) are prepared
+/// and cached; queries without arguments given as a plain `&str` are not counted as either
+/// hits or misses.
+///
+/// [`query`]: crate::query::query
+/// [`query!`]: https://docs.rs/sqlx/*/sqlx/macro.query.html
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct StatementCacheMetrics {
+    /// Queries that were run with a statement already in the cache.
+    pub hits: u64,
+
+    /// Queries for which a new statement was prepared (and cached).
+    pub misses: u64,
+
+    /// Statements that were dropped from the cache to make room for others.
+    ///
+    /// The caches are not bounded so, as of now, this is always `0`.
+    pub evictions: u64,
+
+    /// Times that requests were sent to the database server, most of which are then waited on
+    /// for a response.
+    ///
+    /// For a connection opened on its own, this includes establishing it; a [`PoolConnection`]
+    /// (and so the pool) only counts what happened while it was checked out. It is always `0`
+    /// for SQLite, which runs in-process.
+    pub round_trips: u64,
+}
+
+impl StatementCacheMetrics {
+    /// Returns the ratio of hits to all queries that used the cache, if there were any.
+    pub fn hit_rate(&self) -> Option<f64> {
+        let total = self.hits + self.misses;
+
+        if total == 0 {
+            return None;
+        }
+
+        Some(self.hits as f64 / total as f64)
+    }
 }
 
 /// Represents a type that can directly establish a new connection.
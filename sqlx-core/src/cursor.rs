@@ -66,6 +66,94 @@ where
     fn next<'cur>(
         &'cur mut self,
     ) -> BoxFuture<'cur, crate::Result<Option<<Self::Database as HasRow<'cur>>::Row>>>;
+
+    /// Creates a future that attempts to resolve the next result in the cursor, which is either
+    /// the next row or, once a statement has returned all of its rows, the result of the
+    /// statement (such as the number of rows it affected).
+    ///
+    /// This is meant for queries of several statements, which may mix ones that return rows and
+    /// ones that do not; see [`Executor::fetch_many`] to map
+    /// each row as it is read.
+    ///
+    /// [`Executor::fetch_many`]: crate::executor::Executor::fetch_many
+    #[allow(clippy::type_complexity)]
+    fn next_many<'cur>(
+        &'cur mut self,
+    ) -> BoxFuture<
+        'cur,
+        crate::Result<
+            Option<
+                Either<
+                    <Self::Database as Database>::QueryResult,
+                    <Self::Database as HasRow<'cur>>::Row,
+                >,
+            >,
+        >,
+    >;
+}
+
+/// A result of [`Cursor::next_many`]: the result of a statement that has completed (`Left`), or
+/// a row returned by it (`Right`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Either<L, R> {
+    Left(L),
+    Right(R),
+}
+
+impl<L, R> Either<L, R> {
+    /// Returns the value if this is `Left`.
+    pub fn left(self) -> Option<L> {
+        match self {
+            Either::Left(left) => Some(left),
+            Either::Right(_) => None,
+        }
+    }
+
+    /// Maps the value with `f` if this is `Left`.
+    pub fn map_left<T>(self, f: impl FnOnce(L) -> T) -> Either<T, R> {
+        match self {
+            Either::Left(left) => Either::Left(f(left)),
+            Either::Right(right) => Either::Right(right),
+        }
+    }
+
+    /// Returns the value if this is `Right`.
+    pub fn right(self) -> Option<R> {
+        match self {
+            Either::Left(_) => None,
+            Either::Right(right) => Some(right),
+        }
+    }
+}
+
+// Reads the rows that are left once the caller has the one it wanted, as `fetch_optional` and
+// `fetch_one` do, so the connection is ready for the next query as soon as they return. This
+// also surfaces an error that the database raises for a later row.
+//
+// The future is boxed so it can be awaited inside of another boxed future, as
+// `make_query_as!` does; as an `async fn` this runs into
+// https://github.com/rust-lang/rust/issues/64552 there
+pub(crate) fn discard_remaining<'a, 'c, 'q, C>(
+    cursor: &'a mut C,
+) -> BoxFuture<'a, crate::Result<()>>
+where
+    C: Cursor<'c, 'q>,
+    'c: 'a,
+    'q: 'a,
+{
+    Box::pin(async move {
+        let mut discarded = 0_u64;
+
+        while cursor.next().await?.is_some() {
+            discarded += 1;
+        }
+
+        if discarded > 0 {
+            log::debug!("discarded {} row(s) after the first one", discarded);
+        }
+
+        Ok(())
+    })
 }
 
 // Prevent users from implementing the `Row` trait.
@@ -37,6 +37,14 @@ where
     /// however, **SQLite** uses `Vec<SqliteArgumentValue>`.
     type RawBuffer: Default;
 
+    /// The concrete type of the result of a statement that has finished running, such as the
+    /// number of rows it affected.
+    ///
+    /// This is returned for each statement by [`Executor::fetch_many`].
+    ///
+    /// [`Executor::fetch_many`]: crate::executor::Executor::fetch_many
+    type QueryResult: Debug + Default + Send + Unpin;
+
     /// The concrete `DatabaseError` type used to report errors from the database.
     type Error: DatabaseError + Send + Sync;
 }
@@ -16,6 +16,11 @@ where
     /// The expected types for the parameters of the query.
     pub param_types: Box<[Option<DB::TypeInfo>]>,
 
+    /// The names of the parameters of the query, for those that have one.
+    ///
+    /// Only SQLite has named parameters; the name includes the prefix, e.g. `:id` or `$id`.
+    pub param_names: Box<[Option<Box<str>>]>,
+
     /// The type and table information, if any for the results of the query.
     pub result_columns: Box<[Column<DB>]>,
 }
@@ -29,6 +34,7 @@ where
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("Describe")
             .field("param_types", &self.param_types)
+            .field("param_names", &self.param_names)
             .field("result_columns", &self.result_columns)
             .finish()
     }
@@ -2,6 +2,7 @@
 
 use crate::database::Database;
 use crate::types::Type;
+use std::error::Error as StdError;
 use std::mem;
 
 /// The return type of [Encode::encode].
@@ -23,6 +24,18 @@ where
     /// Writes the value of `self` into `buf` in the expected format for the database.
     fn encode(&self, buf: &mut DB::RawBuffer);
 
+    /// Returns an error if the value cannot be encoded for the database; e.g., a time outside of
+    /// the range the database supports.
+    ///
+    /// This is called as the value is bound to a query. If it fails, the query returns the error
+    /// as an [`Error::Encode`] when it is run, without being sent, and the result of
+    /// [`encode`](#tymethod.encode) is not used.
+    ///
+    /// [`Error::Encode`]: crate::Error::Encode
+    fn check(&self) -> Result<(), Box<dyn StdError + Send + Sync>> {
+        Ok(())
+    }
+
     fn encode_nullable(&self, buf: &mut DB::RawBuffer) -> IsNull {
         self.encode(buf);
 
@@ -44,6 +57,10 @@ where
         (*self).encode(buf)
     }
 
+    fn check(&self) -> Result<(), Box<dyn StdError + Send + Sync>> {
+        (*self).check()
+    }
+
     fn encode_nullable(&self, buf: &mut DB::RawBuffer) -> IsNull {
         (*self).encode_nullable(buf)
     }
@@ -64,6 +81,10 @@ where
         let _ = self.encode_nullable(buf);
     }
 
+    fn check(&self) -> Result<(), Box<dyn StdError + Send + Sync>> {
+        self.as_ref().map_or(Ok(()), Encode::check)
+    }
+
     fn encode_nullable(&self, buf: &mut DB::RawBuffer) -> IsNull {
         if let Some(self_) = self {
             self_.encode(buf);
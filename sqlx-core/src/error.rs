@@ -58,6 +58,11 @@ pub enum Error {
     /// A [`Pool::acquire`] timed out due to connections not becoming available or
     /// because another task encountered too many errors while trying to open a new connection.
     ///
+    /// This is `None` if the time was spent waiting for a connection to be returned to the pool
+    /// (or for room to open a new one). If the time ran out while the pool was opening a new
+    /// connection instead, this is the error of the last failed attempt or, if there was none,
+    /// an [`io::ErrorKind::TimedOut`] error.
+    ///
     /// [`Pool::acquire`]: crate::pool::Pool::acquire
     PoolTimedOut(Option<Box<dyn StdError + Send + Sync>>),
 
@@ -67,12 +72,50 @@ pub enum Error {
     /// [`Pool::close`]: crate::pool::Pool::close
     PoolClosed,
 
+    /// The query was cancelled with its [`CancelHandle`].
+    ///
+    /// [`CancelHandle`]: crate::cancel::CancelHandle
+    Cancelled,
+
     /// An error occurred while attempting to setup TLS.
     /// This should only be returned from an explicit ask for TLS.
     Tls(Box<dyn StdError + Send + Sync>),
 
+    /// Connecting kept failing with a transient error (e.g., the connection was refused) until
+    /// there were no more retries left; see the `connect_retries` parameter of the connection URL
+    /// (or `PgConnectOptions::connect_retries` and `MySqlConnectOptions::connect_retries`).
+    ///
+    /// `error` is the error of the last attempt.
+    ConnectRetriesExhausted { attempts: u32, error: Box<Error> },
+
     /// An error occurred decoding data received from the database.
     Decode(Box<dyn StdError + Send + Sync>),
+
+    /// A value bound to the query cannot be encoded for the database; e.g., a time outside of
+    /// the range the database supports. The query was not sent.
+    ///
+    /// See [`Encode::check`].
+    ///
+    /// [`Encode::check`]: crate::encode::Encode::check
+    Encode(Box<dyn StdError + Send + Sync>),
+
+    /// A value of a column could not be decoded (during [`Row::get`]) or could not be converted
+    /// into the type it was decoded for; e.g., by a field of a derived [`FromRow`] with
+    /// `#[sqlx(try_from = "..")]`.
+    ///
+    /// `index` names the column and, if it differs, the field. `sql` is the SQL of the
+    /// [statement] the row was returned by, if known, as kept by its [`SqlRedaction`]; only its
+    /// first 500 bytes are displayed.
+    ///
+    /// [`Row::get`]: crate::row::Row::get
+    /// [`FromRow`]: crate::row::FromRow
+    /// [statement]: crate::statement::Statement::sql
+    /// [`SqlRedaction`]: crate::statement::SqlRedaction
+    ColumnDecode {
+        index: Box<str>,
+        source: Box<dyn StdError + Send + Sync>,
+        sql: Option<Box<str>>,
+    },
 }
 
 impl Error {
@@ -107,7 +150,10 @@ impl StdError for Error {
             Error::UrlParse(error) => Some(error),
             Error::PoolTimedOut(Some(error)) => Some(&**error),
             Error::Decode(error) => Some(&**error),
+            Error::Encode(error) => Some(&**error),
+            Error::ColumnDecode { source, .. } => Some(&**source),
             Error::Tls(error) => Some(&**error),
+            Error::ConnectRetriesExhausted { error, .. } => Some(&**error),
             Error::Database(error) => Some(error.as_ref_err()),
 
             _ => None,
@@ -115,6 +161,9 @@ impl StdError for Error {
     }
 }
 
+// The length of SQL beyond which [Error::ColumnDecode] displays only its start
+const MAX_DISPLAYED_SQL_LEN: usize = 500;
+
 impl Display for Error {
     // IntellijRust does not understand that [non_exhaustive] applies only for downstream crates
     // noinspection RsMatchCheck
@@ -126,6 +175,21 @@ impl Display for Error {
 
             Error::Decode(error) => write!(f, "{}", error),
 
+            Error::Encode(error) => write!(f, "{}", error),
+
+            Error::ColumnDecode { index, source, sql } => {
+                write!(f, "error decoding column {}: {}", index, source)?;
+
+                if let Some(sql) = sql {
+                    write!(f, "\n\nin the results of: ")?;
+
+                    // the SQL may be of any length; show no more than its start
+                    f.write_str(&crate::statement::truncate(sql, MAX_DISPLAYED_SQL_LEN))?;
+                }
+
+                Ok(())
+            }
+
             Error::Database(error) => Display::fmt(error, f),
 
             Error::RowNotFound => f.write_str("found no row when we expected at least one"),
@@ -143,7 +207,7 @@ impl Display for Error {
             Error::Protocol(ref err) => f.write_str(err),
 
             Error::PoolTimedOut(Some(ref err)) => {
-                write!(f, "timed out while waiting for an open connection: {}", err)
+                write!(f, "timed out while establishing a new connection: {}", err)
             }
 
             Error::PoolTimedOut(None) => {
@@ -152,7 +216,18 @@ impl Display for Error {
 
             Error::PoolClosed => f.write_str("attempted to acquire a connection on a closed pool"),
 
+            Error::Cancelled => f.write_str("the query was cancelled"),
+
             Error::Tls(ref err) => write!(f, "error during TLS upgrade: {}", err),
+
+            Error::ConnectRetriesExhausted {
+                attempts,
+                ref error,
+            } => write!(
+                f,
+                "failed to connect after {} attempts: {}",
+                attempts, error
+            ),
         }
     }
 }
@@ -385,3 +460,35 @@ impl Display for UnexpectedNullError {
 }
 
 impl StdError for UnexpectedNullError {}
+
+#[cfg(test)]
+mod tests {
+    use super::Error;
+
+    fn column_decode(sql: String) -> Error {
+        Error::ColumnDecode {
+            index: "\"id\"".into(),
+            source: "invalid".into(),
+            sql: Some(sql.into_boxed_str()),
+        }
+    }
+
+    #[test]
+    fn it_displays_the_start_of_long_sql() {
+        let short = format!("SELECT {}", "1, ".repeat(100));
+
+        assert_eq!(
+            column_decode(short.clone()).to_string(),
+            format!(
+                "error decoding column \"id\": invalid\n\nin the results of: {}",
+                short
+            )
+        );
+
+        // 'é' is 2 bytes, so byte 500 is not on a character boundary
+        let long = format!("SELECT 'a{}'", "é".repeat(1000));
+        let message = column_decode(long).to_string();
+
+        assert!(message.ends_with("é ... (1511 more bytes)"), "{}", message);
+    }
+}
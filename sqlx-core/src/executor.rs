@@ -1,8 +1,13 @@
+use async_stream::try_stream;
 use futures_core::future::BoxFuture;
+use futures_core::stream::BoxStream;
 
-use crate::cursor::HasCursor;
+use crate::cancel::CancelHandle;
+use crate::cursor::{Cursor, Either, HasCursor};
 use crate::database::Database;
 use crate::describe::Describe;
+use crate::logging::LevelFilter;
+use crate::query::TryMapRow;
 
 /// A type that contains or can provide a database connection to use for executing queries
 /// against the database.
@@ -14,6 +19,38 @@ use crate::describe::Describe;
 /// Implementations are provided for [`&Pool`](struct.Pool.html),
 /// [`&mut PoolConnection`](struct.PoolConnection.html),
 /// and [`&mut Connection`](trait.Connection.html).
+///
+/// ### Queries on a connection run one at a time
+/// The methods on this trait take `&mut self` and the returned future (or [`Cursor`]) borrows
+/// the executor until it is done. On a single connection, the next query can therefore only
+/// be started once the previous one has completed; trying to queue several and await them
+/// together does not compile:
+///
+/// ```rust,compile_fail,E0499
+/// # use sqlx_core::executor::Executor;
+/// # async fn example(conn: &mut impl Executor) -> sqlx_core::Result<()> {
+/// let a = conn.execute("INSERT INTO todos (text) VALUES ('a')");
+/// let b = conn.execute("INSERT INTO todos (text) VALUES ('b')");
+/// //      ^^^^ error[E0499]: cannot borrow `*conn` as mutable more than once at a time
+///
+/// a.await?;
+/// b.await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Await each query before starting the next or, to send several statements in one round trip,
+/// pass them to [`execute`] as a single string of statements separated by `;` (without any
+/// bind parameters). Those are run in order. Postgres and MySQL return the number of rows
+/// affected by all of them, while SQLite returns that of the last `INSERT`, `UPDATE` or `DELETE`
+/// among them.
+///
+/// To run queries concurrently, run them on a [`Pool`](struct.Pool.html) instead; `&Pool`
+/// can be shared by any number of queries in flight and each of them acquires a connection of
+/// its own, so there is no order between them.
+///
+/// [`Cursor`]: crate::cursor::Cursor
+/// [`execute`]: #tymethod.execute
 pub trait Executor
 where
     Self: Send,
@@ -43,6 +80,65 @@ where
     where
         E: Execute<'q, Self::Database>;
 
+    /// Executes a query of several statements for all of their results.
+    ///
+    /// Returns a [`Stream`] of, for each statement in turn, the rows it returned (if any) as
+    /// mapped by `mapper` (`Right`), followed by its [`QueryResult`] (`Left`). The number of
+    /// rows affected in that is the number of rows inserted, updated or deleted by the
+    /// statement, and `0` for any other statement (including a `SELECT`), on every database.
+    ///
+    /// A row borrows the connection it was read from, so it is mapped as it is read.
+    ///
+    /// If a statement fails, its error is the last item of the stream and the statements after
+    /// it are not run; the connection can be used again afterwards.
+    ///
+    /// ```rust,ignore
+    /// let mut results = conn.fetch_many(
+    ///     "UPDATE todos SET done = TRUE WHERE id = 1; SELECT id, text FROM todos",
+    ///     |row: PgRow| row.try_get::<String, _>("text"),
+    /// );
+    ///
+    /// while let Some(result) = results.try_next().await? {
+    ///     match result {
+    ///         Either::Left(result) => println!("{} rows affected", result.rows_affected()),
+    ///         Either::Right(text) => println!("{}", text),
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// To read the rows themselves instead, use [`Cursor::next_many`] on the cursor returned
+    /// by [`fetch`](#tymethod.fetch).
+    ///
+    /// [`Stream`]: futures_core::stream::Stream
+    /// [`QueryResult`]: crate::database::Database::QueryResult
+    /// [`Cursor::next_many`]: crate::cursor::Cursor::next_many
+    #[allow(clippy::type_complexity)]
+    fn fetch_many<'e, 'q: 'e, E, F>(
+        &'e mut self,
+        query: E,
+        mut mapper: F,
+    ) -> BoxStream<'e, crate::Result<Either<<Self::Database as Database>::QueryResult, F::Output>>>
+    where
+        E: Execute<'q, Self::Database> + 'e,
+        F: TryMapRow<Self::Database> + Send + 'e,
+        F::Output: Send + 'e,
+    {
+        Box::pin(try_stream! {
+            let mut cursor = self.fetch(query);
+
+            while let Some(next) = cursor.next_many().await? {
+                match next {
+                    Either::Left(result) => yield Either::Left(result),
+
+                    Either::Right(row) => {
+                        let mapped = mapper.try_map_row(row)?;
+                        yield Either::Right(mapped);
+                    }
+                }
+            }
+        })
+    }
+
     /// Prepare the SQL query and return type information about its parameters
     /// and results.
     ///
@@ -83,6 +179,19 @@ where
     /// Returns the query string, without any parameters replaced.
     #[doc(hidden)]
     fn query_string(&self) -> &'q str;
+
+    /// Returns the level that this query is to be logged at instead of the default of the
+    /// connection, if any.
+    #[doc(hidden)]
+    fn log_level(&self) -> Option<LevelFilter> {
+        None
+    }
+
+    /// Returns the handle that this query can be cancelled with, if any.
+    #[doc(hidden)]
+    fn cancel_handle(&self) -> Option<&CancelHandle> {
+        None
+    }
 }
 
 impl<'q, DB> Execute<'q, DB> for &'q str
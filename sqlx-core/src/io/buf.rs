@@ -88,7 +88,7 @@ impl<'a> Buf<'a> for &'a [u8] {
 
     fn get_i64<T: ByteOrder>(&mut self) -> io::Result<i64> {
         let val = T::read_i64(*self);
-        self.advance(4);
+        self.advance(8);
 
         Ok(val)
     }
@@ -142,3 +142,27 @@ impl ToBuf for u8 {
         slice::from_ref(self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Buf;
+    use byteorder::{BigEndian, LittleEndian};
+
+    #[test]
+    fn it_advances_past_each_value() {
+        let mut buf: &[u8] = &[
+            0, 0, 0, 0, 0, 0, 0, 1, // i64
+            2, 0, 0, 0, // i32 (little endian)
+            0, 0, 0, 0, 0, 0, 0, 3, // u64
+            4, 0, // i16 (little endian)
+            5,
+        ];
+
+        assert_eq!(buf.get_i64::<BigEndian>().unwrap(), 1);
+        assert_eq!(buf.get_i32::<LittleEndian>().unwrap(), 2);
+        assert_eq!(buf.get_u64::<BigEndian>().unwrap(), 3);
+        assert_eq!(buf.get_i16::<LittleEndian>().unwrap(), 4);
+        assert_eq!(buf.get_u8().unwrap(), 5);
+        assert!(buf.is_empty());
+    }
+}
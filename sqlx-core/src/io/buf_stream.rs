@@ -5,6 +5,7 @@ use std::pin::Pin;
 use std::task::{Context, Poll};
 
 use futures_util::ready;
+use futures_util::task::noop_waker_ref;
 
 use crate::runtime::{AsyncRead, AsyncReadExt, AsyncWrite};
 
@@ -65,6 +66,20 @@ where
         }
     }
 
+    /// Write out the outgoing buffer if, and only if, the stream can take it right now.
+    ///
+    /// This never waits and so does not need a running executor, which makes it usable
+    /// from `Drop`. Returns `true` if the whole buffer was written and flushed; otherwise,
+    /// whatever was not written is discarded.
+    pub fn flush_now(&mut self) -> bool {
+        let mut cx = Context::from_waker(noop_waker_ref());
+
+        matches!(
+            Pin::new(&mut self.flush()).poll(&mut cx),
+            Poll::Ready(Ok(()))
+        )
+    }
+
     #[inline]
     pub fn consume(&mut self, cnt: usize) {
         self.rbuf_rindex += cnt;
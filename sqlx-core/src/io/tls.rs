@@ -3,10 +3,16 @@ use std::net::Shutdown;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
+use crate::retry::ResolveError;
 use crate::runtime::{AsyncRead, AsyncWrite, TcpStream};
 
 use self::Inner::*;
 
+/// A byte stream that was established outside of SQLx; e.g., a tunnel through a proxy.
+pub(crate) trait RawStream: AsyncRead + AsyncWrite + Send + Unpin + 'static {}
+
+impl<S> RawStream for S where S: AsyncRead + AsyncWrite + Send + Unpin + 'static {}
+
 pub struct MaybeTlsStream {
     inner: Inner,
 }
@@ -15,9 +21,12 @@ enum Inner {
     NotTls(TcpStream),
     #[cfg(all(feature = "postgres", unix))]
     UnixStream(crate::runtime::UnixStream),
+    Raw(Box<dyn RawStream>),
     #[cfg(feature = "tls")]
     Tls(async_native_tls::TlsStream<TcpStream>),
     #[cfg(feature = "tls")]
+    RawTls(async_native_tls::TlsStream<Box<dyn RawStream>>),
+    #[cfg(feature = "tls")]
     Upgrading,
 }
 
@@ -30,20 +39,31 @@ impl MaybeTlsStream {
         })
     }
     pub async fn connect(host: &str, port: u16) -> crate::Result<Self> {
-        let conn = TcpStream::connect((host, port)).await?;
+        let addrs = crate::runtime::lookup_host(host, port)
+            .await
+            .map_err(ResolveError::wrap)?;
+
+        let conn = TcpStream::connect(&*addrs).await?;
         Ok(Self {
             inner: Inner::NotTls(conn),
         })
     }
 
+    pub fn from_raw<S: RawStream>(stream: S) -> Self {
+        Self {
+            inner: Inner::Raw(Box::new(stream)),
+        }
+    }
+
     #[allow(dead_code)]
     pub fn is_tls(&self) -> bool {
         match self.inner {
             Inner::NotTls(_) => false,
             #[cfg(all(feature = "postgres", unix))]
             Inner::UnixStream(_) => false,
+            Inner::Raw(_) => false,
             #[cfg(feature = "tls")]
-            Inner::Tls(_) => true,
+            Inner::Tls(_) | Inner::RawTls(_) => true,
             #[cfg(feature = "tls")]
             Inner::Upgrading => false,
         }
@@ -56,18 +76,17 @@ impl MaybeTlsStream {
         host: &str,
         connector: async_native_tls::TlsConnector,
     ) -> crate::Result<()> {
-        let conn = match std::mem::replace(&mut self.inner, Upgrading) {
-            NotTls(conn) => conn,
+        self.inner = match std::mem::replace(&mut self.inner, Upgrading) {
+            NotTls(conn) => Tls(connector.connect(host, conn).await?),
+            Raw(conn) => RawTls(connector.connect(host, conn).await?),
             #[cfg(all(feature = "postgres", unix))]
             UnixStream(_) => {
                 return Err(tls_err!("TLS is not supported with unix domain sockets").into())
             }
-            Tls(_) => return Err(tls_err!("connection already upgraded").into()),
+            Tls(_) | RawTls(_) => return Err(tls_err!("connection already upgraded").into()),
             Upgrading => return Err(tls_err!("connection already failed to upgrade").into()),
         };
 
-        self.inner = Tls(connector.connect(host, conn).await?);
-
         Ok(())
     }
 
@@ -76,9 +95,14 @@ impl MaybeTlsStream {
             NotTls(ref conn) => conn.shutdown(how),
             #[cfg(all(feature = "postgres", unix))]
             UnixStream(ref conn) => conn.shutdown(how),
+            // there is no runtime-agnostic way to shut down an arbitrary stream; it is closed
+            // when the connection is dropped
+            Raw(_) => Ok(()),
             #[cfg(feature = "tls")]
             Tls(ref conn) => conn.get_ref().shutdown(how),
             #[cfg(feature = "tls")]
+            RawTls(_) => Ok(()),
+            #[cfg(feature = "tls")]
             // connection already closed
             Upgrading => Ok(()),
         }
@@ -91,9 +115,12 @@ macro_rules! forward_pin (
             NotTls(ref mut conn) => Pin::new(conn).$method($($arg),*),
             #[cfg(all(feature = "postgres", unix))]
             UnixStream(ref mut conn) => Pin::new(conn).$method($($arg),*),
+            Raw(ref mut conn) => Pin::new(conn).$method($($arg),*),
             #[cfg(feature = "tls")]
             Tls(ref mut conn) => Pin::new(conn).$method($($arg),*),
             #[cfg(feature = "tls")]
+            RawTls(ref mut conn) => Pin::new(conn).$method($($arg),*),
+            #[cfg(feature = "tls")]
             Upgrading => Err(io::Error::new(io::ErrorKind::Other, "connection broken; TLS upgrade failed")).into(),
         }
     )
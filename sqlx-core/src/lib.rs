@@ -26,6 +26,8 @@ pub mod error;
 #[macro_use]
 mod io;
 
+pub mod cancel;
+pub mod columnar;
 pub mod connection;
 pub mod cursor;
 pub mod database;
@@ -37,6 +39,12 @@ pub mod executor;
 pub mod transaction;
 mod url;
 
+#[cfg(any(feature = "mysql", feature = "postgres"))]
+mod retry;
+
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
+mod civil;
+
 #[macro_use]
 pub mod arguments;
 pub mod decode;
@@ -47,6 +55,7 @@ pub mod describe;
 pub mod encode;
 pub mod pool;
 pub mod query;
+pub mod statement;
 
 #[macro_use]
 pub mod query_as;
@@ -56,9 +65,8 @@ pub mod types;
 #[macro_use]
 pub mod row;
 
-#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 #[macro_use]
-mod logging;
+pub mod logging;
 
 #[cfg(feature = "mysql")]
 #[cfg_attr(docsrs, doc(cfg(feature = "mysql")))]
@@ -1,36 +1,248 @@
-/// Logs the query and execution time of a statement as it runs.
+//! How the statements run on a connection are logged.
+
+use std::fmt::{self, Debug};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use futures_core::future::BoxFuture;
+use log::Level;
+
+// Re-exported as it is used with [LogSettings] and the `log_level` of a query
+pub use log::LevelFilter;
+
+/// Logs the query and execution time of a statement once it ran.
+///
+/// The time is taken from when the future is first polled until it completes, or from the first
+/// call to `next` on the cursor until it is exhausted or dropped.
 macro_rules! log_execution {
-    ( $query:expr, $block:expr ) => {{
+    ( $settings:expr, $query:expr, $block:expr ) => {{
         // TODO: Log bound parameters
         let query_string = $query.query_string();
-        let timer = std::time::Instant::now();
-        let result = $block;
-        let elapsed = timer.elapsed();
-        if elapsed >= std::time::Duration::from_secs(1) {
-            log::warn!(
-                "{} ..., elapsed: {:.3?}\n\n{}\n",
-                crate::logging::parse_query_summary(query_string),
-                elapsed,
-                sqlformat::format(
-                    query_string,
-                    &sqlformat::QueryParams::None,
-                    sqlformat::FormatOptions::default()
-                )
-            );
+        let log = $settings.statement_log(query_string, $query.log_level());
+        let log = crate::logging::QueryLog::new(module_path!(), query_string, log);
+
+        crate::logging::Logged::logged($block, log)
+    }};
+}
+
+/// Settings for the logging of the statements run on a connection.
+///
+/// Every statement is logged along with how long it took at the `DEBUG` level by default, or at
+/// the `WARN` level if it took a second or longer. These are set for a single connection with
+/// [`Connection::set_log_settings`], or for every connection of a pool with
+/// [`Builder::log_settings`], and can be overridden for a single query with its `log_level`.
+///
+/// ```rust,ignore
+/// let pool = PgPool::builder()
+///     .log_settings(
+///         LogSettings::default()
+///             .log_slow_statements(LevelFilter::Warn, Duration::from_millis(250))
+///             // the health checks of the load balancer
+///             .filter(|sql| sql != "SELECT 1"),
+///     )
+///     .build(&url)
+///     .await?;
+/// ```
+///
+/// [`Connection::set_log_settings`]: crate::connection::Connection::set_log_settings
+/// [`Builder::log_settings`]: crate::pool::Builder::log_settings
+#[derive(Clone)]
+pub struct LogSettings {
+    statements_level: LevelFilter,
+    slow_statements_level: LevelFilter,
+    slow_statements_duration: Duration,
+    filter: Option<Filter>,
+}
+
+// Decides if a statement is logged from its SQL; see [LogSettings::filter]
+type Filter = Arc<dyn Fn(&str) -> bool + Send + Sync>;
+
+impl LogSettings {
+    /// Sets the level that statements are logged at; `LevelFilter::Off` logs no statements
+    /// other than the slow ones.
+    pub fn log_statements(mut self, level: LevelFilter) -> Self {
+        self.statements_level = level;
+        self
+    }
+
+    /// Sets the level that statements which took at least `duration` are logged at instead.
+    pub fn log_slow_statements(mut self, level: LevelFilter, duration: Duration) -> Self {
+        self.slow_statements_level = level;
+        self.slow_statements_duration = duration;
+        self
+    }
+
+    /// Only log the statements for which `filter` returns `true` when given their SQL.
+    ///
+    /// This is called with the SQL as it was given to the query, before it runs; it is not
+    /// called when the level of the statement is not enabled, or for a query that was told not
+    /// to log at all.
+    pub fn filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        self.filter = Some(Arc::new(filter));
+        self
+    }
+
+    // Decides how `sql` is logged once it ran, given the `log_level` of its query if any;
+    // returns `None` if it won't be logged at all
+    pub(crate) fn statement_log(
+        &self,
+        sql: &str,
+        query_level: Option<LevelFilter>,
+    ) -> Option<StatementLog> {
+        let log = match query_level {
+            // a query that is told not to be logged never is, however long it takes
+            Some(LevelFilter::Off) => return None,
+
+            Some(level) => StatementLog {
+                level: level.to_level(),
+                slow_level: self.slow_statements_level.to_level().or(level.to_level()),
+                slow_duration: self.slow_statements_duration,
+            },
+
+            None => StatementLog {
+                level: self.statements_level.to_level(),
+                slow_level: self.slow_statements_level.to_level(),
+                slow_duration: self.slow_statements_duration,
+            },
+        };
+
+        let enabled = |level| matches!(level, Some(level) if level <= log::max_level());
+
+        if !enabled(log.level) && !enabled(log.slow_level) {
+            return None;
+        }
+
+        if let Some(filter) = &self.filter {
+            if !filter(sql) {
+                return None;
+            }
+        }
+
+        Some(log)
+    }
+}
+
+impl Default for LogSettings {
+    fn default() -> Self {
+        Self {
+            statements_level: LevelFilter::Debug,
+            slow_statements_level: LevelFilter::Warn,
+            slow_statements_duration: Duration::from_secs(1),
+            filter: None,
+        }
+    }
+}
+
+impl Debug for LogSettings {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LogSettings")
+            .field("statements_level", &self.statements_level)
+            .field("slow_statements_level", &self.slow_statements_level)
+            .field("slow_statements_duration", &self.slow_statements_duration)
+            .field("filter", &self.filter.as_ref().map(|_| ".."))
+            .finish()
+    }
+}
+
+// How a statement is logged once it ran; see [LogSettings::statement_log]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct StatementLog {
+    level: Option<Level>,
+    slow_level: Option<Level>,
+    slow_duration: Duration,
+}
+
+impl StatementLog {
+    pub(crate) fn finish(self, target: &str, sql: &str, elapsed: Duration) {
+        let level = if elapsed >= self.slow_duration {
+            self.slow_level
         } else {
-            log::debug!(
+            self.level
+        };
+
+        if let Some(level) = level {
+            log::log!(
+                target: target,
+                level,
                 "{} ..., elapsed: {:.3?}\n\n{}\n",
-                crate::logging::parse_query_summary(query_string),
+                parse_query_summary(sql),
                 elapsed,
                 sqlformat::format(
-                    query_string,
+                    sql,
                     &sqlformat::QueryParams::None,
                     sqlformat::FormatOptions::default()
                 )
             );
         }
-        result
-    }};
+    }
+}
+
+// The log of a statement as it runs, finished when the statement did; if it is dropped before
+// then, the statement is logged with the time it ran for until that point
+#[derive(Default)]
+pub(crate) struct QueryLog<'q> {
+    target: &'static str,
+    sql: &'q str,
+    log: Option<StatementLog>,
+    started: Option<Instant>,
+}
+
+impl<'q> QueryLog<'q> {
+    pub(crate) fn new(target: &'static str, sql: &'q str, log: Option<StatementLog>) -> Self {
+        Self {
+            target,
+            sql,
+            log,
+            started: None,
+        }
+    }
+
+    // Starts the timer of the statement, unless it was already started
+    pub(crate) fn start(&mut self) {
+        if self.log.is_some() && self.started.is_none() {
+            self.started = Some(Instant::now());
+        }
+    }
+
+    // Logs the statement if it started; this does nothing the second time
+    pub(crate) fn finish(&mut self) {
+        if let (Some(log), Some(started)) = (self.log.take(), self.started) {
+            log.finish(self.target, self.sql, started.elapsed());
+        }
+    }
+}
+
+impl Drop for QueryLog<'_> {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+// What `log_execution!` gives the log of its statement to: a future that runs the statement, or
+// a cursor that does as it is advanced
+pub(crate) trait Logged<'q> {
+    fn logged(self, log: QueryLog<'q>) -> Self;
+}
+
+impl<'e, 'q: 'e, T: 'e> Logged<'q> for BoxFuture<'e, T> {
+    fn logged(self, mut log: QueryLog<'q>) -> Self {
+        if log.log.is_none() {
+            return self;
+        }
+
+        Box::pin(async move {
+            log.start();
+
+            let result = self.await;
+
+            log.finish();
+
+            result
+        })
+    }
 }
 
 pub(crate) fn parse_query_summary(query: &str) -> String {
@@ -1,3 +1,5 @@
+use std::error::Error as StdError;
+
 use crate::arguments::Arguments;
 use crate::encode::{Encode, IsNull};
 use crate::mysql::type_info::MySqlTypeInfo;
@@ -9,6 +11,9 @@ pub struct MySqlArguments {
     pub(crate) param_types: Vec<MySqlTypeInfo>,
     pub(crate) params: Vec<u8>,
     pub(crate) null_bitmap: Vec<u8>,
+
+    // The first bind value that cannot be encoded; the query fails with it before being sent
+    pub(crate) error: Option<Box<dyn StdError + Send + Sync>>,
 }
 
 impl Arguments for MySqlArguments {
@@ -30,6 +35,10 @@ impl Arguments for MySqlArguments {
         T: Type<Self::Database>,
         T: Encode<Self::Database>,
     {
+        if let Err(error) = value.check() {
+            self.error.get_or_insert(error);
+        }
+
         let type_id = <T as Type<MySql>>::type_info();
         let index = self.param_types.len();
 
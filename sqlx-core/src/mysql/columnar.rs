@@ -0,0 +1,106 @@
+use std::convert::TryFrom;
+
+use futures_core::future::BoxFuture;
+
+use crate::columnar::{ColumnBuilder, Columnar, ColumnarResult, ColumnarResultBuilder};
+use crate::cursor::Either;
+use crate::decode::Decode;
+use crate::mysql::cursor::{self, MySqlCursor};
+use crate::mysql::protocol::{Row, TypeId};
+use crate::mysql::{MySql, MySqlValue};
+
+// https://dev.mysql.com/doc/refman/8.0/en/charset-binary-set.html
+const BINARY_CHAR_SET: u16 = 63;
+
+impl Columnar for MySql {
+    fn fetch_columnar<'c, 'q>(
+        mut cursor: MySqlCursor<'c, 'q>,
+    ) -> BoxFuture<'c, crate::Result<ColumnarResult>>
+    where
+        'q: 'c,
+    {
+        Box::pin(async move {
+            let mut result = ColumnarResultBuilder::new();
+
+            while let Some(next) = cursor::next(&mut cursor, false).await? {
+                let row = match next {
+                    Either::Right(row) => row,
+                    Either::Left(_) => continue,
+                };
+
+                let columns = result.push_row(row.row.len(), || {
+                    row.statement
+                        .columns()
+                        .iter()
+                        .map(|column| column.name.as_deref().map(ToOwned::to_owned))
+                        .collect()
+                })?;
+
+                for (index, column) in columns.iter_mut().enumerate() {
+                    push_value(&row.row, index, column)?;
+                }
+            }
+
+            Ok(result.finish())
+        })
+    }
+}
+
+fn push_value(row: &Row<'_>, index: usize, column: &mut ColumnBuilder) -> crate::Result<()> {
+    let type_info = &row.columns[index];
+
+    let value = match row.get(index) {
+        Some(buf) if row.binary => MySqlValue::binary(type_info.clone(), buf),
+        Some(buf) => MySqlValue::text(type_info.clone(), buf),
+
+        None => {
+            column.push_null();
+            return Ok(());
+        }
+    };
+
+    match type_info.id {
+        TypeId::TINY_INT if type_info.is_unsigned => {
+            column.push_int(<u8 as Decode<MySql>>::decode(value)?.into())
+        }
+
+        TypeId::SMALL_INT if type_info.is_unsigned => {
+            column.push_int(<u16 as Decode<MySql>>::decode(value)?.into())
+        }
+
+        TypeId::INT if type_info.is_unsigned => {
+            column.push_int(<u32 as Decode<MySql>>::decode(value)?.into())
+        }
+
+        TypeId::BIG_INT if type_info.is_unsigned => {
+            let value = <u64 as Decode<MySql>>::decode(value)?;
+
+            column.push_int(i64::try_from(value).map_err(crate::Error::decode)?)
+        }
+
+        TypeId::TINY_INT => column.push_int(<i8 as Decode<MySql>>::decode(value)?.into()),
+        TypeId::SMALL_INT => column.push_int(<i16 as Decode<MySql>>::decode(value)?.into()),
+        TypeId::INT => column.push_int(<i32 as Decode<MySql>>::decode(value)?.into()),
+        TypeId::BIG_INT => column.push_int(Decode::<MySql>::decode(value)?),
+
+        TypeId::FLOAT => column.push_float(<f32 as Decode<MySql>>::decode(value)?.into()),
+        TypeId::DOUBLE => column.push_float(Decode::<MySql>::decode(value)?),
+
+        TypeId::CHAR
+        | TypeId::VAR_CHAR
+        | TypeId::TEXT
+        | TypeId::TINY_BLOB
+        | TypeId::MEDIUM_BLOB
+        | TypeId::LONG_BLOB
+            if type_info.char_set == BINARY_CHAR_SET =>
+        {
+            column.push_bytes(Decode::<MySql>::decode(value)?)
+        }
+
+        TypeId::CHAR | TypeId::VAR_CHAR | TypeId::TEXT | TypeId::ENUM => {
+            column.push_text(Decode::<MySql>::decode(value)?)
+        }
+
+        _ => Err(column.unsupported(type_info)),
+    }
+}
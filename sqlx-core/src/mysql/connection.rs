@@ -2,19 +2,27 @@ use std::borrow::Cow;
 use std::collections::HashMap;
 use std::convert::TryInto;
 use std::ops::Range;
+use std::sync::Arc;
 
 use futures_core::future::BoxFuture;
 use sha1::Sha1;
 
-use crate::connection::{Connect, Connection};
+use crate::connection::{Connect, Connection, StatementCacheMetrics};
 use crate::executor::Executor;
+use crate::io::MaybeTlsStream;
+use crate::logging::LogSettings;
 use crate::mysql::protocol::{
-    AuthPlugin, AuthSwitch, Capabilities, ComPing, Handshake, HandshakeResponse,
+    AuthPlugin, AuthSwitch, Capabilities, ComPing, ComQuit, Handshake, HandshakeResponse,
 };
 use crate::mysql::stream::MySqlStream;
 use crate::mysql::util::xor_eq;
+use crate::mysql::MySql;
+use crate::statement::SqlRedaction;
 
 use crate::mysql::{rsa, tls};
+use crate::retry::{RetryOptions, RetryPolicy};
+use crate::runtime::{AsyncRead, AsyncWrite};
+use crate::transaction::Transaction;
 use crate::url::Url;
 
 // Size before a packet is split
@@ -83,14 +91,45 @@ pub(super) const COLLATE_UTF8MB4_UNICODE_CI: u8 = 224;
 /// `ssl-mode=VERIFY_CA`, the hostname in the connection string will be verified
 /// against the hostname in the server certificate, so they must be the same for the TLS
 /// upgrade to succeed. `ssl-ca` must still be specified.
+///
+/// ### Retrying
+/// By default, a connection attempt that fails is not retried. Add `connect_retries=<n>` to the
+/// connection string to retry up to `n` times when connecting fails with a transient error: the
+/// connection being refused, reset or timing out, or a temporary failure to resolve the host (the
+/// name server not answering). Authentication and TLS errors are never retried, and neither is a
+/// host that does not exist.
+///
+/// Before each retry we wait for `connect_retry_backoff` milliseconds (`100` by default), doubled
+/// after each attempt and with some random jitter. If every attempt fails,
+/// [`Error::ConnectRetriesExhausted`] reports how many attempts were made and the last error.
+///
+/// ```text
+/// mysql://localhost/my_database?connect_retries=5&connect_retry_backoff=250
+/// ```
+///
+/// Both can also be set with [`MySqlConnectOptions`], which takes precedence over the URL.
+///
+/// [`Error::ConnectRetriesExhausted`]: crate::Error::ConnectRetriesExhausted
+/// [`MySqlConnectOptions`]: crate::mysql::MySqlConnectOptions
 pub struct MySqlConnection {
     pub(super) stream: MySqlStream,
     pub(super) is_ready: bool,
     pub(super) cache_statement: HashMap<Box<str>, u32>,
 
+    // hits and misses of [cache_statement]
+    pub(super) cache_metrics: StatementCacheMetrics,
+
+    pub(super) log_settings: LogSettings,
+    pub(super) sql_redaction: SqlRedaction,
+
     // Work buffer for the value ranges of the current row
     // This is used as the backing memory for each Row's value indexes
     pub(super) current_row_values: Vec<Option<Range<usize>>>,
+
+    // The id of this connection, which a query running on it is killed with, and the URL that
+    // the connection to kill it from is opened with; see [cancel_request]
+    connection_id: u32,
+    url: Arc<Url>,
 }
 
 fn to_asciz(s: &str) -> Vec<u8> {
@@ -149,7 +188,8 @@ async fn make_auth_response(
     }
 }
 
-async fn establish(stream: &mut MySqlStream, url: &Url) -> crate::Result<()> {
+// Returns the id of the connection that the server assigned
+async fn establish(stream: &mut MySqlStream, url: &Url) -> crate::Result<u32> {
     // https://dev.mysql.com/doc/dev/mysql-server/8.0.12/page_protocol_connection_phase.html
     // https://mariadb.com/kb/en/connection/
 
@@ -157,6 +197,7 @@ async fn establish(stream: &mut MySqlStream, url: &Url) -> crate::Result<()> {
     // received from the database server.
 
     let handshake = Handshake::read(stream.receive().await?)?;
+    let connection_id = handshake.connection_id;
     let mut auth_plugin = handshake.auth_plugin;
     let mut auth_plugin_data = handshake.auth_plugin_data;
 
@@ -246,13 +287,15 @@ async fn establish(stream: &mut MySqlStream, url: &Url) -> crate::Result<()> {
         }
     }
 
-    Ok(())
+    Ok(connection_id)
 }
 
+// https://dev.mysql.com/doc/internals/en/com-quit.html
 async fn close(mut stream: MySqlStream) -> crate::Result<()> {
-    // TODO: Actually tell MySQL that we're closing
+    stream.quit_on_drop = false;
 
-    stream.flush().await?;
+    // the server closes the connection in response; there is nothing to wait for
+    stream.send(ComQuit, true).await?;
     stream.shutdown()?;
 
     Ok(())
@@ -274,17 +317,61 @@ async fn ping(stream: &mut MySqlStream) -> crate::Result<()> {
 }
 
 impl MySqlConnection {
-    pub(super) async fn new(url: std::result::Result<Url, url::ParseError>) -> crate::Result<Self> {
-        let url = url?;
-        let mut stream = MySqlStream::new(&url).await?;
+    /// Establish a connection over a stream that was already opened by the caller.
+    ///
+    /// DNS resolution and the TCP dial are skipped entirely and the handshake is performed
+    /// directly over `stream`. This can be used to connect through a SOCKS proxy or an SSH
+    /// tunnel, or to talk to an in-process fake server in tests.
+    ///
+    /// Everything else is still read from `url`. In particular, `ssl-mode` is honored and
+    /// the TLS session is negotiated on top of `stream`, verifying the host given in `url`.
+    ///
+    /// ```rust,ignore
+    /// let stream = async_std::net::TcpStream::connect("127.0.0.1:6033").await?;
+    ///
+    /// let conn = MySqlConnection::connect_with_stream(stream, "mysql://db.internal/app").await?;
+    /// ```
+    pub async fn connect_with_stream<S, T>(stream: S, url: T) -> crate::Result<Self>
+    where
+        S: AsyncRead + AsyncWrite + Send + Unpin + 'static,
+        T: TryInto<Url, Error = url::ParseError>,
+    {
+        let url = url.try_into()?;
+        let stream = MySqlStream::from_stream(MaybeTlsStream::from_raw(stream), &url);
+
+        Self::establish(stream, &url).await
+    }
+
+    pub(super) async fn new(
+        url: std::result::Result<Url, url::ParseError>,
+        retry: RetryOptions,
+    ) -> crate::Result<Self> {
+        let url = &url?;
+
+        RetryPolicy::new(url, retry)?
+            .run(move || async move {
+                let stream = MySqlStream::new(url).await?;
+
+                Self::establish(stream, url).await
+            })
+            .await
+    }
+
+    async fn establish(mut stream: MySqlStream, url: &Url) -> crate::Result<Self> {
+        let connection_id = establish(&mut stream, url).await?;
 
-        establish(&mut stream, &url).await?;
+        stream.quit_on_drop = true;
 
         let mut self_ = Self {
             stream,
             current_row_values: Vec::with_capacity(10),
             is_ready: true,
             cache_statement: HashMap::new(),
+            cache_metrics: StatementCacheMetrics::default(),
+            log_settings: LogSettings::default(),
+            sql_redaction: SqlRedaction::default(),
+            connection_id,
+            url: Arc::new(url.clone()),
         };
 
         // After the connection is established, we initialize by configuring a few
@@ -320,6 +407,23 @@ SET NAMES utf8mb4 COLLATE utf8mb4_unicode_ci;
 
         Ok(self_)
     }
+
+    // Kills the query that is running on this connection with `KILL QUERY`, from a connection of
+    // its own; see `CancelHandle`
+    pub(super) fn cancel_request(&self) -> BoxFuture<'static, crate::Result<()>> {
+        let connection_id = self.connection_id;
+        let url = Arc::clone(&self.url);
+
+        Box::pin(async move {
+            let stream = MySqlStream::new(&url).await?;
+            let mut conn = Self::establish(stream, &url).await?;
+
+            conn.execute(&*format!("KILL QUERY {}", connection_id))
+                .await?;
+
+            conn.close().await
+        })
+    }
 }
 
 impl Connect for MySqlConnection {
@@ -328,11 +432,18 @@ impl Connect for MySqlConnection {
         T: TryInto<Url, Error = url::ParseError>,
         Self: Sized,
     {
-        Box::pin(MySqlConnection::new(url.try_into()))
+        Box::pin(MySqlConnection::new(
+            url.try_into(),
+            RetryOptions::default(),
+        ))
     }
 }
 
 impl Connection for MySqlConnection {
+    fn begin(&mut self) -> BoxFuture<'_, crate::Result<Transaction<'_, MySql>>> {
+        Box::pin(Transaction::new(0, self))
+    }
+
     #[inline]
     fn close(self) -> BoxFuture<'static, crate::Result<()>> {
         Box::pin(close(self.stream))
@@ -342,4 +453,34 @@ impl Connection for MySqlConnection {
     fn ping(&mut self) -> BoxFuture<crate::Result<()>> {
         Box::pin(ping(&mut self.stream))
     }
+
+    fn statement_cache_metrics(&self) -> StatementCacheMetrics {
+        StatementCacheMetrics {
+            round_trips: self.stream.round_trips,
+            ..self.cache_metrics
+        }
+    }
+
+    fn reset_statement_cache_metrics(&mut self) {
+        self.cache_metrics = StatementCacheMetrics::default();
+        self.stream.round_trips = 0;
+    }
+
+    fn set_log_settings(&mut self, settings: LogSettings) {
+        self.log_settings = settings;
+    }
+
+    fn set_sql_redaction(&mut self, redaction: SqlRedaction) {
+        self.sql_redaction = redaction;
+    }
+
+    fn queue_rollback(&mut self, statement: &str) -> bool {
+        self.stream.queue_query(statement);
+
+        true
+    }
+
+    fn flush_queued_now(&mut self) -> bool {
+        self.stream.stream.flush_now()
+    }
 }
@@ -1,25 +1,72 @@
-use std::collections::HashMap;
+use std::borrow::Cow;
 use std::sync::Arc;
 
 use futures_core::future::BoxFuture;
+use futures_util::TryFutureExt;
 
+use crate::cancel::{next_cancellable, CancelHandle};
 use crate::connection::ConnectionSource;
-use crate::cursor::Cursor;
+use crate::cursor::{Cursor, Either};
+use crate::describe::Column;
 use crate::executor::Execute;
+use crate::logging::{Logged, QueryLog};
 use crate::mysql::protocol::{ColumnCount, ColumnDefinition, Row, Status};
-use crate::mysql::{MySql, MySqlArguments, MySqlConnection, MySqlRow, MySqlTypeInfo};
+use crate::mysql::{
+    MySql, MySqlArguments, MySqlConnection, MySqlQueryResult, MySqlRow, MySqlTypeInfo,
+};
 use crate::pool::Pool;
+use crate::statement::Statement;
 
 pub struct MySqlCursor<'c, 'q> {
-    source: ConnectionSource<'c, MySqlConnection>,
-    query: Option<(&'q str, Option<MySqlArguments>)>,
-    column_names: Arc<HashMap<Box<str>, u16>>,
+    pub(super) source: ConnectionSource<'c, MySqlConnection>,
+    sql: Cow<'q, str>,
+    arguments: Option<MySqlArguments>,
+    // the query has been sent
+    started: bool,
+    // at the start of the next result of the response, rather than within the rows of a result
+    // set; a query of several statements has a result for each of them
+    between_results: bool,
+    // the query is the `CALL` of a stored procedure, the results of which the stream is told
+    // about to discard them if this is dropped before the end of them
+    call: bool,
+    statement: Arc<Statement<MySql>>,
     column_types: Vec<MySqlTypeInfo>,
     binary: bool,
+    // the last result of the query has been received
+    done: bool,
+    cancel_handle: Option<CancelHandle>,
+    log: QueryLog<'q>,
+}
+
+impl<'c> MySqlCursor<'c, 'static> {
+    // For the `CALL` of a stored procedure, which is sent as text
+    pub(super) fn from_call(conn: &'c mut MySqlConnection, sql: String) -> Self {
+        Self {
+            source: ConnectionSource::ConnectionRef(conn),
+            statement: Arc::default(),
+            column_types: Vec::new(),
+            binary: true,
+            done: false,
+            started: false,
+            between_results: false,
+            call: true,
+            sql: Cow::Owned(sql),
+            arguments: None,
+            cancel_handle: None,
+            log: QueryLog::default(),
+        }
+    }
 }
 
 impl crate::cursor::private::Sealed for MySqlCursor<'_, '_> {}
 
+impl<'q> Logged<'q> for MySqlCursor<'_, 'q> {
+    fn logged(mut self, log: QueryLog<'q>) -> Self {
+        self.log = log;
+        self
+    }
+}
+
 impl<'c, 'q> Cursor<'c, 'q> for MySqlCursor<'c, 'q> {
     type Database = MySql;
 
@@ -29,12 +76,22 @@ impl<'c, 'q> Cursor<'c, 'q> for MySqlCursor<'c, 'q> {
         Self: Sized,
         E: Execute<'q, MySql>,
     {
+        let cancel_handle = query.cancel_handle().cloned();
+        let (sql, arguments) = query.into_parts();
+
         Self {
             source: ConnectionSource::Pool(pool.clone()),
-            column_names: Arc::default(),
+            statement: Arc::default(),
             column_types: Vec::new(),
             binary: true,
-            query: Some(query.into_parts()),
+            done: false,
+            started: false,
+            between_results: false,
+            call: false,
+            sql: Cow::Borrowed(sql),
+            arguments,
+            cancel_handle,
+            log: QueryLog::default(),
         }
     }
 
@@ -44,38 +101,104 @@ impl<'c, 'q> Cursor<'c, 'q> for MySqlCursor<'c, 'q> {
         Self: Sized,
         E: Execute<'q, MySql>,
     {
+        let cancel_handle = query.cancel_handle().cloned();
+        let (sql, arguments) = query.into_parts();
+
         Self {
             source: ConnectionSource::ConnectionRef(conn),
-            column_names: Arc::default(),
+            statement: Arc::default(),
             column_types: Vec::new(),
             binary: true,
-            query: Some(query.into_parts()),
+            done: false,
+            started: false,
+            between_results: false,
+            call: false,
+            sql: Cow::Borrowed(sql),
+            arguments,
+            cancel_handle,
+            log: QueryLog::default(),
         }
     }
 
     fn next(&mut self) -> BoxFuture<crate::Result<Option<MySqlRow<'_>>>> {
-        Box::pin(next(self))
+        Box::pin(
+            next(self, false).map_ok(|result| result.and_then(Either::right).map(RawRow::into_row)),
+        )
+    }
+
+    fn next_many(
+        &mut self,
+    ) -> BoxFuture<'_, crate::Result<Option<Either<MySqlQueryResult, MySqlRow<'_>>>>> {
+        Box::pin(next(self, true).map_ok(|result| {
+            result.map(|next| match next {
+                Either::Left(result) => Either::Left(result),
+                Either::Right(row) => Either::Right(row.into_row()),
+            })
+        }))
+    }
+}
+
+// A row as it was read from the connection, along with the statement it is from
+pub(super) struct RawRow<'a> {
+    pub(super) row: Row<'a>,
+    pub(super) statement: &'a Arc<Statement<MySql>>,
+}
+
+impl<'a> RawRow<'a> {
+    fn into_row(self) -> MySqlRow<'a> {
+        MySqlRow {
+            row: self.row,
+            statement: Arc::clone(self.statement),
+        }
     }
 }
 
-async fn next<'a, 'c: 'a, 'q: 'a>(
+pub(super) async fn next<'a, 'c: 'a, 'q: 'a>(
     cursor: &'a mut MySqlCursor<'c, 'q>,
-) -> crate::Result<Option<MySqlRow<'a>>> {
+    many: bool,
+) -> crate::Result<Option<Either<MySqlQueryResult, RawRow<'a>>>> {
+    let handle = match &cursor.cancel_handle {
+        Some(handle) => handle.clone(),
+        None => return fetch_next(cursor, many).await,
+    };
+
+    let request = cursor.source.resolve().await?.cancel_request();
+
+    next_cancellable(&handle, fetch_next(cursor, many), request).await
+}
+
+// With [many], the result of each statement is returned as well
+async fn fetch_next<'a, 'c: 'a, 'q: 'a>(
+    cursor: &'a mut MySqlCursor<'c, 'q>,
+    many: bool,
+) -> crate::Result<Option<Either<MySqlQueryResult, RawRow<'a>>>> {
+    cursor.log.start();
+
     let mut conn = cursor.source.resolve().await?;
 
+    if cursor.done {
+        cursor.log.finish();
+
+        return Ok(None);
+    }
+
     // The first time [next] is called we need to actually execute our
     // contained query. We guard against this happening on _all_ next calls
-    // by using [Option::take] which replaces the potential value in the Option with `None
-    let mut initial = if let Some((query, arguments)) = cursor.query.take() {
-        let statement = conn.run(query, arguments).await?;
+    // with [started]
+    if !cursor.started {
+        cursor.started = true;
+
+        let statement = conn.run(&cursor.sql, cursor.arguments.take()).await?;
 
         // No statement ID = TEXT mode
         cursor.binary = statement.is_some();
+        cursor.between_results = true;
 
-        true
-    } else {
-        false
-    };
+        if cursor.call {
+            conn.stream.in_call = true;
+            conn.stream.between_results = true;
+        }
+    }
 
     loop {
         let packet_id = conn.stream.receive().await?[0];
@@ -83,61 +206,93 @@ async fn next<'a, 'c: 'a, 'q: 'a>(
         match packet_id {
             // OK or EOF packet
             0x00 | 0xFE
-                if conn.stream.packet().len() < 0xFF_FF_FF && (packet_id != 0x00 || initial) =>
+                if conn.stream.packet().len() < 0xFF_FF_FF
+                    && (packet_id != 0x00 || cursor.between_results) =>
             {
-                let status = if let Some(eof) = conn.stream.maybe_handle_eof()? {
-                    eof.status
+                // An EOF packet ends a result set, which did not affect any rows
+                let (status, result) = if let Some(eof) = conn.stream.maybe_handle_eof()? {
+                    (eof.status, MySqlQueryResult::default())
                 } else {
-                    conn.stream.handle_ok()?.status
+                    let ok = conn.stream.handle_ok()?;
+
+                    let result = MySqlQueryResult {
+                        rows_affected: ok.affected_rows,
+                        last_insert_id: ok.last_insert_id,
+                    };
+
+                    (ok.status, result)
                 };
 
                 if status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
                     // There is more to this query
-                    initial = true;
+                    cursor.between_results = true;
                 } else {
-                    conn.is_ready = true;
+                    conn.stream.is_ready = true;
+                    cursor.done = true;
+                }
+
+                if cursor.call {
+                    conn.stream.finish_result(status);
+                }
+
+                if many {
+                    return Ok(Some(Either::Left(result)));
+                }
+
+                if cursor.done {
+                    cursor.log.finish();
+
                     return Ok(None);
                 }
             }
 
             // ERR packet
             0xFF => {
-                conn.is_ready = true;
+                cursor.done = true;
                 return conn.stream.handle_err();
             }
 
-            _ if initial => {
+            _ if cursor.between_results => {
                 // At the start of the results we expect to see a
                 // COLUMN_COUNT followed by N COLUMN_DEF
 
                 let cc = ColumnCount::read(conn.stream.packet())?;
 
+                cursor.between_results = false;
+
+                if cursor.call {
+                    conn.stream.between_results = false;
+                }
+
                 // We use these definitions to get the actual column types that is critical
                 // in parsing the rows coming back soon
 
                 cursor.column_types.clear();
                 cursor.column_types.reserve(cc.columns as usize);
 
-                let mut column_names = HashMap::with_capacity(cc.columns as usize);
+                let mut columns = Vec::with_capacity(cc.columns as usize);
 
-                for i in 0..cc.columns {
+                for _ in 0..cc.columns {
                     let column = ColumnDefinition::read(conn.stream.receive().await?)?;
 
                     cursor
                         .column_types
                         .push(MySqlTypeInfo::from_nullable_column_def(&column));
 
-                    if let Some(name) = column.name() {
-                        column_names.insert(name.to_owned().into_boxed_str(), i as u16);
-                    }
+                    columns.push(Column::<MySql> {
+                        type_info: MySqlTypeInfo::from_column_def(&column),
+                        name: column.name().map(Into::into),
+                        table_id: column.table_alias.or(column.table),
+                        non_null: None,
+                    });
                 }
 
                 if cc.columns > 0 {
                     conn.stream.maybe_receive_eof().await?;
                 }
 
-                cursor.column_names = Arc::new(column_names);
-                initial = false;
+                cursor.statement =
+                    Arc::new(Statement::new(&cursor.sql, columns, conn.sql_redaction));
             }
 
             _ if !cursor.binary || packet_id == 0x00 => {
@@ -148,12 +303,10 @@ async fn next<'a, 'c: 'a, 'q: 'a>(
                     cursor.binary,
                 )?;
 
-                let row = MySqlRow {
+                return Ok(Some(Either::Right(RawRow {
                     row,
-                    names: Arc::clone(&cursor.column_names),
-                };
-
-                return Ok(Some(row));
+                    statement: &cursor.statement,
+                })));
             }
 
             _ => {
@@ -19,6 +19,8 @@ impl Database for MySql {
 
     type RawBuffer = Vec<u8>;
 
+    type QueryResult = super::MySqlQueryResult;
+
     type Error = MySqlError;
 }
 
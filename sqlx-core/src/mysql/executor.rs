@@ -41,8 +41,12 @@ impl super::MySqlConnection {
     // At the end we should have [cache_statement] and [cache_statement_columns] filled
     async fn get_or_prepare(&mut self, query: &str) -> crate::Result<u32> {
         if let Some(&id) = self.cache_statement.get(query) {
+            self.cache_metrics.hits += 1;
+
             Ok(id)
         } else {
+            self.cache_metrics.misses += 1;
+
             let stmt = self.prepare(query).await?;
 
             self.cache_statement.insert(query.into(), stmt.statement_id);
@@ -62,8 +66,15 @@ impl super::MySqlConnection {
     pub(crate) async fn run(
         &mut self,
         query: &str,
-        arguments: Option<MySqlArguments>,
+        mut arguments: Option<MySqlArguments>,
     ) -> crate::Result<Option<u32>> {
+        if let Some(error) = arguments
+            .as_mut()
+            .and_then(|arguments| arguments.error.take())
+        {
+            return Err(crate::Error::Encode(error));
+        }
+
         self.stream.wait_until_ready().await?;
         self.stream.is_ready = false;
 
@@ -93,7 +104,7 @@ impl super::MySqlConnection {
         }
     }
 
-    async fn affected_rows(&mut self) -> crate::Result<u64> {
+    pub(super) async fn affected_rows(&mut self) -> crate::Result<u64> {
         let mut rows = 0;
 
         loop {
@@ -166,6 +177,7 @@ impl super::MySqlConnection {
         }
 
         Ok(Describe {
+            param_names: vec![None; param_types.len()].into_boxed_slice(),
             param_types: param_types.into_boxed_slice(),
             result_columns: result_columns.into_boxed_slice(),
         })
@@ -182,7 +194,7 @@ impl Executor for super::MySqlConnection {
     where
         E: Execute<'q, Self::Database>,
     {
-        log_execution!(query, {
+        log_execution!(self.log_settings, query, {
             Box::pin(async move {
                 let (query, arguments) = query.into_parts();
 
@@ -196,7 +208,9 @@ impl Executor for super::MySqlConnection {
     where
         E: Execute<'q, Self::Database>,
     {
-        log_execution!(query, { MySqlCursor::from_connection(self, query) })
+        log_execution!(self.log_settings, query, {
+            MySqlCursor::from_connection(self, query)
+        })
     }
 
     #[doc(hidden)]
@@ -218,6 +232,8 @@ impl<'c> RefExecutor<'c> for &'c mut super::MySqlConnection {
     where
         E: Execute<'q, Self::Database>,
     {
-        log_execution!(query, { MySqlCursor::from_connection(self, query) })
+        log_execution!(self.log_settings, query, {
+            MySqlCursor::from_connection(self, query)
+        })
     }
 }
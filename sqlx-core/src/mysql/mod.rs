@@ -5,18 +5,25 @@ pub use connection::MySqlConnection;
 pub use cursor::MySqlCursor;
 pub use database::MySql;
 pub use error::MySqlError;
+pub use options::MySqlConnectOptions;
+pub use procedure::{MySqlCall, MySqlCallResults};
+pub use query_result::MySqlQueryResult;
 pub use row::MySqlRow;
 pub use type_info::MySqlTypeInfo;
 pub use value::{MySqlData, MySqlValue};
 
 mod arguments;
+mod columnar;
 mod connection;
 mod cursor;
 mod database;
 mod error;
 mod executor;
 mod io;
+mod options;
+mod procedure;
 mod protocol;
+mod query_result;
 mod row;
 mod rsa;
 mod stream;
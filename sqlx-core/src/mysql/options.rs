@@ -0,0 +1,99 @@
+use std::convert::TryInto;
+use std::fmt::{self, Debug};
+use std::time::Duration;
+
+use futures_core::future::BoxFuture;
+
+use crate::mysql::MySqlConnection;
+use crate::pool::{Builder, Pool};
+use crate::retry::RetryOptions;
+
+/// Options that are applied to every new [MySqlConnection] on top of those given in the URL.
+///
+/// ```rust,no_run
+/// # use std::time::Duration;
+/// # use sqlx_core::mysql::MySqlConnectOptions;
+/// # async fn run() -> sqlx_core::Result<()> {
+/// let options = MySqlConnectOptions::new("mysql://localhost/my_database")
+///     .connect_retries(5)
+///     .connect_retry_backoff(Duration::from_millis(250));
+///
+/// // a single connection
+/// let conn = options.connect().await?;
+///
+/// // or every connection opened by a pool
+/// let pool = sqlx_core::mysql::MySqlPool::builder()
+///     .max_size(5)
+///     .build_with(options)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct MySqlConnectOptions {
+    url: String,
+    retry: RetryOptions,
+}
+
+impl MySqlConnectOptions {
+    /// Create options for connecting to the database at `url`.
+    ///
+    /// The URL syntax is the same as for [`MySqlConnection::connect`].
+    ///
+    /// [`MySqlConnection::connect`]: crate::connection::Connect::connect
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_owned(),
+            retry: RetryOptions::default(),
+        }
+    }
+
+    /// Retry up to `retries` times when connecting fails with a transient error, instead of
+    /// as many times as the `connect_retries` parameter of the URL says (if any).
+    ///
+    /// See [Retrying](crate::mysql::MySqlConnection#retrying) for the errors that are retried.
+    pub fn connect_retries(mut self, retries: u32) -> Self {
+        self.retry.retries = Some(retries);
+        self
+    }
+
+    /// Wait for `backoff` before the first retry, instead of for the `connect_retry_backoff`
+    /// parameter of the URL (or 100 milliseconds); it doubles after each attempt.
+    pub fn connect_retry_backoff(mut self, backoff: Duration) -> Self {
+        self.retry.backoff = Some(backoff);
+        self
+    }
+
+    /// Open a new connection with these options.
+    pub fn connect(&self) -> BoxFuture<'static, crate::Result<MySqlConnection>> {
+        self.connect_to(&self.url)
+    }
+
+    fn connect_to(&self, url: &str) -> BoxFuture<'static, crate::Result<MySqlConnection>> {
+        Box::pin(MySqlConnection::new(url.try_into(), self.retry))
+    }
+}
+
+impl Debug for MySqlConnectOptions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MySqlConnectOptions")
+            .field("url", &self.url)
+            .field("connect_retries", &self.retry.retries)
+            .field("connect_retry_backoff", &self.retry.backoff)
+            .finish()
+    }
+}
+
+impl Builder<MySqlConnection> {
+    /// Spin up the connection pool, opening every connection with the given options.
+    pub async fn build_with(
+        self,
+        options: MySqlConnectOptions,
+    ) -> crate::Result<Pool<MySqlConnection>> {
+        let url = options.url.clone();
+
+        self.connector(move |url| options.connect_to(url))
+            .build(&url)
+            .await
+    }
+}
@@ -0,0 +1,237 @@
+//! Calling stored procedures with `CALL`, including their `OUT` parameters.
+//!
+//! <https://dev.mysql.com/doc/refman/8.0/en/call.html>
+
+use futures_core::future::BoxFuture;
+
+use crate::arguments::Arguments;
+use crate::cursor::{Cursor, Either};
+use crate::encode::Encode;
+use crate::executor::Executor;
+use crate::mysql::{
+    MySql, MySqlArguments, MySqlConnection, MySqlCursor, MySqlQueryResult, MySqlRow,
+};
+use crate::row::FromRow;
+use crate::types::Type;
+
+impl MySqlConnection {
+    /// Start a call of the stored procedure `name`, which is then given its parameters in order
+    /// with [`MySqlCall::bind`] for an `IN` parameter and [`MySqlCall::bind_out`] for an `OUT`
+    /// parameter.
+    ///
+    /// The result sets of the procedure, if it selects any, are read with
+    /// [`MySqlCallResults::next_many`], and the values of its `OUT` parameters are then read
+    /// with [`MySqlCallResults::out_params`], which decodes them like a row.
+    ///
+    /// ```rust,ignore
+    /// let mut call = conn
+    ///     .call_procedure("order_totals")
+    ///     .bind(customer_id)
+    ///     .bind_out()
+    ///     .bind_out()
+    ///     .fetch_many();
+    ///
+    /// while let Some(result) = call.next_many().await? {
+    ///     if let Either::Right(row) = result {
+    ///         println!("{}", row.get::<i64, _>("order_id"));
+    ///     }
+    /// }
+    ///
+    /// let (orders, total): (i64, f64) = call.out_params().await?;
+    /// ```
+    ///
+    /// `name` is used as it is, so it may be qualified with the name of its database; it must
+    /// not come from user input.
+    ///
+    /// Each parameter is given a session variable: the values of the `IN` parameters are set
+    /// with a prepared `SET` before the `CALL`, which is then sent as text, and the values of
+    /// the `OUT` parameters are selected from theirs afterwards. A session variable holds an
+    /// integer as a `BIGINT`, so an `INT` parameter is read as an `i64`, for example.
+    pub fn call_procedure(&mut self, name: &str) -> MySqlCall<'_> {
+        MySqlCall {
+            connection: self,
+            name: name.to_owned(),
+            params: Vec::new(),
+            arguments: MySqlArguments::default(),
+            in_params: 0,
+            out_params: 0,
+        }
+    }
+}
+
+/// A call of a stored procedure that is being given its parameters; see
+/// [`MySqlConnection::call_procedure`].
+#[must_use = "call must have `.fetch_many()` or `.out_params()` called to run the procedure"]
+pub struct MySqlCall<'c> {
+    connection: &'c mut MySqlConnection,
+    name: String,
+    // the session variable of each parameter, in order
+    params: Vec<String>,
+    arguments: MySqlArguments,
+    in_params: usize,
+    out_params: usize,
+}
+
+impl<'c> MySqlCall<'c> {
+    /// Give a value to the next parameter of the procedure, an `IN` parameter.
+    pub fn bind<T>(mut self, value: T) -> Self
+    where
+        T: Type<MySql> + Encode<MySql>,
+    {
+        self.params.push(in_param(self.in_params));
+        self.in_params += 1;
+        self.arguments.add(value);
+        self
+    }
+
+    /// Make the next parameter of the procedure an `OUT` parameter, the value of which is read
+    /// with [`MySqlCallResults::out_params`].
+    pub fn bind_out(mut self) -> Self {
+        self.params.push(out_param(self.out_params));
+        self.out_params += 1;
+        self
+    }
+
+    /// Call the procedure, and get its results as they are read.
+    ///
+    /// The procedure is only called once [`MySqlCallResults::next_many`] or
+    /// [`MySqlCallResults::out_params`] is.
+    pub fn fetch_many(self) -> MySqlCallResults<'c> {
+        let sql = format!("CALL {}({})", self.name, self.params.join(", "));
+
+        // a prepared `CALL` could not select any result sets without the server being told that
+        // prepared statements may have several results, so it is given its values this way
+        let in_params = if self.in_params > 0 {
+            let sql = format!(
+                "SET {}",
+                (0..self.in_params)
+                    .map(|index| format!("{} = ?", in_param(index)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+
+            Some((sql, self.arguments))
+        } else {
+            None
+        };
+
+        MySqlCallResults {
+            cursor: MySqlCursor::from_call(self.connection, sql),
+            in_params,
+            out_params: self.out_params,
+        }
+    }
+
+    /// Call the procedure, skipping over its result sets, and get the values of its `OUT`
+    /// parameters; see [`MySqlCallResults::out_params`].
+    pub fn out_params<O>(self) -> BoxFuture<'c, crate::Result<O>>
+    where
+        O: 'c + Send + for<'r> FromRow<'r, MySqlRow<'r>>,
+    {
+        self.fetch_many().out_params()
+    }
+}
+
+/// The results of a call of a stored procedure; see [`MySqlConnection::call_procedure`].
+///
+/// The results must be read to the end, which [`out_params`] and [`finish`] both do, before the
+/// procedure is known to have succeeded. If this is dropped before then, whatever is left of the
+/// results is read and discarded before the next query on the connection, which returns the
+/// error of the procedure instead if it raised one.
+///
+/// [`out_params`]: MySqlCallResults::out_params
+/// [`finish`]: MySqlCallResults::finish
+pub struct MySqlCallResults<'c> {
+    cursor: MySqlCursor<'c, 'static>,
+    // the `SET` of the session variables of the `IN` parameters, until it is run
+    in_params: Option<(String, MySqlArguments)>,
+    out_params: usize,
+}
+
+impl<'c> MySqlCallResults<'c> {
+    /// Get the next result of the procedure: either the next row of a result set it selected,
+    /// or the result of a statement of it that has completed.
+    ///
+    /// The last result is always that of the `CALL` itself. An error
+    /// raised by the procedure is returned once it is reached, and ends the results.
+    pub fn next_many(
+        &mut self,
+    ) -> BoxFuture<'_, crate::Result<Option<Either<MySqlQueryResult, MySqlRow<'_>>>>> {
+        Box::pin(async move {
+            if let Some((sql, arguments)) = self.in_params.take() {
+                let conn = self.cursor.source.resolve().await?;
+
+                conn.run(&sql, Some(arguments)).await?;
+                conn.affected_rows().await?;
+            }
+
+            self.cursor.next_many().await
+        })
+    }
+
+    /// Read the rest of the results of the procedure, and get the values of its `OUT`
+    /// parameters, decoded like a row with a column for each of them in order.
+    ///
+    /// The procedure must have at least one `OUT` parameter; without any, this returns
+    /// [`ColumnIndexOutOfBounds`] and leaves the results to be discarded as when this is
+    /// dropped. Use [`finish`] for such a procedure instead.
+    ///
+    /// [`ColumnIndexOutOfBounds`]: crate::Error::ColumnIndexOutOfBounds
+    /// [`finish`]: MySqlCallResults::finish
+    pub fn out_params<O>(mut self) -> BoxFuture<'c, crate::Result<O>>
+    where
+        O: 'c + Send + for<'r> FromRow<'r, MySqlRow<'r>>,
+    {
+        Box::pin(async move {
+            // there would be nothing to select, let alone to decode
+            if self.out_params == 0 {
+                return Err(crate::Error::ColumnIndexOutOfBounds { index: 0, len: 0 });
+            }
+
+            self.discard_remaining().await?;
+
+            let sql = format!(
+                "SELECT {}",
+                (0..self.out_params)
+                    .map(out_param)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+
+            let conn = self.cursor.source.resolve().await?;
+            let mut cursor = conn.fetch(&*sql);
+
+            let values = match cursor.next().await? {
+                Some(row) => O::from_row(&row)?,
+                None => return Err(crate::Error::RowNotFound),
+            };
+
+            // the end of the one row
+            while cursor.next().await?.is_some() {}
+
+            Ok(values)
+        })
+    }
+
+    /// Read the rest of the results of the procedure, for a procedure without `OUT`
+    /// parameters.
+    pub fn finish(mut self) -> BoxFuture<'c, crate::Result<()>> {
+        Box::pin(async move { self.discard_remaining().await })
+    }
+
+    async fn discard_remaining(&mut self) -> crate::Result<()> {
+        while self.next_many().await?.is_some() {}
+
+        Ok(())
+    }
+}
+
+// The session variable that the value of the `IN` parameter at [index] is given in
+fn in_param(index: usize) -> String {
+    format!("@sqlx_in_{}", index)
+}
+
+// The session variable that the value of the `OUT` parameter at [index] is given to
+fn out_param(index: usize) -> String {
+    format!("@sqlx_out_{}", index)
+}
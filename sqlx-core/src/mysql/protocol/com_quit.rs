@@ -0,0 +1,13 @@
+use crate::io::BufMut;
+use crate::mysql::protocol::{Capabilities, Encode};
+
+// https://dev.mysql.com/doc/internals/en/com-quit.html
+#[derive(Debug)]
+pub struct ComQuit;
+
+impl Encode for ComQuit {
+    fn encode(&self, buf: &mut Vec<u8>, _: Capabilities) {
+        // COM_QUIT : int<1>
+        buf.put_u8(0x01);
+    }
+}
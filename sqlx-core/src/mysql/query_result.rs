@@ -0,0 +1,21 @@
+/// The result of a statement run by [`Executor::fetch_many`] on a **MySQL** connection.
+///
+/// [`Executor::fetch_many`]: crate::executor::Executor::fetch_many
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MySqlQueryResult {
+    pub(super) rows_affected: u64,
+    pub(super) last_insert_id: u64,
+}
+
+impl MySqlQueryResult {
+    /// Returns the number of rows inserted, updated or deleted by the statement.
+    pub fn rows_affected(&self) -> u64 {
+        self.rows_affected
+    }
+
+    /// Returns the value generated for an `AUTO_INCREMENT` column by the statement, or `0` if
+    /// it did not generate one.
+    pub fn last_insert_id(&self) -> u64 {
+        self.last_insert_id
+    }
+}
@@ -1,13 +1,16 @@
-use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::mysql::protocol;
 use crate::mysql::{MySql, MySqlValue};
 use crate::row::{ColumnIndex, Row};
+use crate::statement::Statement;
 
 pub struct MySqlRow<'c> {
     pub(super) row: protocol::Row<'c>,
-    pub(super) names: Arc<HashMap<Box<str>, u16>>,
+
+    // shared reference to the statement this row is coming from,
+    // which also maps each column name to its position
+    pub(super) statement: Arc<Statement<MySql>>,
 }
 
 impl crate::row::private_row::Sealed for MySqlRow<'_> {}
@@ -19,6 +22,11 @@ impl<'c> Row<'c> for MySqlRow<'c> {
         self.row.len()
     }
 
+    #[inline]
+    fn statement(&self) -> &Arc<Statement<MySql>> {
+        &self.statement
+    }
+
     #[doc(hidden)]
     fn try_get_raw<I>(&self, index: I) -> crate::Result<MySqlValue<'c>>
     where
@@ -51,9 +59,10 @@ impl<'c> ColumnIndex<'c, MySqlRow<'c>> for usize {
 
 impl<'c> ColumnIndex<'c, MySqlRow<'c>> for str {
     fn index(&self, row: &MySqlRow<'c>) -> crate::Result<usize> {
-        row.names
+        row.statement
+            .names
             .get(self)
+            .copied()
             .ok_or_else(|| crate::Error::ColumnNotFound((*self).into()))
-            .map(|&index| index as usize)
     }
 }
@@ -3,7 +3,9 @@ use std::net::Shutdown;
 use byteorder::{ByteOrder, LittleEndian};
 
 use crate::io::{Buf, BufMut, BufStream, MaybeTlsStream};
-use crate::mysql::protocol::{Capabilities, Encode, EofPacket, ErrPacket, OkPacket};
+use crate::mysql::protocol::{
+    Capabilities, ColumnCount, ComQuery, ComQuit, Encode, EofPacket, ErrPacket, OkPacket, Status,
+};
 
 use crate::mysql::MySqlError;
 use crate::url::Url;
@@ -18,6 +20,18 @@ pub(crate) struct MySqlStream {
     // Put another way, are we still expecting an EOF or OK packet to terminate
     pub(super) is_ready: bool,
 
+    // The response being read is to the `CALL` of a stored procedure, which has a result for
+    // each result set it selected as well as one for the `CALL` itself; see [wait_until_ready]
+    pub(super) in_call: bool,
+
+    // While [in_call], whether the response is to be read from the start of its next result (a
+    // result set, or an OK or ERR packet) rather than from within the rows of a result set
+    pub(super) between_results: bool,
+
+    // Queries written by `queue_rollback` that have yet to be answered by an OK or ERR packet,
+    // which comes after whatever [is_ready] is still waiting on
+    pub(super) queued_queries: u32,
+
     // Active capabilities
     pub(super) capabilities: Capabilities,
 
@@ -30,6 +44,12 @@ pub(crate) struct MySqlStream {
     // decoding
     packet_buf: Vec<u8>,
     packet_len: usize,
+
+    // Should `COM_QUIT` be sent when this is dropped
+    // Is set once the handshake completes and cleared by an explicit close
+    pub(super) quit_on_drop: bool,
+    // Count of flushes that sent anything; see `StatementCacheMetrics::round_trips`
+    pub(super) round_trips: u64,
 }
 
 impl MySqlStream {
@@ -38,6 +58,10 @@ impl MySqlStream {
         let port = url.port(3306);
         let stream = MaybeTlsStream::connect(host, port).await?;
 
+        Ok(Self::from_stream(stream, url))
+    }
+
+    pub(super) fn from_stream(stream: MaybeTlsStream, url: &Url) -> Self {
         let mut capabilities = Capabilities::PROTOCOL_41
             | Capabilities::IGNORE_SPACE
             | Capabilities::DEPRECATE_EOF
@@ -57,14 +81,19 @@ impl MySqlStream {
             capabilities |= Capabilities::SSL;
         }
 
-        Ok(Self {
+        Self {
             capabilities,
             stream: BufStream::new(stream),
             packet_buf: Vec::with_capacity(MAX_PACKET_SIZE as usize),
             packet_len: 0,
             seq_no: 0,
             is_ready: true,
-        })
+            in_call: false,
+            between_results: false,
+            queued_queries: 0,
+            quit_on_drop: false,
+            round_trips: 0,
+        }
     }
 
     pub(super) fn is_tls(&self) -> bool {
@@ -90,6 +119,10 @@ impl MySqlStream {
 
     #[inline]
     pub(super) async fn flush(&mut self) -> crate::Result<()> {
+        if !self.stream.buffer_mut().is_empty() {
+            self.round_trips += 1;
+        }
+
         Ok(self.stream.flush().await?)
     }
 
@@ -191,6 +224,7 @@ impl MySqlStream {
 
     pub(crate) fn handle_err<T>(&mut self) -> crate::Result<T> {
         self.is_ready = true;
+        self.in_call = false;
         Err(MySqlError(ErrPacket::read(self.packet(), self.capabilities)?).into())
     }
 
@@ -199,7 +233,21 @@ impl MySqlStream {
         OkPacket::read(self.packet())
     }
 
+    // While [in_call], either the response is complete, or another result of it follows
+    pub(super) fn finish_result(&mut self, status: Status) {
+        self.is_ready = !status.contains(Status::SERVER_MORE_RESULTS_EXISTS);
+        self.in_call = !self.is_ready;
+        self.between_results = self.in_call;
+    }
+
     pub(crate) async fn wait_until_ready(&mut self) -> crate::Result<()> {
+        // anything queued must reach the server before we can wait on it
+        self.flush().await?;
+
+        if self.in_call {
+            self.discard_call_results().await?;
+        }
+
         if !self.is_ready {
             loop {
                 let packet_id = self.receive().await?[0];
@@ -223,6 +271,86 @@ impl MySqlStream {
             }
         }
 
+        while self.queued_queries > 0 {
+            // an OK or ERR packet that is no longer of interest to anyone
+            self.receive().await?;
+            self.queued_queries -= 1;
+        }
+
+        Ok(())
+    }
+
+    // Reads what is left of the response to a `CALL`, each of its results in turn
+    async fn discard_call_results(&mut self) -> crate::Result<()> {
+        while self.in_call {
+            let packet_id = self.receive().await?[0];
+
+            match packet_id {
+                // ERR packet
+                0xFF => return self.handle_err(),
+
+                // OK packet
+                0x00 if self.between_results => {
+                    let ok = self.handle_ok()?;
+
+                    self.finish_result(ok.status);
+                }
+
+                _ if self.between_results => {
+                    // The start of a result set; skip over its column definitions
+                    let cc = ColumnCount::read(self.packet())?;
+
+                    for _ in 0..cc.columns {
+                        self.receive().await?;
+                    }
+
+                    if cc.columns > 0 {
+                        self.maybe_receive_eof().await?;
+                    }
+
+                    self.between_results = false;
+                }
+
+                0xFE if self.packet().len() < 0xFF_FF_FF => {
+                    // OK or EOF packet, which ends a result set
+                    let status = if let Some(eof) = self.maybe_handle_eof()? {
+                        eof.status
+                    } else {
+                        self.handle_ok()?.status
+                    };
+
+                    self.finish_result(status);
+                }
+
+                _ => {
+                    // A row of a result set; skip
+                }
+            }
+        }
+
         Ok(())
     }
+
+    // Writes a query to be flushed and waited on by [wait_until_ready]
+    pub(super) fn queue_query(&mut self, query: &str) {
+        self.seq_no = 0;
+        self.write(ComQuery { query });
+        self.queued_queries += 1;
+    }
+}
+
+// Best-effort graceful close for a connection that was not explicitly closed
+// The write is attempted only if the socket can take it right now; we can't wait here as
+// the executor may already be gone (e.g., the runtime is shutting down)
+impl Drop for MySqlStream {
+    fn drop(&mut self) {
+        if self.quit_on_drop {
+            // anything left in the buffer belongs to an abandoned command and must not be sent
+            self.stream.buffer_mut().clear();
+            self.seq_no = 0;
+            self.write(ComQuit);
+
+            let _ = self.stream.flush_now();
+        }
+    }
 }
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::fmt::{self, Display};
 
 use crate::mysql::protocol::{ColumnDefinition, FieldFlags, TypeId};
@@ -66,38 +67,57 @@ impl MySqlTypeInfo {
     }
 }
 
+impl MySqlTypeInfo {
+    // The name of the type, from the type byte and the flags
+    fn known_name(&self) -> Option<&'static str> {
+        Some(match self.id {
+            TypeId::NULL => "NULL",
+
+            TypeId::TINY_INT if self.is_unsigned => "TINYINT UNSIGNED",
+            TypeId::SMALL_INT if self.is_unsigned => "SMALLINT UNSIGNED",
+            TypeId::INT if self.is_unsigned => "INT UNSIGNED",
+            TypeId::BIG_INT if self.is_unsigned => "BIGINT UNSIGNED",
+
+            TypeId::TINY_INT => "TINYINT",
+            TypeId::SMALL_INT => "SMALLINT",
+            TypeId::INT => "INT",
+            TypeId::BIG_INT => "BIGINT",
+
+            TypeId::FLOAT => "FLOAT",
+            TypeId::DOUBLE => "DOUBLE",
+            TypeId::NEWDECIMAL => "DECIMAL",
+
+            TypeId::CHAR if self.is_binary => "BINARY",
+            TypeId::VAR_CHAR if self.is_binary => "VARBINARY",
+            TypeId::TEXT if self.is_binary => "BLOB",
+            TypeId::TINY_BLOB if self.is_binary => "TINYBLOB",
+            TypeId::MEDIUM_BLOB if self.is_binary => "MEDIUMBLOB",
+            TypeId::LONG_BLOB if self.is_binary => "LONGBLOB",
+
+            TypeId::CHAR => "CHAR",
+            TypeId::VAR_CHAR => "VARCHAR",
+            TypeId::TEXT => "TEXT",
+            TypeId::TINY_BLOB => "TINYTEXT",
+            TypeId::MEDIUM_BLOB => "MEDIUMTEXT",
+            TypeId::LONG_BLOB => "LONGTEXT",
+
+            TypeId::ENUM => "ENUM",
+
+            TypeId::DATE => "DATE",
+            TypeId::TIME => "TIME",
+            TypeId::DATETIME => "DATETIME",
+            TypeId::TIMESTAMP => "TIMESTAMP",
+
+            _ => return None,
+        })
+    }
+}
+
 impl Display for MySqlTypeInfo {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.id {
-            TypeId::NULL => f.write_str("NULL"),
-
-            TypeId::TINY_INT if self.is_unsigned => f.write_str("TINYINT UNSIGNED"),
-            TypeId::SMALL_INT if self.is_unsigned => f.write_str("SMALLINT UNSIGNED"),
-            TypeId::INT if self.is_unsigned => f.write_str("INT UNSIGNED"),
-            TypeId::BIG_INT if self.is_unsigned => f.write_str("BIGINT UNSIGNED"),
-
-            TypeId::TINY_INT => f.write_str("TINYINT"),
-            TypeId::SMALL_INT => f.write_str("SMALLINT"),
-            TypeId::INT => f.write_str("INT"),
-            TypeId::BIG_INT => f.write_str("BIGINT"),
-
-            TypeId::FLOAT => f.write_str("FLOAT"),
-            TypeId::DOUBLE => f.write_str("DOUBLE"),
-
-            TypeId::CHAR if self.is_binary => f.write_str("BINARY"),
-            TypeId::VAR_CHAR if self.is_binary => f.write_str("VARBINARY"),
-            TypeId::TEXT if self.is_binary => f.write_str("BLOB"),
-
-            TypeId::CHAR => f.write_str("CHAR"),
-            TypeId::VAR_CHAR => f.write_str("VARCHAR"),
-            TypeId::TEXT => f.write_str("TEXT"),
-
-            TypeId::DATE => f.write_str("DATE"),
-            TypeId::TIME => f.write_str("TIME"),
-            TypeId::DATETIME => f.write_str("DATETIME"),
-            TypeId::TIMESTAMP => f.write_str("TIMESTAMP"),
-
-            id => write!(f, "<{:#x}>", id.0),
+        match self.known_name() {
+            Some(name) => f.write_str(name),
+            None => write!(f, "<{:#x}>", self.id.0),
         }
     }
 }
@@ -148,6 +168,11 @@ impl PartialEq<MySqlTypeInfo> for MySqlTypeInfo {
 }
 
 impl TypeInfo for MySqlTypeInfo {
+    /// Returns `UNKNOWN` for a type byte that we don't recognize.
+    fn name(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self.known_name().unwrap_or("UNKNOWN"))
+    }
+
     fn compatible(&self, other: &Self) -> bool {
         // NOTE: MySQL is weakly typed so much of this may be surprising to a Rust developer.
 
@@ -17,6 +17,17 @@
 //! | `f64`                                 | DOUBLE                                               |
 //! | `&str`, `String`                      | VARCHAR, CHAR, TEXT                                  |
 //! | `&[u8]`, `Vec<u8>`                    | VARBINARY, BINARY, BLOB                              |
+//! | `std::time::SystemTime`               | DATETIME                                             |
+//! | `std::time::Duration`                 | TIME                                                 |
+//!
+//! The `NonZero` integer types from `std::num` (`NonZeroI8` through `NonZeroU64`) are encoded and
+//! decoded as the integer they wrap; decoding a `0` as one of them is an error. The query macros
+//! never choose these, but `query_as!()` accepts them as the type of a field.
+//!
+//! `SystemTime` and `Duration` need no extra dependencies but are only chosen by the query
+//! macros when neither the `chrono` nor the `time` feature is enabled. Both are truncated to
+//! microseconds. A `Duration` must fit in a `TIME`, which is at most `838:59:59`, and a negative
+//! `TIME` cannot be decoded as a `Duration`.
 //!
 //! ### [`chrono`](https://crates.io/crates/chrono)
 //!
@@ -65,6 +76,7 @@ mod bool;
 mod bytes;
 mod float;
 mod int;
+mod std_time;
 mod str;
 mod uint;
 
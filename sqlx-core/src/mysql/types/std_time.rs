@@ -0,0 +1,389 @@
+use std::convert::TryFrom;
+use std::error::Error as StdError;
+use std::str::from_utf8;
+use std::time::{Duration, SystemTime};
+
+use byteorder::LittleEndian;
+
+use crate::civil::{self, DateTime};
+use crate::decode::Decode;
+use crate::encode::Encode;
+use crate::io::{Buf, BufMut};
+use crate::mysql::protocol::TypeId;
+use crate::mysql::type_info::MySqlTypeInfo;
+use crate::mysql::{MySql, MySqlData, MySqlValue};
+use crate::types::Type;
+use crate::Error;
+
+// 838:59:59, the largest value of a TIME
+// https://dev.mysql.com/doc/refman/8.0/en/time.html
+const MAX_TIME_SECS: u64 = 838 * 3600 + 59 * 60 + 59;
+
+impl Type<MySql> for SystemTime {
+    fn type_info() -> MySqlTypeInfo {
+        MySqlTypeInfo::new(TypeId::DATETIME)
+    }
+}
+
+/// MySQL only stores microseconds; any further precision is truncated.
+///
+/// A time whose year does not fit in a `u16` cannot be encoded.
+impl Encode<MySql> for SystemTime {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        // a time out of range is reported by [check] and never sent
+        let dt = match to_datetime(self) {
+            Ok(dt) => dt,
+            Err(_) => return,
+        };
+
+        let len = Encode::<MySql>::size_hint(self) - 1;
+        buf.push(len as u8);
+
+        // MySQL supports years from 1000 - 9999
+        buf.extend_from_slice(&(dt.year as u16).to_le_bytes());
+        buf.push(dt.month);
+        buf.push(dt.day);
+
+        if len > 4 {
+            buf.push(dt.hour);
+            buf.push(dt.minute);
+            buf.push(dt.second);
+        }
+
+        if len > 7 {
+            buf.put_u32::<LittleEndian>(dt.nanosecond / 1000);
+        }
+    }
+
+    fn check(&self) -> Result<(), Box<dyn StdError + Send + Sync>> {
+        to_datetime(self).map(drop)
+    }
+
+    fn size_hint(&self) -> usize {
+        let dt = match to_datetime(self) {
+            Ok(dt) => dt,
+            Err(_) => return 0,
+        };
+
+        // see the `Encode` of `NaiveDateTime`
+        match (dt.hour, dt.minute, dt.second, dt.nanosecond / 1000) {
+            (0, 0, 0, 0) => 5,
+            (_, _, _, 0) => 8,
+            (_, _, _, _) => 12,
+        }
+    }
+}
+
+// The date and time of a `SystemTime` as sent in a DATETIME, whose year is a `u16`
+fn to_datetime(time: &SystemTime) -> Result<DateTime, Box<dyn StdError + Send + Sync>> {
+    civil::unix_timestamp(*time)
+        .and_then(|(secs, nanos)| DateTime::from_unix_timestamp(secs, nanos))
+        .filter(|dt| u16::try_from(dt.year).is_ok())
+        .ok_or_else(|| format!("SystemTime out of range for MySQL: {:?}", time).into())
+}
+
+impl<'de> Decode<'de, MySql> for SystemTime {
+    fn decode(value: MySqlValue<'de>) -> crate::Result<Self> {
+        let dt = match value.try_get()? {
+            MySqlData::Binary(mut buf) => {
+                let len = buf.get_u8()?;
+
+                if len == 0 {
+                    return Err(crate::Error::Decode(
+                        "zero DATETIME cannot be decoded as SystemTime".into(),
+                    ));
+                }
+
+                let year = buf.get_u16::<LittleEndian>()?;
+                let month = buf.get_u8()?;
+                let day = buf.get_u8()?;
+
+                let (hour, minute, second) = if len > 4 {
+                    (buf.get_u8()?, buf.get_u8()?, buf.get_u8()?)
+                } else {
+                    (0, 0, 0)
+                };
+
+                let micros = if len > 7 {
+                    buf.get_u32::<LittleEndian>()?
+                } else {
+                    0
+                };
+
+                DateTime {
+                    year: year as i32,
+                    month,
+                    day,
+                    hour,
+                    minute,
+                    second,
+                    nanosecond: micros * 1000,
+                }
+            }
+
+            MySqlData::Text(buf) => DateTime::parse(from_utf8(buf).map_err(Error::decode)?)?,
+        };
+
+        let (secs, nanos) = dt.unix_timestamp();
+
+        civil::from_unix_timestamp(secs, nanos)
+    }
+}
+
+impl Type<MySql> for Duration {
+    fn type_info() -> MySqlTypeInfo {
+        MySqlTypeInfo::new(TypeId::TIME)
+    }
+}
+
+/// MySQL only stores microseconds; any further precision is truncated.
+///
+/// A duration longer than `838:59:59`, the largest value of a `TIME`, cannot be encoded.
+impl Encode<MySql> for Duration {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        // a duration out of range is reported by [check] and never sent
+        if Encode::<MySql>::check(self).is_err() {
+            return;
+        }
+
+        let secs = self.as_secs();
+        let micros = self.subsec_micros();
+
+        let len = Encode::<MySql>::size_hint(self) - 1;
+        buf.push(len as u8);
+
+        // Duration is not negative
+        buf.push(0);
+
+        buf.put_u32::<LittleEndian>((secs / 86_400) as u32);
+        buf.push((secs / 3600 % 24) as u8);
+        buf.push((secs / 60 % 60) as u8);
+        buf.push((secs % 60) as u8);
+
+        if len > 8 {
+            buf.put_u32::<LittleEndian>(micros);
+        }
+    }
+
+    fn check(&self) -> Result<(), Box<dyn StdError + Send + Sync>> {
+        let secs = self.as_secs();
+
+        if secs > MAX_TIME_SECS || (secs == MAX_TIME_SECS && self.subsec_micros() > 0) {
+            return Err(format!("Duration out of range for MySQL TIME: {:?}", self).into());
+        }
+
+        Ok(())
+    }
+
+    fn size_hint(&self) -> usize {
+        if self.subsec_micros() == 0 {
+            9
+        } else {
+            13
+        }
+    }
+}
+
+impl<'de> Decode<'de, MySql> for Duration {
+    fn decode(value: MySqlValue<'de>) -> crate::Result<Self> {
+        match value.try_get()? {
+            MySqlData::Binary(mut buf) => {
+                // data length, expecting 0, 8 or 12 (fractional seconds)
+                let len = buf.get_u8()?;
+
+                if len == 0 {
+                    return Ok(Duration::from_secs(0));
+                }
+
+                if buf.get_u8()? != 0 {
+                    return Err(crate::Error::Decode(
+                        "negative TIME cannot be decoded as Duration".into(),
+                    ));
+                }
+
+                let days = buf.get_u32::<LittleEndian>()? as u64;
+                let hours = buf.get_u8()? as u64;
+                let minutes = buf.get_u8()? as u64;
+                let seconds = buf.get_u8()? as u64;
+
+                let micros = if len > 8 {
+                    buf.get_u32::<LittleEndian>()?
+                } else {
+                    0
+                };
+
+                Ok(Duration::new(
+                    days * 86_400 + hours * 3600 + minutes * 60 + seconds,
+                    micros * 1000,
+                ))
+            }
+
+            MySqlData::Text(buf) => {
+                let s = from_utf8(buf).map_err(Error::decode)?;
+
+                parse_time(s).ok_or_else(|| {
+                    if s.starts_with('-') {
+                        crate::Error::Decode("negative TIME cannot be decoded as Duration".into())
+                    } else {
+                        decode_err!("invalid TIME: {:?}", s)
+                    }
+                })
+            }
+        }
+    }
+}
+
+// HHH:MM:SS[.ffffff]
+fn parse_time(s: &str) -> Option<Duration> {
+    let (clock, fraction) = match s.find('.') {
+        Some(at) => (&s[..at], &s[at + 1..]),
+        None => (s, ""),
+    };
+
+    let mut fields = clock.splitn(3, ':').map(|field| {
+        if field.bytes().all(|b| b.is_ascii_digit()) {
+            field.parse::<u64>().ok()
+        } else {
+            None
+        }
+    });
+
+    let hours = fields.next()??;
+    let minutes = fields.next()??;
+    let seconds = fields.next()??;
+
+    let micros = if fraction.is_empty() {
+        0
+    } else {
+        let digits = &fraction[..fraction.len().min(6)];
+
+        digits.parse::<u32>().ok()? * 10_u32.pow(6 - digits.len() as u32)
+    };
+
+    Some(Duration::new(
+        hours * 3600 + minutes * 60 + seconds,
+        micros * 1000,
+    ))
+}
+
+#[test]
+fn test_encode_duration() {
+    let mut buf = Vec::new();
+
+    // 26:03:04.000005
+    let duration = Duration::new(26 * 3600 + 3 * 60 + 4, 5_000);
+    Encode::<MySql>::encode(&duration, &mut buf);
+    assert_eq!(*buf, [12, 0, 1, 0, 0, 0, 2, 3, 4, 5, 0, 0, 0]);
+
+    buf.clear();
+
+    Encode::<MySql>::encode(&Duration::from_secs(59), &mut buf);
+    assert_eq!(*buf, [8, 0, 0, 0, 0, 0, 0, 0, 59]);
+}
+
+#[test]
+fn test_encode_duration_out_of_range() {
+    let max = Duration::from_secs(MAX_TIME_SECS);
+
+    assert!(Encode::<MySql>::check(&max).is_ok());
+
+    for duration in &[
+        max + Duration::from_micros(1),
+        max + Duration::from_secs(1),
+        Duration::from_secs(u64::MAX),
+    ] {
+        let err = Encode::<MySql>::check(duration).unwrap_err();
+        assert!(err.to_string().contains("out of range"));
+
+        let mut buf = Vec::new();
+        Encode::<MySql>::encode(duration, &mut buf);
+        assert!(buf.is_empty());
+    }
+}
+
+#[test]
+fn test_decode_duration() {
+    let decode = |bytes: &[u8]| -> crate::Result<Duration> {
+        Decode::<MySql>::decode(MySqlValue::binary(MySqlTypeInfo::new(TypeId::TIME), bytes))
+    };
+
+    assert_eq!(
+        decode(&[12, 0, 1, 0, 0, 0, 2, 3, 4, 5, 0, 0, 0]).unwrap(),
+        Duration::new(26 * 3600 + 3 * 60 + 4, 5_000)
+    );
+
+    assert!(decode(&[8, 1, 0, 0, 0, 0, 0, 0, 59]).is_err());
+
+    let decode = |s: &str| -> crate::Result<Duration> {
+        Decode::<MySql>::decode(MySqlValue::text(
+            MySqlTypeInfo::new(TypeId::TIME),
+            s.as_bytes(),
+        ))
+    };
+
+    assert_eq!(
+        decode("838:59:59").unwrap(),
+        Duration::from_secs(MAX_TIME_SECS)
+    );
+    assert_eq!(
+        decode("12:00:00.25").unwrap(),
+        Duration::from_millis(43_200_250)
+    );
+
+    assert!(decode("-00:00:01").is_err());
+}
+
+#[test]
+fn test_encode_system_time() {
+    let mut buf = Vec::new();
+
+    // test values from https://dev.mysql.com/doc/internals/en/binary-protocol-value.html
+    // 2010-10-17 19:27:30.000001
+    let time = std::time::UNIX_EPOCH + Duration::new(1_287_343_650, 1_000);
+    Encode::<MySql>::encode(&time, &mut buf);
+    assert_eq!(*buf, [11, 218, 7, 10, 17, 19, 27, 30, 1, 0, 0, 0]);
+
+    buf.clear();
+
+    // 1969-07-20 20:17:40
+    let time = std::time::UNIX_EPOCH - Duration::from_secs(14_182_940);
+    Encode::<MySql>::encode(&time, &mut buf);
+    assert_eq!(*buf, [7, 177, 7, 7, 20, 20, 17, 40]);
+}
+
+#[test]
+fn test_encode_system_time_out_of_range() {
+    use crate::arguments::Arguments;
+    use crate::mysql::MySqlArguments;
+
+    let new_year = |year| {
+        let (secs, _) = DateTime {
+            year,
+            month: 1,
+            day: 1,
+            hour: 0,
+            minute: 0,
+            second: 0,
+            nanosecond: 0,
+        }
+        .unix_timestamp();
+
+        civil::from_unix_timestamp(secs, 0).unwrap()
+    };
+
+    let second = Duration::from_secs(1);
+
+    assert!(Encode::<MySql>::check(&new_year(0)).is_ok());
+    assert!(Encode::<MySql>::check(&(new_year(65536) - second)).is_ok());
+
+    for time in &[new_year(0) - second, new_year(65536)] {
+        let err = Encode::<MySql>::check(time).unwrap_err();
+        assert!(err.to_string().contains("out of range"));
+
+        assert_eq!(Encode::<MySql>::size_hint(time), 0);
+
+        let mut arguments = MySqlArguments::default();
+        arguments.add(*time);
+        assert!(arguments.error.is_some());
+    }
+}
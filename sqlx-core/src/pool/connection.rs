@@ -5,7 +5,11 @@ use std::sync::Arc;
 use std::time::Instant;
 
 use super::inner::{DecrementSizeGuard, SharedPool};
-use crate::connection::{Connect, Connection};
+use crate::connection::{Connect, Connection, StatementCacheMetrics};
+use crate::database::Database;
+use crate::logging::LogSettings;
+use crate::statement::SqlRedaction;
+use crate::transaction::Transaction;
 
 /// A connection checked out from [`Pool`][crate::pool::Pool].
 ///
@@ -74,13 +78,19 @@ where
     }
 }
 
-impl<C> Connection for PoolConnection<C>
+impl<C, DB> Connection for PoolConnection<C>
 where
-    C: Connect,
+    C: Connect<Database = DB>,
+    DB: Database<Connection = C>,
 {
+    fn begin(&mut self) -> BoxFuture<'_, crate::Result<Transaction<'_, DB>>> {
+        Box::pin(Transaction::new(0, &mut **self))
+    }
+
     fn close(mut self) -> BoxFuture<'static, crate::Result<()>> {
         Box::pin(async move {
-            let live = self.live.take().expect("PoolConnection double-dropped");
+            let mut live = self.live.take().expect("PoolConnection double-dropped");
+            live.return_statement_cache_metrics(&self.pool);
             live.float(&self.pool).into_idle().close().await
         })
     }
@@ -89,21 +99,89 @@ where
     fn ping(&mut self) -> BoxFuture<crate::Result<()>> {
         Box::pin(self.deref_mut().ping())
     }
+
+    #[inline]
+    fn statement_cache_metrics(&self) -> StatementCacheMetrics {
+        self.deref().statement_cache_metrics()
+    }
+
+    #[inline]
+    fn reset_statement_cache_metrics(&mut self) {
+        self.deref_mut().reset_statement_cache_metrics()
+    }
+
+    #[inline]
+    fn set_log_settings(&mut self, settings: LogSettings) {
+        self.deref_mut().set_log_settings(settings)
+    }
+
+    #[inline]
+    fn set_sql_redaction(&mut self, redaction: SqlRedaction) {
+        self.deref_mut().set_sql_redaction(redaction)
+    }
+
+    #[inline]
+    fn queue_rollback(&mut self, statement: &str) -> bool {
+        self.deref_mut().queue_rollback(statement)
+    }
+
+    #[inline]
+    fn flush_queued_now(&mut self) -> bool {
+        self.deref_mut().flush_queued_now()
+    }
+}
+
+impl<C> PoolConnection<C>
+where
+    C: Connect,
+{
+    // Drops the connection instead of returning it to the pool, closing it on a best-effort basis
+    pub(crate) fn discard(&mut self) {
+        if let Some(mut live) = self.live.take() {
+            live.return_statement_cache_metrics(&self.pool);
+
+            drop(live.float(&self.pool));
+        }
+    }
 }
 
 /// Returns the connection to the [`Pool`][crate::pool::Pool] it was checked-out from.
+///
+/// If the pool has been closed, the connection is dropped instead. This closes it on a
+/// best-effort basis; see [`Connection::close`].
+///
+/// A connection with a query still to be sent, such as the `ROLLBACK` of a transaction that was
+/// dropped, has it sent right away so that the server runs it while the connection is idle; the
+/// response is read the next time the connection is used. If it cannot all be sent without
+/// waiting, the connection is dropped instead, which has the server roll the transaction back.
 impl<C> Drop for PoolConnection<C>
 where
     C: Connect,
 {
     fn drop(&mut self) {
-        if let Some(live) = self.live.take() {
-            self.pool.release(live.float(&self.pool));
+        if let Some(mut live) = self.live.take() {
+            live.return_statement_cache_metrics(&self.pool);
+
+            // a lock taken by the transaction would be held until the `ROLLBACK` is sent
+            if self.pool.is_closed() || !live.raw.flush_queued_now() {
+                drop(live.float(&self.pool));
+            } else {
+                self.pool.release(live.float(&self.pool));
+            }
         }
     }
 }
 
 impl<C> Live<C> {
+    // Adds what the connection did while it was checked out to the totals of the pool
+    fn return_statement_cache_metrics(&mut self, pool: &SharedPool<C>)
+    where
+        C: Connection,
+    {
+        pool.add_statement_cache_metrics(self.raw.statement_cache_metrics());
+        self.raw.reset_statement_cache_metrics();
+    }
+
     pub fn float(self, pool: &SharedPool<C>) -> Floating<Self> {
         Floating {
             inner: self,
@@ -155,7 +233,7 @@ impl<'s, C> Floating<'s, Live<C>> {
     where
         C: Connect,
     {
-        let Floating { inner, guard } = self;
+        let Floating { mut inner, guard } = self;
 
         debug_assert!(
             guard.same_pool(pool),
@@ -163,6 +241,10 @@ impl<'s, C> Floating<'s, Live<C>> {
         );
 
         guard.cancel();
+
+        // only count what happens while the connection is checked out
+        inner.raw.reset_statement_cache_metrics();
+
         PoolConnection {
             live: Some(inner),
             pool: Arc::clone(pool),
@@ -192,6 +274,10 @@ impl<'s, C> Floating<'s, Idle<C>> {
         self.live.raw.ping().await
     }
 
+    pub fn raw_mut(&mut self) -> &mut C {
+        &mut self.inner.live.raw
+    }
+
     pub fn into_live(self) -> Floating<'s, Live<C>> {
         Floating {
             inner: self.inner.live,
@@ -0,0 +1,20 @@
+use crate::connection::StatementCacheMetrics;
+
+/// Something that happened in a [`Pool`], as given to the handler set with
+/// [`Builder::event_handler`].
+///
+/// [`Pool`]: crate::pool::Pool
+/// [`Builder::event_handler`]: crate::pool::Builder::event_handler
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum PoolEvent {
+    /// A connection that was checked out has been returned to the pool, or closed.
+    ///
+    /// `statement_cache_metrics` are those of the connection while it was checked out, which
+    /// have just been added to [`Pool::statement_cache_metrics`].
+    ///
+    /// [`Pool::statement_cache_metrics`]: crate::pool::Pool::statement_cache_metrics
+    Released {
+        statement_cache_metrics: StatementCacheMetrics,
+    },
+}
@@ -1,9 +1,10 @@
 use std::cmp;
+use std::io;
 use std::mem;
 use std::ptr;
-use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use crossbeam_queue::{ArrayQueue, SegQueue};
 use futures_core::task::{Poll, Waker};
@@ -12,22 +13,35 @@ use futures_util::future;
 use crate::pool::deadline_as_timeout;
 use crate::runtime::{sleep, spawn, timeout};
 use crate::{
-    connection::{Connect, Connection},
+    connection::{Connect, Connection, StatementCacheMetrics},
     error::Error,
 };
 
 use super::connection::{Floating, Idle, Live};
-use super::Options;
+use super::{Connector, EventHandler, Options, PoolEvent, Recycle};
 
 pub(crate) struct SharedPool<C> {
     url: String,
+    connector: Option<Connector<C>>,
+    recycle: Option<Recycle<C>>,
+    event_handler: Option<EventHandler>,
     idle_conns: ArrayQueue<Idle<C>>,
     waiters: SegQueue<Waker>,
     pub(super) size: AtomicU32,
     is_closed: AtomicBool,
+    cache_metrics: SharedCacheMetrics,
     options: Options,
 }
 
+// [StatementCacheMetrics] of the connections returned to the pool so far
+#[derive(Default)]
+struct SharedCacheMetrics {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+    round_trips: AtomicU64,
+}
+
 impl<C> SharedPool<C>
 where
     C: Connection,
@@ -44,6 +58,46 @@ where
         self.size.load(Ordering::Acquire)
     }
 
+    pub(super) fn statement_cache_metrics(&self) -> StatementCacheMetrics {
+        let metrics = &self.cache_metrics;
+
+        StatementCacheMetrics {
+            hits: metrics.hits.load(Ordering::Relaxed),
+            misses: metrics.misses.load(Ordering::Relaxed),
+            evictions: metrics.evictions.load(Ordering::Relaxed),
+            round_trips: metrics.round_trips.load(Ordering::Relaxed),
+        }
+    }
+
+    // Adds the metrics of a connection that is being released, and reports them
+    pub(super) fn add_statement_cache_metrics(&self, added: StatementCacheMetrics) {
+        let metrics = &self.cache_metrics;
+
+        metrics.hits.fetch_add(added.hits, Ordering::Relaxed);
+        metrics.misses.fetch_add(added.misses, Ordering::Relaxed);
+        metrics
+            .evictions
+            .fetch_add(added.evictions, Ordering::Relaxed);
+        metrics
+            .round_trips
+            .fetch_add(added.round_trips, Ordering::Relaxed);
+
+        if let Some(event_handler) = &self.event_handler {
+            event_handler(&PoolEvent::Released {
+                statement_cache_metrics: added,
+            });
+        }
+    }
+
+    pub(super) fn reset_statement_cache_metrics(&self) {
+        let metrics = &self.cache_metrics;
+
+        metrics.hits.store(0, Ordering::Relaxed);
+        metrics.misses.store(0, Ordering::Relaxed);
+        metrics.evictions.store(0, Ordering::Relaxed);
+        metrics.round_trips.store(0, Ordering::Relaxed);
+    }
+
     pub(super) fn num_idle(&self) -> usize {
         // NOTE: This is very expensive
         self.idle_conns.len()
@@ -55,10 +109,15 @@ where
 
     pub(super) async fn close(&self) {
         self.is_closed.store(true, Ordering::Release);
-        while let Ok(_) = self.idle_conns.pop() {}
+
+        // anyone waiting for a connection gives up right away instead of after closing these
         while let Ok(waker) = self.waiters.pop() {
             waker.wake();
         }
+
+        while let Ok(idle) = self.idle_conns.pop() {
+            let _ = Floating::from_idle(idle, self).close().await;
+        }
     }
 
     #[inline]
@@ -117,6 +176,12 @@ where
                     // only push the waker once
                     self.waiters.push(ctx.waker().to_owned());
                     waker_pushed = true;
+
+                    // the pool may have been closed (and its waiters woken) in the meantime
+                    if self.is_closed() {
+                        return Poll::Ready(());
+                    }
+
                     Poll::Pending
                 } else {
                     Poll::Ready(())
@@ -132,13 +197,23 @@ impl<C> SharedPool<C>
 where
     C: Connect,
 {
-    pub(super) async fn new_arc(url: &str, options: Options) -> crate::Result<Arc<Self>> {
+    pub(super) async fn new_arc(
+        url: &str,
+        options: Options,
+        connector: Option<Connector<C>>,
+        recycle: Option<Recycle<C>>,
+        event_handler: Option<EventHandler>,
+    ) -> crate::Result<Arc<Self>> {
         let mut pool = Self {
             url: url.to_owned(),
+            connector,
+            recycle,
+            event_handler,
             idle_conns: ArrayQueue::new(options.max_size as usize),
             waiters: SegQueue::new(),
             size: AtomicU32::new(0),
             is_closed: AtomicBool::new(false),
+            cache_metrics: SharedCacheMetrics::default(),
             options,
         };
 
@@ -151,9 +226,14 @@ where
         Ok(pool)
     }
 
-    pub(super) async fn acquire<'s>(&'s self) -> crate::Result<Floating<'s, Live<C>>> {
-        let start = Instant::now();
-        let deadline = start + self.options.connect_timeout;
+    pub(super) async fn acquire<'s>(
+        &'s self,
+        timeout: Duration,
+    ) -> crate::Result<Floating<'s, Live<C>>> {
+        let deadline = Instant::now() + timeout;
+
+        // the error of the last attempt to open a connection, if we are to try again
+        let mut connect_error = None;
 
         // Unless the pool has been closed ...
         while !self.is_closed() {
@@ -161,14 +241,14 @@ where
             // if there is an idle connection in our channel.
             if let Ok(conn) = self.idle_conns.pop() {
                 let conn = Floating::from_idle(conn, self);
-                if let Some(live) = check_conn(conn, &self.options).await {
+                if let Some(live) = check_conn(conn, self).await {
                     return Ok(live);
                 }
             }
 
             if let Some(guard) = self.try_increment_size() {
                 // pool has slots available; open a new connection
-                match self.connect(deadline, guard).await {
+                match self.connect(deadline, guard, &mut connect_error).await {
                     Ok(Some(conn)) => return Ok(conn),
                     // [size] is internally decremented on _retry_ and _error_
                     Ok(None) => continue,
@@ -188,12 +268,13 @@ where
     async fn init_min_connections(&mut self) -> crate::Result<()> {
         for _ in 0..self.options.min_size {
             let deadline = Instant::now() + self.options.connect_timeout;
+            let mut connect_error = None;
 
             // this guard will prevent us from exceeding `max_size`
             while let Some(guard) = self.try_increment_size() {
                 // [connect] will raise an error when past deadline
                 // [connect] returns None if its okay to retry
-                if let Some(conn) = self.connect(deadline, guard).await? {
+                if let Some(conn) = self.connect(deadline, guard, &mut connect_error).await? {
                     self.idle_conns
                         .push(conn.into_idle().into_leakable())
                         .expect("BUG: connection queue overflow in init_min_connections");
@@ -204,37 +285,77 @@ where
         Ok(())
     }
 
+    /// Open a new connection, giving up once `deadline` passes.
+    ///
+    /// Returns `None` if the attempt failed but may be retried, keeping its error in
+    /// `connect_error` to be reported should we run out of time while retrying.
     async fn connect<'s>(
         &'s self,
         deadline: Instant,
         guard: DecrementSizeGuard<'s>,
+        connect_error: &mut Option<Error>,
     ) -> crate::Result<Option<Floating<'s, Live<C>>>> {
         if self.is_closed() {
             return Err(Error::PoolClosed);
         }
 
-        let timeout = super::deadline_as_timeout::<C::Database>(deadline)?;
+        let timed_out = |connect_error: &mut Option<Error>| {
+            Error::PoolTimedOut(Some(match connect_error.take() {
+                Some(error) => Box::new(error),
+                None => Box::new(io::Error::from(io::ErrorKind::TimedOut)),
+            }))
+        };
+
+        let timeout = match deadline.checked_duration_since(Instant::now()) {
+            Some(timeout) => timeout,
+            None => return Err(timed_out(connect_error)),
+        };
+
+        let connect = match &self.connector {
+            Some(connector) => connector(&self.url),
+            None => C::connect(&self.url),
+        };
 
         // result here is `Result<Result<C, Error>, TimeoutError>`
-        match crate::runtime::timeout(timeout, C::connect(&self.url)).await {
+        match crate::runtime::timeout(timeout, connect).await {
+            // the pool was closed while we were connecting; this is closed on a best-effort
+            // basis as it is dropped
+            Ok(Ok(_)) if self.is_closed() => Err(Error::PoolClosed),
+
             // successfully established connection
-            Ok(Ok(raw)) => Ok(Some(Floating::new_live(raw, guard))),
+            Ok(Ok(mut raw)) => {
+                if let Some(settings) = &self.options.log_settings {
+                    raw.set_log_settings(settings.clone());
+                }
+
+                if let Some(redaction) = self.options.sql_redaction {
+                    raw.set_sql_redaction(redaction);
+                }
+
+                Ok(Some(Floating::new_live(raw, guard)))
+            }
 
             // an IO error while connecting is assumed to be the system starting up
-            Ok(Err(crate::Error::Io(_))) => Ok(None),
+            Ok(Err(error @ crate::Error::Io(_))) => {
+                *connect_error = Some(error);
+                Ok(None)
+            }
 
             // TODO: Handle other database "boot period"s
 
             // [postgres] the database system is starting up
             // TODO: Make this check actually check if this is postgres
-            Ok(Err(crate::Error::Database(error))) if error.code() == Some("57P03") => Ok(None),
+            Ok(Err(crate::Error::Database(error))) if error.code() == Some("57P03") => {
+                *connect_error = Some(crate::Error::Database(error));
+                Ok(None)
+            }
 
             // Any other error while connection should immediately
             // terminate and bubble the error up
             Ok(Err(e)) => Err(e),
 
             // timed out
-            Err(e) => Err(crate::Error::PoolTimedOut(Some(Box::new(e)))),
+            Err(_) => Err(timed_out(connect_error)),
         }
     }
 }
@@ -257,11 +378,13 @@ fn is_beyond_idle<C>(idle: &Idle<C>, options: &Options) -> bool {
 
 async fn check_conn<'s: 'p, 'p, C>(
     mut conn: Floating<'s, Idle<C>>,
-    options: &'p Options,
+    pool: &'p SharedPool<C>,
 ) -> Option<Floating<'s, Live<C>>>
 where
     C: Connection,
 {
+    let options = &pool.options;
+
     // If the connection we pulled has expired, close the connection and
     // immediately create a new connection
     if is_beyond_lifetime(&conn, options) {
@@ -282,6 +405,22 @@ where
         }
     }
 
+    if let Some(recycle) = &pool.recycle {
+        match recycle(conn.raw_mut()).await {
+            Ok(false) => {}
+
+            Ok(true) => {
+                let _ = conn.close().await;
+                return None;
+            }
+
+            Err(e) => {
+                log::info!("recycle check on idle connection returned error: {}", e);
+                return None;
+            }
+        }
+    }
+
     // No need to re-connect; connection is alive or we don't care
     Some(conn.into_live())
 }
@@ -299,10 +438,15 @@ where
         (None, None) => return,
     };
 
-    let pool = Arc::clone(&pool);
+    // hold only a weak reference so that dropping every `Pool` drops (and closes) its connections
+    let weak = Arc::downgrade(pool);
 
     spawn(async move {
-        while !pool.is_closed.load(Ordering::Acquire) {
+        while let Some(pool) = weak.upgrade() {
+            if pool.is_closed() {
+                break;
+            }
+
             // reap at most the current size minus the minimum idle
             let max_reaped = pool.size().saturating_sub(pool.options.min_size);
 
@@ -325,6 +469,7 @@ where
                 let _ = conn.close().await;
             }
 
+            drop(pool);
             sleep(period).await;
         }
     });
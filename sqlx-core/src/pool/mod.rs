@@ -2,23 +2,25 @@
 
 use std::{
     fmt,
-    sync::Arc,
+    sync::{Arc, Weak},
     time::{Duration, Instant},
 };
 
-use crate::connection::Connect;
+use crate::connection::{Connect, StatementCacheMetrics};
 use crate::database::Database;
 use crate::transaction::Transaction;
 
 use self::inner::SharedPool;
-use self::options::Options;
+use self::options::{Connector, EventHandler, Options, Recycle};
 
 mod connection;
+mod event;
 mod executor;
 mod inner;
 mod options;
 
 pub use self::connection::PoolConnection;
+pub use self::event::PoolEvent;
 pub use self::options::Builder;
 
 /// A pool of database connections.
@@ -39,8 +41,15 @@ where
         Self::builder().build(url).await
     }
 
-    async fn with_options(url: &str, options: Options) -> crate::Result<Self> {
-        let inner = SharedPool::<C>::new_arc(url, options).await?;
+    async fn with_options(
+        url: &str,
+        options: Options,
+        connector: Option<Connector<C>>,
+        recycle: Option<Recycle<C>>,
+        event_handler: Option<EventHandler>,
+    ) -> crate::Result<Self> {
+        let inner =
+            SharedPool::<C>::new_arc(url, options, connector, recycle, event_handler).await?;
 
         Ok(Pool(inner))
     }
@@ -54,7 +63,22 @@ where
     ///
     /// Waits for at most the configured connection timeout before returning an error.
     pub async fn acquire(&self) -> crate::Result<PoolConnection<C>> {
-        self.0.acquire().await.map(|conn| conn.attach(&self.0))
+        self.acquire_timeout(self.0.options().connect_timeout).await
+    }
+
+    /// Retrieves a connection from the pool, waiting for at most `timeout` instead of the
+    /// configured connection timeout.
+    ///
+    /// If there is no idle connection, the time spent opening a new one counts towards `timeout`
+    /// as well. Once it elapses, [`Error::PoolTimedOut`] is returned; see there for how to tell
+    /// waiting for a connection apart from establishing one.
+    ///
+    /// [`Error::PoolTimedOut`]: crate::Error::PoolTimedOut
+    pub async fn acquire_timeout(&self, timeout: Duration) -> crate::Result<PoolConnection<C>> {
+        self.0
+            .acquire(timeout)
+            .await
+            .map(|conn| conn.attach(&self.0))
     }
 
     /// Attempts to retrieve a connection from the pool if there is one available.
@@ -65,18 +89,38 @@ where
     }
 
     /// Retrieves a new connection and immediately begins a new transaction.
-    pub async fn begin(&self) -> crate::Result<Transaction<PoolConnection<C>>> {
+    ///
+    /// The transaction holds onto the connection and returns it to the pool once it is
+    /// finished.
+    pub async fn begin(&self) -> crate::Result<Transaction<'static, C::Database>>
+    where
+        C::Database: Database<Connection = C>,
+    {
         Ok(Transaction::new(0, self.acquire().await?).await?)
     }
 
     /// Ends the use of a connection pool. Prevents any new connections
     /// and will close all active connections when they are returned to the pool.
     ///
-    /// Does not resolve until all connections are closed.
+    /// Does not resolve until all idle connections are closed. Each is closed with
+    /// [`Connection::close`][crate::connection::Connection::close], which waits for the server to be told that the client is going
+    /// away. Connections that are returned to the pool afterwards are only closed on a
+    /// best-effort basis, as are the idle connections of a pool that is dropped without being
+    /// closed.
     pub async fn close(&self) {
         self.0.close().await;
     }
 
+    /// Creates a [WeakPool] handle to this pool, which does not keep it alive.
+    ///
+    /// This is meant for long-running background tasks, which should not be the reason that
+    /// a pool outlives the rest of the application; such a task can instead
+    /// [upgrade][WeakPool::upgrade] the weak handle whenever it needs the pool and stop once
+    /// that fails.
+    pub fn downgrade(&self) -> WeakPool<C> {
+        WeakPool(Arc::downgrade(&self.0))
+    }
+
     /// Returns `true` if [`.close()`][Pool::close] has been called on the pool, `false` otherwise.
     pub fn is_closed(&self) -> bool {
         self.0.is_closed()
@@ -92,6 +136,24 @@ where
         self.0.num_idle()
     }
 
+    /// Returns the combined [`statement_cache_metrics`] of every connection that has been
+    /// returned to the pool, or closed, since it was created or these counters were last
+    /// [reset][Pool::reset_statement_cache_metrics].
+    ///
+    /// Connections that are still checked out are not included yet. To follow the metrics of
+    /// each connection as it is returned instead, see [`Builder::event_handler`].
+    ///
+    /// [`statement_cache_metrics`]: crate::connection::Connection::statement_cache_metrics
+    pub fn statement_cache_metrics(&self) -> StatementCacheMetrics {
+        self.0.statement_cache_metrics()
+    }
+
+    /// Resets all counters of [`.statement_cache_metrics()`][Pool::statement_cache_metrics]
+    /// to zero.
+    pub fn reset_statement_cache_metrics(&self) {
+        self.0.reset_statement_cache_metrics()
+    }
+
     /// Returns the configured maximum pool size.
     pub fn max_size(&self) -> u32 {
         self.0.options().max_size
@@ -140,6 +202,60 @@ where
     }
 }
 
+/// A weak handle to a [Pool], created with [Pool::downgrade].
+///
+/// This does not keep the pool from being dropped once every [Pool] handle to it (and every
+/// [PoolConnection] acquired from it) is gone.
+///
+/// ```rust,ignore
+/// let weak = pool.downgrade();
+///
+/// spawn(async move {
+///     // stops once the application has closed or dropped the pool
+///     while let Some(pool) = weak.upgrade() {
+///         let _ = sqlx::query("DELETE FROM sessions WHERE expires < now()")
+///             .execute(&pool)
+///             .await;
+///
+///         // let go of the pool while waiting for the next run
+///         drop(pool);
+///         sleep(Duration::from_secs(60)).await;
+///     }
+/// });
+/// ```
+pub struct WeakPool<C>(Weak<SharedPool<C>>);
+
+impl<C> WeakPool<C>
+where
+    C: Connect,
+{
+    /// Returns a [Pool] handle if the pool is still around and has not been
+    /// [closed][Pool::close].
+    pub fn upgrade(&self) -> Option<Pool<C>> {
+        let pool = self.0.upgrade()?;
+
+        if pool.is_closed() {
+            return None;
+        }
+
+        Some(Pool(pool))
+    }
+}
+
+impl<C> Clone for WeakPool<C> {
+    fn clone(&self) -> Self {
+        Self(Weak::clone(&self.0))
+    }
+}
+
+impl<C> fmt::Debug for WeakPool<C> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("WeakPool")
+            .field("is_alive", &(self.0.strong_count() > 0))
+            .finish()
+    }
+}
+
 /// get the time between the deadline and now and use that as our timeout
 ///
 /// returns `Error::PoolTimedOut` if the deadline is in the past
@@ -158,5 +274,7 @@ fn assert_pool_traits() {
     fn assert_pool<C: Connect>() {
         assert_send_sync::<Pool<C>>();
         assert_clone::<Pool<C>>();
+        assert_send_sync::<WeakPool<C>>();
+        assert_clone::<WeakPool<C>>();
     }
 }
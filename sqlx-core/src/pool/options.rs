@@ -1,13 +1,35 @@
 use std::{marker::PhantomData, time::Duration};
 
-use super::Pool;
+use futures_core::future::BoxFuture;
+
+use super::{Pool, PoolEvent};
 use crate::connection::Connect;
 use crate::database::Database;
+use crate::logging::LogSettings;
+use crate::statement::SqlRedaction;
+
+/// Opens a new connection for the pool from the pool's URL.
+///
+/// See [`Builder::connector`].
+pub(crate) type Connector<C> =
+    Box<dyn Fn(&str) -> BoxFuture<'static, crate::Result<C>> + Send + Sync + 'static>;
+
+/// Decides if an idle connection should be closed instead of being handed out again.
+pub(crate) type Recycle<C> =
+    Box<dyn for<'c> Fn(&'c mut C) -> BoxFuture<'c, crate::Result<bool>> + Send + Sync + 'static>;
+
+/// Receives every [PoolEvent] of the pool.
+///
+/// See [`Builder::event_handler`].
+pub(crate) type EventHandler = Box<dyn Fn(&PoolEvent) + Send + Sync + 'static>;
 
 /// Builder for [Pool].
 pub struct Builder<C> {
     phantom: PhantomData<C>,
     options: Options,
+    connector: Option<Connector<C>>,
+    recycle: Option<Recycle<C>>,
+    event_handler: Option<EventHandler>,
 }
 
 impl<C> Builder<C>
@@ -35,7 +57,13 @@ where
                 idle_timeout: None,
                 // If true, test the health of a connection on acquire
                 test_on_acquire: true,
+                // keep the default of each connection
+                log_settings: None,
+                sql_redaction: None,
             },
+            connector: None,
+            recycle: None,
+            event_handler: None,
         }
     }
 
@@ -47,7 +75,14 @@ where
 
     /// Set the amount of time to attempt connecting to the database.
     ///
-    /// If this timeout elapses, [`Pool::acquire`] will return an error.
+    /// If this timeout elapses, [`Pool::acquire`] will return an error. It can be overridden
+    /// for a single call with [`Pool::acquire_timeout`].
+    ///
+    /// Connections are opened with [`Connect::connect`] and so are retried as configured by
+    /// the `connect_retries` parameter of the connection URL (or by the connect options given
+    /// to `build_with`); the time spent waiting between retries counts towards this timeout.
+    ///
+    /// [`Connect::connect`]: crate::connection::Connect::connect
     pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
         self.options.connect_timeout = connect_timeout;
         self
@@ -107,6 +142,87 @@ where
         self
     }
 
+    /// Set how the statements run on the connections of this pool are logged.
+    ///
+    /// This is applied to every connection as it is opened; see
+    /// [`Connection::set_log_settings`] to change it for a single connection.
+    ///
+    /// [`Connection::set_log_settings`]: crate::connection::Connection::set_log_settings
+    pub fn log_settings(mut self, settings: LogSettings) -> Self {
+        self.options.log_settings = Some(settings);
+        self
+    }
+
+    /// Set how much of the SQL of the statements run on the connections of this pool is kept
+    /// for [`Statement::sql`], and so for the errors that include it.
+    ///
+    /// This is applied to every connection as it is opened; see
+    /// [`Connection::set_sql_redaction`] to change it for a single connection.
+    ///
+    /// [`Statement::sql`]: crate::statement::Statement::sql
+    /// [`Connection::set_sql_redaction`]: crate::connection::Connection::set_sql_redaction
+    pub fn sql_redaction(mut self, redaction: SqlRedaction) -> Self {
+        self.options.sql_redaction = Some(redaction);
+        self
+    }
+
+    /// Replace how the pool opens new connections; by default this is [`Connect::connect`]
+    /// with the URL given to [`build`].
+    ///
+    /// `connector` is given that URL every time the pool needs a new connection. Together with
+    /// `connect_with_stream`, this lets pooled connections go through a proxy or a tunnel:
+    ///
+    /// ```rust,ignore
+    /// let pool = PgPool::builder()
+    ///     .connector(|url| {
+    ///         let url = url.to_owned();
+    ///
+    ///         Box::pin(async move {
+    ///             let stream = open_tunnel().await?;
+    ///
+    ///             PgConnection::connect_with_stream(stream, &*url).await
+    ///         })
+    ///     })
+    ///     .build("postgres://db.internal/app")
+    ///     .await?;
+    /// ```
+    ///
+    /// [`build`]: #method.build
+    pub fn connector<F>(mut self, connector: F) -> Self
+    where
+        F: Fn(&str) -> BoxFuture<'static, crate::Result<C>> + Send + Sync + 'static,
+    {
+        self.connector = Some(Box::new(connector));
+        self
+    }
+
+    /// Check idle connections with `recycle` before they are handed out again; if it returns
+    /// `true` (or an error), the connection is closed and another one is acquired.
+    #[cfg_attr(not(feature = "sqlite"), allow(dead_code))]
+    pub(crate) fn recycle_if<F>(mut self, recycle: F) -> Self
+    where
+        F: for<'c> Fn(&'c mut C) -> BoxFuture<'c, crate::Result<bool>> + Send + Sync + 'static,
+    {
+        self.recycle = Some(Box::new(recycle));
+        self
+    }
+
+    /// Call `handler` with every [`PoolEvent`] of the pool, such as the
+    /// [statement cache metrics] of each connection as it is returned.
+    ///
+    /// `handler` is called right away by the task the event happened on (e.g., the one dropping
+    /// a [`PoolConnection`]), so it should not block.
+    ///
+    /// [statement cache metrics]: crate::connection::Connection::statement_cache_metrics
+    /// [`PoolConnection`]: crate::pool::PoolConnection
+    pub fn event_handler<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&PoolEvent) + Send + Sync + 'static,
+    {
+        self.event_handler = Some(Box::new(handler));
+        self
+    }
+
     /// Spin up the connection pool.
     ///
     /// If [`min_size`] was set to a non-zero value, that many connections will be immediately
@@ -117,7 +233,14 @@ where
     where
         C: Connect,
     {
-        Pool::<C>::with_options(url, self.options).await
+        Pool::<C>::with_options(
+            url,
+            self.options,
+            self.connector,
+            self.recycle,
+            self.event_handler,
+        )
+        .await
     }
 }
 
@@ -139,4 +262,6 @@ pub(crate) struct Options {
     pub max_lifetime: Option<Duration>,
     pub idle_timeout: Option<Duration>,
     pub test_on_acquire: bool,
+    pub log_settings: Option<LogSettings>,
+    pub sql_redaction: Option<SqlRedaction>,
 }
@@ -1,8 +1,7 @@
-use byteorder::{ByteOrder, NetworkEndian};
+use std::error::Error as StdError;
 
 use crate::arguments::Arguments;
-use crate::encode::{Encode, IsNull};
-use crate::io::BufMut;
+use crate::encode::Encode;
 use crate::postgres::{PgRawBuffer, PgTypeInfo, Postgres};
 use crate::types::Type;
 
@@ -13,6 +12,9 @@ pub struct PgArguments {
 
     // Write buffer for serializing bind values
     pub(super) buffer: PgRawBuffer,
+
+    // The first bind value that cannot be encoded; the query fails with it before being sent
+    pub(super) error: Option<Box<dyn StdError + Send + Sync>>,
 }
 
 impl Arguments for PgArguments {
@@ -30,22 +32,11 @@ impl Arguments for PgArguments {
         // TODO: When/if we receive types that do _not_ support BINARY, we need to check here
         // TODO: There is no need to be explicit unless we are expecting mixed BINARY / TEXT
 
-        self.types.push(<T as Type<Postgres>>::type_info());
+        if let Err(error) = value.check() {
+            self.error.get_or_insert(error);
+        }
 
-        // Reserves space for the length of the value
-        let pos = self.buffer.len();
-        self.buffer.put_i32::<NetworkEndian>(0);
-
-        let len = if let IsNull::No = value.encode_nullable(&mut self.buffer) {
-            (self.buffer.len() - pos - 4) as i32
-        } else {
-            // Write a -1 for the len to indicate NULL
-            // TODO: It is illegal for [encode] to write any data
-            //       if IsSql::No; fail a debug assertion
-            -1
-        };
-
-        // Write-back the len to the beginning of this frame (not including the len of len)
-        NetworkEndian::write_i32(&mut self.buffer[pos..], len as i32);
+        self.types.push(<T as Type<Postgres>>::type_info());
+        self.buffer.put_value(&value);
     }
 }
@@ -1,5 +1,7 @@
+use crate::encode::{Encode, IsNull};
+use crate::io::BufMut;
 use crate::postgres::type_info::SharedStr;
-use crate::postgres::PgConnection;
+use crate::postgres::{PgConnection, Postgres};
 use byteorder::{ByteOrder, NetworkEndian};
 use core::ops::{Deref, DerefMut};
 
@@ -16,6 +18,35 @@ pub struct PgRawBuffer {
 }
 
 impl PgRawBuffer {
+    // Writes `value` prefixed with its length, or only a length of -1 if it is NULL, as
+    // both [Bind] and binary COPY expect it
+    pub(crate) fn put_value<T>(&mut self, value: &T)
+    where
+        T: Encode<Postgres> + ?Sized,
+    {
+        // Reserves space for the length of the value
+        let pos = self.len();
+        self.put_i32::<NetworkEndian>(0);
+
+        let len = if let IsNull::No = value.encode_nullable(self) {
+            (self.len() - pos - 4) as i32
+        } else {
+            // Write a -1 for the len to indicate NULL
+            // TODO: It is illegal for [encode] to write any data
+            //       if IsSql::No; fail a debug assertion
+            -1
+        };
+
+        // Write-back the len to the beginning of this frame (not including the len of len)
+        NetworkEndian::write_i32(&mut self[pos..], len);
+    }
+
+    // Clears the buffer, along with any type holes in it
+    pub(crate) fn clear(&mut self) {
+        self.inner.clear();
+        self.type_holes.clear();
+    }
+
     // Extends the inner buffer by enough space to have an OID
     // Remembers where the OID goes and type name for the OID
     pub(crate) fn push_type_hole(&mut self, type_name: &SharedStr) {
@@ -0,0 +1,204 @@
+use std::str::from_utf8;
+use std::sync::Arc;
+
+use futures_core::future::BoxFuture;
+
+use crate::columnar::{ColumnBuilder, Columnar, ColumnarResult, ColumnarResultBuilder};
+use crate::cursor::Either;
+use crate::decode::Decode;
+use crate::postgres::cursor::{self, PgCursor};
+use crate::postgres::protocol::{TypeFormat, TypeId};
+use crate::postgres::row::{Column, Statement};
+use crate::postgres::value::PgValue;
+use crate::postgres::Postgres;
+
+impl Columnar for Postgres {
+    fn fetch_columnar<'c, 'q>(
+        mut cursor: PgCursor<'c, 'q>,
+    ) -> BoxFuture<'c, crate::Result<ColumnarResult>>
+    where
+        'q: 'c,
+    {
+        Box::pin(async move {
+            let mut result = ColumnarResultBuilder::new();
+
+            // The kind of each column is worked out once for each statement of the query,
+            // instead of once for each value
+            let mut statement: Option<Arc<Statement>> = None;
+            let mut kinds = Vec::new();
+
+            while let Some(next) = cursor::next(&mut cursor, false).await? {
+                let row = match next {
+                    Either::Right(row) => row,
+                    Either::Left(_) => continue,
+                };
+
+                if !matches!(&statement, Some(statement) if Arc::ptr_eq(statement, row.statement)) {
+                    kinds = row.statement.columns.iter().map(Kind::of).collect();
+                    statement = Some(Arc::clone(row.statement));
+                }
+
+                let columns = result.push_row(row.data.len(), || {
+                    row.statement
+                        .columns
+                        .iter()
+                        .map(|column| column.name.as_deref().map(ToOwned::to_owned))
+                        .collect()
+                })?;
+
+                for (index, column) in columns.iter_mut().enumerate() {
+                    match row.data.get(index) {
+                        Some(buf) => {
+                            kinds[index].push(buf, &row.statement.columns[index], column)?
+                        }
+                        None => column.push_null(),
+                    }
+                }
+            }
+
+            Ok(result.finish())
+        })
+    }
+}
+
+// How the values of a column are pushed onto its builder
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    Bool,
+    Int2,
+    Int4,
+    Int8,
+    Oid,
+    Float4,
+    Float8,
+    Text,
+    Bytes,
+    Unsupported,
+}
+
+impl Kind {
+    fn of(column: &Column) -> Self {
+        match column.type_info.id {
+            Some(TypeId::BOOL) => Kind::Bool,
+            Some(TypeId::INT2) => Kind::Int2,
+            Some(TypeId::INT4) => Kind::Int4,
+            Some(TypeId::INT8) => Kind::Int8,
+            Some(TypeId::OID) => Kind::Oid,
+            Some(TypeId::FLOAT4) => Kind::Float4,
+            Some(TypeId::FLOAT8) => Kind::Float8,
+
+            Some(TypeId::TEXT)
+            | Some(TypeId::VARCHAR)
+            | Some(TypeId::BPCHAR)
+            | Some(TypeId::NAME)
+            | Some(TypeId::UNKNOWN) => Kind::Text,
+
+            Some(TypeId::BYTEA) => Kind::Bytes,
+
+            _ => Kind::Unsupported,
+        }
+    }
+
+    fn push(self, buf: &[u8], info: &Column, column: &mut ColumnBuilder) -> crate::Result<()> {
+        let value = match info.format {
+            TypeFormat::Binary => PgValue::from_bytes(buf),
+            TypeFormat::Text => PgValue::from_str(from_utf8(buf).map_err(crate::Error::decode)?),
+        };
+
+        match self {
+            Kind::Bool => column.push_bool(Decode::<Postgres>::decode(value)?),
+            Kind::Int2 => column.push_int(<i16 as Decode<Postgres>>::decode(value)?.into()),
+            Kind::Int4 => column.push_int(<i32 as Decode<Postgres>>::decode(value)?.into()),
+            Kind::Int8 => column.push_int(Decode::<Postgres>::decode(value)?),
+            Kind::Oid => column.push_int(<u32 as Decode<Postgres>>::decode(value)?.into()),
+            Kind::Float4 => column.push_float(<f32 as Decode<Postgres>>::decode(value)?.into()),
+            Kind::Float8 => column.push_float(Decode::<Postgres>::decode(value)?),
+            Kind::Text => column.push_text(Decode::<Postgres>::decode(value)?),
+            Kind::Bytes => match info.format {
+                TypeFormat::Binary => column.push_bytes(buf),
+                TypeFormat::Text => {
+                    column.push_bytes(&<Vec<u8> as Decode<Postgres>>::decode(value)?)
+                }
+            },
+
+            Kind::Unsupported => Err(column.unsupported(&info.type_info)),
+        }
+    }
+}
+
+#[cfg(all(feature = "bench", feature = "runtime-async-std"))]
+mod bench {
+    use crate::columnar::ColumnValues;
+    use crate::connection::Connect;
+    use crate::cursor::Cursor;
+    use crate::executor::Executor;
+    use crate::postgres::PgConnection;
+    use crate::query::query;
+    use crate::row::Row;
+
+    // A connection to the `DATABASE_URL` with a temporary table of 1M rows and 5 columns
+    // (two integers, a float, a nullable integer and text)
+    async fn setup() -> PgConnection {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let mut conn = PgConnection::connect(&*url).await.unwrap();
+
+        conn.execute(
+            r#"
+CREATE TEMPORARY TABLE bench (a INT8, b INT8, c FLOAT8, d INT8, e TEXT);
+
+INSERT INTO bench
+SELECT i, i * 2, i / 3.0::FLOAT8, CASE WHEN i % 10 = 0 THEN NULL ELSE i END, 'row ' || i
+FROM generate_series(1, 1000000) AS i;
+            "#,
+        )
+        .await
+        .unwrap();
+
+        conn
+    }
+
+    #[bench]
+    fn bench_fetch_row_by_row_and_transpose(b: &mut test::Bencher) {
+        let mut conn = async_std::task::block_on(setup());
+
+        b.iter(|| {
+            async_std::task::block_on(async {
+                let mut a = Vec::new();
+                let mut b = Vec::new();
+                let mut c = Vec::new();
+                let mut d = Vec::new();
+                let mut e = Vec::new();
+
+                let mut cursor = query("SELECT a, b, c, d, e FROM bench").fetch(&mut conn);
+
+                while let Some(row) = cursor.next().await.unwrap() {
+                    a.push(row.get::<i64, _>(0));
+                    b.push(row.get::<i64, _>(1));
+                    c.push(row.get::<f64, _>(2));
+                    d.push(row.get::<Option<i64>, _>(3));
+                    e.push(row.get::<Option<String>, _>(4));
+                }
+
+                (a, b, c, d, e)
+            })
+        });
+    }
+
+    #[bench]
+    fn bench_fetch_columnar(b: &mut test::Bencher) {
+        let mut conn = async_std::task::block_on(setup());
+
+        b.iter(|| {
+            async_std::task::block_on(async {
+                let result = query("SELECT a, b, c, d, e FROM bench")
+                    .fetch_columnar(&mut conn)
+                    .await
+                    .unwrap();
+
+                assert!(matches!(result.columns()[0].values(), ColumnValues::Int(_)));
+
+                result
+            })
+        });
+    }
+}
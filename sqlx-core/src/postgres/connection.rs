@@ -6,17 +6,24 @@ use std::sync::Arc;
 use futures_core::future::BoxFuture;
 use futures_util::TryFutureExt;
 
-use crate::connection::{Connect, Connection};
+use crate::connection::{Connect, Connection, StatementCacheMetrics};
 use crate::executor::Executor;
+use crate::io::MaybeTlsStream;
+use crate::logging::LogSettings;
+use crate::statement::SqlRedaction;
 
 use crate::postgres::protocol::{
-    Authentication, AuthenticationMd5, AuthenticationSasl, BackendKeyData, Message,
+    Authentication, AuthenticationMd5, AuthenticationSasl, BackendKeyData, CancelRequest, Message,
     PasswordMessage, StartupMessage, StatementId, Terminate,
 };
 use crate::postgres::row::Statement;
 use crate::postgres::stream::PgStream;
 use crate::postgres::type_info::SharedStr;
+use crate::postgres::Postgres;
 use crate::postgres::{sasl, tls};
+use crate::retry::{RetryOptions, RetryPolicy};
+use crate::runtime::{AsyncRead, AsyncWrite};
+use crate::transaction::Transaction;
 use crate::url::Url;
 
 /// An asynchronous connection to a [Postgres](struct.Postgres.html) database.
@@ -79,17 +86,51 @@ use crate::url::Url;
 /// `sslmode=verify-ca`, the hostname in the connection string will be verified
 /// against the hostname in the server certificate, so they must be the same for the TLS
 /// upgrade to succeed.
+///
+/// ### Retrying
+/// By default, a connection attempt that fails is not retried. Add `connect_retries=<n>` to the
+/// connection string to retry up to `n` times when connecting fails with a transient error: the
+/// connection being refused, reset or timing out, a temporary failure to resolve the host (the
+/// name server not answering), or the server still starting up (`57P03`). Authentication and TLS
+/// errors are never retried, and neither is a host that does not exist.
+///
+/// Before each retry we wait for `connect_retry_backoff` milliseconds (`100` by default), doubled
+/// after each attempt and with some random jitter. If every attempt fails,
+/// [`Error::ConnectRetriesExhausted`] reports how many attempts were made and the last error.
+///
+/// ```text
+/// postgresql://localhost/my_database?connect_retries=5&connect_retry_backoff=250
+/// ```
+///
+/// Both can also be set with [`PgConnectOptions`], which takes precedence over the URL.
+///
+/// [`Error::ConnectRetriesExhausted`]: crate::Error::ConnectRetriesExhausted
+/// [`PgConnectOptions`]: crate::postgres::PgConnectOptions
 pub struct PgConnection {
     pub(super) stream: PgStream,
     pub(super) next_statement_id: u32,
     pub(super) is_ready: bool,
 
+    // queries written by [queue_rollback] that are still to send a [ReadyForQuery] once the
+    // one [is_ready] is waiting on has been received
+    pub(super) queued_queries: u32,
+
+    // a [CopyFail] was written for a copy that was dropped; the error postgres fails the copy
+    // with is expected (and ignored) by [wait_until_ready]
+    pub(super) aborting_copy: bool,
+
     // cache query -> statement ID
     pub(super) cache_statement_id: HashMap<Box<str>, StatementId>,
 
     // cache statement ID -> statement description
     pub(super) cache_statement: HashMap<StatementId, Arc<Statement>>,
 
+    // hits and misses of [cache_statement_id]
+    pub(super) cache_metrics: StatementCacheMetrics,
+
+    pub(super) log_settings: LogSettings,
+    pub(super) sql_redaction: SqlRedaction,
+
     // cache type name -> type OID
     pub(super) cache_type_oid: HashMap<SharedStr, u32>,
 
@@ -100,12 +141,11 @@ pub struct PgConnection {
     // This is used as the backing memory for each Row's value indexes
     pub(super) current_row_values: Vec<Option<(u32, u32)>>,
 
-    // TODO: Find a use for these values. Perhaps in a debug impl of PgConnection?
-    #[allow(dead_code)]
+    // The key that a query running on this connection is cancelled with, and where the cancel
+    // request is sent to; see [cancel_request]
     process_id: u32,
-
-    #[allow(dead_code)]
     secret_key: u32,
+    url: Arc<Url>,
 }
 
 // https://www.postgresql.org/docs/12/protocol-flow.html#id-1.10.5.7.3
@@ -246,6 +286,7 @@ async fn startup(stream: &mut PgStream, url: &Url) -> crate::Result<BackendKeyDa
 
 // https://www.postgresql.org/docs/12/protocol-flow.html#id-1.10.5.7.10
 async fn terminate(mut stream: PgStream) -> crate::Result<()> {
+    stream.terminate_on_drop = false;
     stream.write(Terminate);
     stream.flush().await?;
     stream.shutdown()?;
@@ -254,24 +295,93 @@ async fn terminate(mut stream: PgStream) -> crate::Result<()> {
 }
 
 impl PgConnection {
-    pub(super) async fn new(url: std::result::Result<Url, url::ParseError>) -> crate::Result<Self> {
-        let url = url?;
-        let mut stream = PgStream::new(&url).await?;
+    /// Establish a connection over a stream that was already opened by the caller.
+    ///
+    /// DNS resolution and the TCP (or Unix socket) dial are skipped entirely and the startup
+    /// handshake is performed directly over `stream`. This can be used to connect through
+    /// a SOCKS proxy or an SSH tunnel, or to talk to an in-process fake server in tests.
+    ///
+    /// Everything else is still read from `url`. In particular, `sslmode` is honored and
+    /// the TLS session is negotiated on top of `stream`, verifying the host given in `url`.
+    ///
+    /// ```rust,ignore
+    /// let stream = async_std::net::TcpStream::connect("127.0.0.1:6543").await?;
+    ///
+    /// let conn = PgConnection::connect_with_stream(stream, "postgres://db.internal/app").await?;
+    /// ```
+    pub async fn connect_with_stream<S, T>(stream: S, url: T) -> crate::Result<Self>
+    where
+        S: AsyncRead + AsyncWrite + Send + Unpin + 'static,
+        T: TryInto<Url, Error = url::ParseError>,
+    {
+        let url = url.try_into()?;
+        let stream = PgStream::from_stream(MaybeTlsStream::from_raw(stream));
+
+        Self::establish(stream, &url).await
+    }
+
+    pub(super) async fn new(
+        url: std::result::Result<Url, url::ParseError>,
+        retry: RetryOptions,
+    ) -> crate::Result<Self> {
+        let url = &url?;
 
-        tls::request_if_needed(&mut stream, &url).await?;
-        let key_data = startup(&mut stream, &url).await?;
+        RetryPolicy::new(url, retry)?
+            .run(move || async move {
+                let stream = PgStream::new(url).await?;
+
+                Self::establish(stream, url).await
+            })
+            .await
+    }
+
+    async fn establish(mut stream: PgStream, url: &Url) -> crate::Result<Self> {
+        tls::request_if_needed(&mut stream, url).await?;
+        let key_data = startup(&mut stream, url).await?;
+
+        stream.terminate_on_drop = true;
 
         Ok(Self {
             stream,
             current_row_values: Vec::with_capacity(10),
             next_statement_id: 1,
             is_ready: true,
+            queued_queries: 0,
+            aborting_copy: false,
             cache_type_oid: HashMap::new(),
             cache_type_name: HashMap::new(),
             cache_statement_id: HashMap::with_capacity(10),
             cache_statement: HashMap::with_capacity(10),
+            cache_metrics: StatementCacheMetrics::default(),
+            log_settings: LogSettings::default(),
+            sql_redaction: SqlRedaction::default(),
             process_id: key_data.process_id,
             secret_key: key_data.secret_key,
+            url: Arc::new(url.clone()),
+        })
+    }
+
+    // Asks postgres to cancel the query that is running on this connection, from a connection of
+    // its own; see `CancelHandle`
+    pub(super) fn cancel_request(&self) -> BoxFuture<'static, crate::Result<()>> {
+        let request = CancelRequest {
+            process_id: self.process_id,
+            secret_key: self.secret_key,
+        };
+
+        let url = Arc::clone(&self.url);
+
+        Box::pin(async move {
+            let mut stream = PgStream::new(&url).await?;
+
+            stream.write(request);
+            stream.flush().await?;
+
+            // postgres closes the connection once it has passed the request on, which is
+            // waited on so that the request cannot reach a later query instead
+            let _ = stream.stream.try_peek(1).await;
+
+            Ok(())
         })
     }
 }
@@ -282,11 +392,15 @@ impl Connect for PgConnection {
         T: TryInto<Url, Error = url::ParseError>,
         Self: Sized,
     {
-        Box::pin(PgConnection::new(url.try_into()))
+        Box::pin(PgConnection::new(url.try_into(), RetryOptions::default()))
     }
 }
 
 impl Connection for PgConnection {
+    fn begin(&mut self) -> BoxFuture<'_, crate::Result<Transaction<'_, Postgres>>> {
+        Box::pin(Transaction::new(0, self))
+    }
+
     fn close(self) -> BoxFuture<'static, crate::Result<()>> {
         Box::pin(terminate(self.stream))
     }
@@ -294,4 +408,40 @@ impl Connection for PgConnection {
     fn ping(&mut self) -> BoxFuture<crate::Result<()>> {
         Box::pin(Executor::execute(self, "SELECT 1").map_ok(|_| ()))
     }
+
+    fn statement_cache_metrics(&self) -> StatementCacheMetrics {
+        StatementCacheMetrics {
+            round_trips: self.stream.round_trips,
+            ..self.cache_metrics
+        }
+    }
+
+    fn reset_statement_cache_metrics(&mut self) {
+        self.cache_metrics = StatementCacheMetrics::default();
+        self.stream.round_trips = 0;
+    }
+
+    fn set_log_settings(&mut self, settings: LogSettings) {
+        self.log_settings = settings;
+    }
+
+    fn set_sql_redaction(&mut self, redaction: SqlRedaction) {
+        self.sql_redaction = redaction;
+    }
+
+    fn queue_rollback(&mut self, statement: &str) -> bool {
+        if !self.is_ready {
+            self.queued_queries += 1;
+        }
+
+        // flushed (and waited on) by [wait_until_ready] before the next query
+        self.write_simple_query(statement);
+        self.is_ready = false;
+
+        true
+    }
+
+    fn flush_queued_now(&mut self) -> bool {
+        self.stream.stream.flush_now()
+    }
 }
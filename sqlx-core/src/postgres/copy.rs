@@ -0,0 +1,488 @@
+//! Bulk transfer of rows with `COPY` in its binary format.
+//!
+//! <https://www.postgresql.org/docs/12/sql-copy.html#id-1.9.3.55.9.4>
+
+use std::io;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use byteorder::{ByteOrder, NetworkEndian};
+
+use crate::encode::Encode;
+use crate::error::DatabaseError;
+use crate::io::{Buf, BufMut};
+use crate::postgres::protocol::{
+    CommandComplete, CopyData, CopyDone, CopyFail, CopyResponse, DataRow, Message, TypeFormat,
+};
+use crate::postgres::row::Statement;
+use crate::postgres::{PgConnection, PgRawBuffer, PgRow, Postgres};
+use crate::row::FromRow;
+
+// PGCOPY\n\377\r\n\0, then 32 bits of flags and the length of the header extension (both 0)
+const HEADER: &[u8] = b"PGCOPY\n\xff\r\n\0\0\0\0\0\0\0\0\0";
+
+// Each tuple starts with its number of fields; a tuple of -1 fields marks the end
+const TRAILER: i16 = -1;
+
+// Rows are sent once this much has been encoded
+const CHUNK_SIZE: usize = 64 * 1024;
+
+// The reasons a copy is failed with, which postgres repeats in the error it fails it with
+const UNSUPPORTED_FORMAT: &str = "only the binary format of COPY is supported";
+const ABORTED: &str = "aborted by the client";
+pub(super) const DROPPED: &str = "the copy was dropped before it was finished";
+
+impl PgConnection {
+    /// Start copying rows into a table with `statement`, a `COPY ... FROM STDIN` that must use
+    /// the binary format.
+    ///
+    /// Rows are then appended with [`PgCopyInWriter::append_row`], which encodes each value
+    /// just like it would be bound to a query, and the copy is completed with
+    /// [`PgCopyInWriter::finish`].
+    ///
+    /// ```rust,ignore
+    /// let mut copy = conn
+    ///     .copy_in("COPY users (id, name) FROM STDIN WITH (FORMAT binary)")
+    ///     .await?;
+    ///
+    /// for (id, name) in users {
+    ///     copy.append_row((id, name)).await?;
+    /// }
+    ///
+    /// let rows = copy.finish().await?;
+    /// ```
+    ///
+    /// The rows are streamed to the server without a round trip for each of them, and without
+    /// the limit of 65535 bind parameters that an `INSERT` of many `VALUES` runs into.
+    ///
+    /// The values are not checked against the columns of the table they are being copied into
+    /// until they reach the server. If a value does not fit its column, the copy fails with the
+    /// error of the server once it is found, and at the latest by [`finish`]. That error tells
+    /// which row could not be copied with [`PgError::copy_row`].
+    ///
+    /// A `statement` that copies in any other format fails with an [`Error::Io`] of
+    /// [`io::ErrorKind::InvalidInput`], after the copy has been failed on the server.
+    ///
+    /// [`finish`]: PgCopyInWriter::finish
+    /// [`PgError::copy_row`]: crate::postgres::PgError::copy_row
+    /// [`Error::Io`]: crate::Error::Io
+    pub async fn copy_in(&mut self, statement: &str) -> crate::Result<PgCopyInWriter<'_>> {
+        self.run(statement, None).await?;
+
+        match self.stream.receive().await? {
+            Message::CopyInResponse => {
+                let response = CopyResponse::read(self.stream.buffer())?;
+
+                if let TypeFormat::Text = response.format {
+                    self.fail_copy(UNSUPPORTED_FORMAT).await?;
+
+                    return Err(crate::Error::Io(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "copy_in: only `COPY ... FROM STDIN WITH (FORMAT binary)` is supported",
+                    )));
+                }
+            }
+
+            message => {
+                return Err(protocol_err!("copy_in: unexpected message: {:?}", message).into());
+            }
+        }
+
+        let mut buffer = PgRawBuffer::default();
+        buffer.extend_from_slice(HEADER);
+
+        Ok(PgCopyInWriter {
+            connection: self,
+            buffer,
+            done: false,
+        })
+    }
+
+    /// Copy the rows of `query` out with `COPY (query) TO STDOUT` in the binary format,
+    /// decoding each of them as a `T`.
+    ///
+    /// `query` is any `SELECT`, `VALUES` or `TABLE` command, without bind parameters. It is
+    /// prepared first so that each row can be decoded exactly like a row returned by `query`
+    /// itself; `T` may be any [`FromRow`], such as a tuple.
+    ///
+    /// ```rust,ignore
+    /// let mut copy = conn.copy_out::<(i64, String)>("TABLE users").await?;
+    ///
+    /// while let Some((id, name)) = copy.next().await? {
+    ///     // ...
+    /// }
+    /// ```
+    pub async fn copy_out<T>(&mut self, query: &str) -> crate::Result<PgCopyOutReader<'_, T>>
+    where
+        T: for<'r> FromRow<'r, PgRow<'r>>,
+    {
+        let statement = self.write_prepare(query, &Default::default()).await?;
+        let statement = Arc::clone(&self.cache_statement[&statement]);
+
+        self.run(
+            &format!("COPY ({}) TO STDOUT WITH (FORMAT binary)", query),
+            None,
+        )
+        .await?;
+
+        match self.stream.receive().await? {
+            Message::CopyOutResponse => {}
+
+            message => {
+                return Err(protocol_err!("copy_out: unexpected message: {:?}", message).into());
+            }
+        }
+
+        Ok(PgCopyOutReader {
+            connection: self,
+            statement,
+            header: false,
+            row: PhantomData,
+        })
+    }
+
+    // Fail the copy in progress with `reason` and wait for the connection to be ready again
+    async fn fail_copy(&mut self, reason: &str) -> crate::Result<()> {
+        self.stream.write(CopyFail(reason));
+        self.stream.flush().await?;
+
+        loop {
+            match self.stream.receive().await {
+                // the server fails the copy with the reason we gave it
+                Err(crate::Error::Database(error)) if is_copy_fail(&*error, reason) => {}
+
+                Ok(Message::ReadyForQuery) => {
+                    self.is_ready = true;
+                    break;
+                }
+
+                Ok(_) => {}
+
+                Err(error) => return Err(error),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A `COPY ... FROM STDIN` in progress; see [`PgConnection::copy_in`].
+///
+/// If this is dropped before [`finish`](PgCopyInWriter::finish) is called, the copy fails and
+/// none of the rows are copied.
+pub struct PgCopyInWriter<'c> {
+    connection: &'c mut PgConnection,
+
+    // rows that have not been sent yet
+    buffer: PgRawBuffer,
+
+    // if the copy is done, successfully or not
+    done: bool,
+}
+
+impl PgCopyInWriter<'_> {
+    /// Append a row with the values of a tuple, in the order of the columns being copied into.
+    ///
+    /// `NULL` is copied in for a value of `None`.
+    pub async fn append_row<R>(&mut self, row: R) -> crate::Result<()>
+    where
+        R: PgCopyRow,
+    {
+        row.encode_row(&mut self.buffer);
+
+        if self.buffer.len() >= CHUNK_SIZE {
+            self.send().await?;
+        }
+
+        Ok(())
+    }
+
+    /// Complete the copy, returning the number of rows that were copied.
+    ///
+    /// This returns the error of the server if any of the rows could not be copied, in which
+    /// case none of them are.
+    pub async fn finish(mut self) -> crate::Result<u64> {
+        self.buffer.put_i16::<NetworkEndian>(TRAILER);
+        self.send().await?;
+
+        self.done = true;
+        self.connection.stream.write(CopyDone);
+        self.connection.stream.flush().await?;
+
+        let mut rows = 0;
+
+        loop {
+            match self.connection.stream.receive().await? {
+                Message::CommandComplete => {
+                    rows = CommandComplete::read(self.connection.stream.buffer())?.affected_rows;
+                }
+
+                Message::ReadyForQuery => {
+                    self.connection.is_ready = true;
+                    break;
+                }
+
+                message => {
+                    return Err(protocol_err!("copy_in: unexpected message: {:?}", message).into());
+                }
+            }
+        }
+
+        Ok(rows)
+    }
+
+    /// Abort the copy; none of the rows are copied.
+    pub async fn abort(mut self) -> crate::Result<()> {
+        self.done = true;
+        self.connection.fail_copy(ABORTED).await
+    }
+
+    async fn send(&mut self) -> crate::Result<()> {
+        self.buffer.patch_type_holes(self.connection).await?;
+
+        self.connection.stream.write(CopyData(&self.buffer));
+        self.connection.stream.flush().await?;
+
+        self.buffer.clear();
+
+        Ok(())
+    }
+}
+
+impl Drop for PgCopyInWriter<'_> {
+    fn drop(&mut self) {
+        if !self.done {
+            // this is sent ahead of the next query; until then, the connection is not ready
+            self.connection.stream.write(CopyFail(DROPPED));
+
+            self.connection.aborting_copy = true;
+        }
+    }
+}
+
+// Whether `error` is the one postgres fails a copy with once it receives our [CopyFail]
+// with `reason` (`query_canceled`, "COPY from stdin failed: <reason>"); the start of the
+// message may be translated but `reason` is repeated as is, which tells it apart from
+// any other cancellation (such as by `statement_timeout`)
+pub(super) fn is_copy_fail(error: &dyn DatabaseError, reason: &str) -> bool {
+    error.code() == Some("57014") && error.message().ends_with(reason)
+}
+
+/// A row that can be copied in with [`PgCopyInWriter::append_row`].
+///
+/// This is implemented for tuples of values that can be bound to a query.
+pub trait PgCopyRow {
+    #[doc(hidden)]
+    fn encode_row(self, buf: &mut PgRawBuffer);
+}
+
+macro_rules! impl_copy_row_for_tuple {
+    ($( $idx:tt : $T:ident ),+) => {
+        impl<$($T,)+> PgCopyRow for ($($T,)+)
+        where
+            $($T: Encode<Postgres>,)+
+        {
+            fn encode_row(self, buf: &mut PgRawBuffer) {
+                let fields = [$($idx,)+].len();
+
+                buf.put_i16::<NetworkEndian>(fields as i16);
+                $(buf.put_value(&self.$idx);)+
+            }
+        }
+    };
+}
+
+impl_copy_row_for_tuple!(0: T1);
+impl_copy_row_for_tuple!(0: T1, 1: T2);
+impl_copy_row_for_tuple!(0: T1, 1: T2, 2: T3);
+impl_copy_row_for_tuple!(0: T1, 1: T2, 2: T3, 3: T4);
+impl_copy_row_for_tuple!(0: T1, 1: T2, 2: T3, 3: T4, 4: T5);
+impl_copy_row_for_tuple!(0: T1, 1: T2, 2: T3, 3: T4, 4: T5, 5: T6);
+impl_copy_row_for_tuple!(0: T1, 1: T2, 2: T3, 3: T4, 4: T5, 5: T6, 6: T7);
+impl_copy_row_for_tuple!(0: T1, 1: T2, 2: T3, 3: T4, 4: T5, 5: T6, 6: T7, 7: T8);
+impl_copy_row_for_tuple!(0: T1, 1: T2, 2: T3, 3: T4, 4: T5, 5: T6, 6: T7, 7: T8, 8: T9);
+
+/// A `COPY ... TO STDOUT` in progress; see [`PgConnection::copy_out`].
+///
+/// Any rows that are left when this is dropped are discarded.
+pub struct PgCopyOutReader<'c, T> {
+    connection: &'c mut PgConnection,
+
+    // the columns of the query being copied out
+    statement: Arc<Statement>,
+
+    // if the header has been read
+    header: bool,
+
+    row: PhantomData<fn() -> T>,
+}
+
+impl<T> PgCopyOutReader<'_, T>
+where
+    T: for<'r> FromRow<'r, PgRow<'r>>,
+{
+    /// Returns the next row or `None` once all of them have been read.
+    pub async fn next(&mut self) -> crate::Result<Option<T>> {
+        let connection = &mut *self.connection;
+
+        loop {
+            match connection.stream.receive().await? {
+                // each row is sent in a message of its own; the header and the trailer may
+                // be too
+                Message::CopyData => {
+                    let mut buf = connection.stream.buffer();
+
+                    if !self.header {
+                        buf = read_header(buf)?;
+                        self.header = true;
+                    }
+
+                    if buf.is_empty() || NetworkEndian::read_i16(buf) == TRAILER {
+                        continue;
+                    }
+
+                    // a row is encoded exactly like the body of a `DataRow`
+                    let data = DataRow::read(buf, &mut connection.current_row_values)?;
+
+                    let row = PgRow {
+                        data,
+                        statement: Arc::clone(&self.statement),
+                    };
+
+                    return T::from_row(&row).map(Some);
+                }
+
+                Message::CopyDone | Message::CommandComplete => {}
+
+                Message::ReadyForQuery => {
+                    connection.is_ready = true;
+                    return Ok(None);
+                }
+
+                message => {
+                    return Err(protocol_err!("copy_out: unexpected message: {:?}", message).into());
+                }
+            }
+        }
+    }
+}
+
+// Skips past the header, returning whatever follows it
+fn read_header(mut buf: &[u8]) -> crate::Result<&[u8]> {
+    if !buf.starts_with(&HEADER[..11]) {
+        return Err(protocol_err!("copy_out: invalid header: {:?}", buf).into());
+    }
+
+    buf.advance(11);
+
+    let _flags = buf.get_u32::<NetworkEndian>()?;
+    let extension = buf.get_u32::<NetworkEndian>()?;
+
+    buf.advance(extension as usize);
+
+    Ok(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_encodes_rows() {
+        let mut buf = PgRawBuffer::default();
+
+        (1_i32, None::<i64>, "hi").encode_row(&mut buf);
+
+        assert_eq!(
+            &**buf,
+            b"\0\x03\0\0\0\x04\0\0\0\x01\xff\xff\xff\xff\0\0\0\x02hi"
+        );
+    }
+
+    #[test]
+    fn it_reads_the_header() {
+        let buf = [HEADER, b"\0\x01"].concat();
+
+        assert_eq!(read_header(&buf).unwrap(), b"\0\x01");
+        assert!(read_header(b"PGCOPY\n").is_err());
+    }
+}
+
+#[cfg(all(feature = "bench", feature = "runtime-async-std"))]
+mod bench {
+    use crate::connection::Connect;
+    use crate::executor::Executor;
+    use crate::postgres::PgConnection;
+    use crate::query::query;
+
+    const ROWS: i64 = 1_000_000;
+
+    // A connection to the `DATABASE_URL` with an empty, temporary table of 3 columns
+    async fn setup() -> PgConnection {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let mut conn = PgConnection::connect(&*url).await.unwrap();
+
+        conn.execute("CREATE TEMPORARY TABLE bench (a INT8, b TEXT, c FLOAT8)")
+            .await
+            .unwrap();
+
+        conn
+    }
+
+    #[bench]
+    fn bench_copy_in(b: &mut test::Bencher) {
+        let mut conn = async_std::task::block_on(setup());
+
+        b.iter(|| {
+            async_std::task::block_on(async {
+                let mut copy = conn
+                    .copy_in("COPY bench FROM STDIN WITH (FORMAT binary)")
+                    .await
+                    .unwrap();
+
+                for i in 0..ROWS {
+                    copy.append_row((i, format!("row {}", i), i as f64 / 3.0))
+                        .await
+                        .unwrap();
+                }
+
+                assert_eq!(copy.finish().await.unwrap(), ROWS as u64);
+            })
+        });
+    }
+
+    #[bench]
+    fn bench_insert_values(b: &mut test::Bencher) {
+        // 1000 rows of 3 values; Postgres allows for at most 65535 bind parameters
+        const ROWS_PER_INSERT: i64 = 1000;
+
+        let mut conn = async_std::task::block_on(setup());
+
+        let mut sql = String::from("INSERT INTO bench (a, b, c) VALUES ");
+
+        for i in 0..ROWS_PER_INSERT {
+            if i > 0 {
+                sql.push_str(", ");
+            }
+
+            sql.push_str(&format!("(${}, ${}, ${})", i * 3 + 1, i * 3 + 2, i * 3 + 3));
+        }
+
+        b.iter(|| {
+            async_std::task::block_on(async {
+                for chunk in 0..ROWS / ROWS_PER_INSERT {
+                    let mut insert = query(&sql);
+
+                    for i in chunk * ROWS_PER_INSERT..(chunk + 1) * ROWS_PER_INSERT {
+                        insert = insert
+                            .bind(i)
+                            .bind(format!("row {}", i))
+                            .bind(i as f64 / 3.0);
+                    }
+
+                    insert.execute(&mut conn).await.unwrap();
+                }
+            })
+        });
+    }
+}
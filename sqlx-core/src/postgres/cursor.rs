@@ -1,23 +1,36 @@
 use std::sync::Arc;
 
 use futures_core::future::BoxFuture;
+use futures_util::TryFutureExt;
 
+use crate::cancel::{next_cancellable, CancelHandle};
 use crate::connection::ConnectionSource;
-use crate::cursor::Cursor;
+use crate::cursor::{Cursor, Either};
 use crate::executor::Execute;
+use crate::logging::{Logged, QueryLog};
 use crate::pool::Pool;
-use crate::postgres::protocol::{DataRow, Message, ReadyForQuery, RowDescription};
+use crate::postgres::protocol::{CommandComplete, DataRow, Message, ReadyForQuery, RowDescription};
 use crate::postgres::row::Statement;
-use crate::postgres::{PgArguments, PgConnection, PgRow, Postgres};
+use crate::postgres::{PgArguments, PgConnection, PgQueryResult, PgRow, Postgres};
 
 pub struct PgCursor<'c, 'q> {
     source: ConnectionSource<'c, PgConnection>,
     query: Option<(&'q str, Option<PgArguments>)>,
+    sql: &'q str,
     statement: Arc<Statement>,
+    cancel_handle: Option<CancelHandle>,
+    log: QueryLog<'q>,
 }
 
 impl crate::cursor::private::Sealed for PgCursor<'_, '_> {}
 
+impl<'q> Logged<'q> for PgCursor<'_, 'q> {
+    fn logged(mut self, log: QueryLog<'q>) -> Self {
+        self.log = log;
+        self
+    }
+}
+
 impl<'c, 'q> Cursor<'c, 'q> for PgCursor<'c, 'q> {
     type Database = Postgres;
 
@@ -27,10 +40,16 @@ impl<'c, 'q> Cursor<'c, 'q> for PgCursor<'c, 'q> {
         Self: Sized,
         E: Execute<'q, Postgres>,
     {
+        let cancel_handle = query.cancel_handle().cloned();
+        let (sql, arguments) = query.into_parts();
+
         Self {
             source: ConnectionSource::Pool(pool.clone()),
             statement: Arc::default(),
-            query: Some(query.into_parts()),
+            query: Some((sql, arguments)),
+            sql,
+            cancel_handle,
+            log: QueryLog::default(),
         }
     }
 
@@ -40,21 +59,73 @@ impl<'c, 'q> Cursor<'c, 'q> for PgCursor<'c, 'q> {
         Self: Sized,
         E: Execute<'q, Postgres>,
     {
+        let cancel_handle = query.cancel_handle().cloned();
+        let (sql, arguments) = query.into_parts();
+
         Self {
             source: ConnectionSource::ConnectionRef(conn),
             statement: Arc::default(),
-            query: Some(query.into_parts()),
+            query: Some((sql, arguments)),
+            sql,
+            cancel_handle,
+            log: QueryLog::default(),
         }
     }
 
     fn next(&mut self) -> BoxFuture<crate::Result<Option<PgRow<'_>>>> {
-        Box::pin(next(self))
+        Box::pin(
+            next(self, false).map_ok(|result| result.and_then(Either::right).map(RawRow::into_row)),
+        )
     }
+
+    fn next_many(
+        &mut self,
+    ) -> BoxFuture<'_, crate::Result<Option<Either<PgQueryResult, PgRow<'_>>>>> {
+        Box::pin(next(self, true).map_ok(|result| {
+            result.map(|next| match next {
+                Either::Left(result) => Either::Left(result),
+                Either::Right(row) => Either::Right(row.into_row()),
+            })
+        }))
+    }
+}
+
+// A row as it was read from the connection, along with the statement it is from
+pub(super) struct RawRow<'a> {
+    pub(super) data: DataRow<'a>,
+    pub(super) statement: &'a Arc<Statement>,
 }
 
-async fn next<'a, 'c: 'a, 'q: 'a>(
+impl<'a> RawRow<'a> {
+    fn into_row(self) -> PgRow<'a> {
+        PgRow {
+            statement: Arc::clone(self.statement),
+            data: self.data,
+        }
+    }
+}
+
+pub(super) async fn next<'a, 'c: 'a, 'q: 'a>(
+    cursor: &'a mut PgCursor<'c, 'q>,
+    many: bool,
+) -> crate::Result<Option<Either<PgQueryResult, RawRow<'a>>>> {
+    let handle = match &cursor.cancel_handle {
+        Some(handle) => handle.clone(),
+        None => return fetch_next(cursor, many).await,
+    };
+
+    let request = cursor.source.resolve().await?.cancel_request();
+
+    next_cancellable(&handle, fetch_next(cursor, many), request).await
+}
+
+// With [many], the result of each statement is returned as well
+async fn fetch_next<'a, 'c: 'a, 'q: 'a>(
     cursor: &'a mut PgCursor<'c, 'q>,
-) -> crate::Result<Option<PgRow<'a>>> {
+    many: bool,
+) -> crate::Result<Option<Either<PgQueryResult, RawRow<'a>>>> {
+    cursor.log.start();
+
     let mut conn = cursor.source.resolve().await?;
 
     // The first time [next] is called we need to actually execute our
@@ -80,7 +151,22 @@ async fn next<'a, 'c: 'a, 'q: 'a>(
             Message::ParseComplete | Message::BindComplete => {}
 
             // Indicates that _a_ query has finished executing
-            Message::CommandComplete => {}
+            Message::CommandComplete => {
+                if many {
+                    let cc = CommandComplete::read(conn.stream.buffer())?;
+
+                    // Unlike [execute], which counts whatever the command tag does, the rows
+                    // that a SELECT returned are not counted as affected here, as for MySQL
+                    // and SQLite
+                    let rows_affected = if cc.returned_rows {
+                        0
+                    } else {
+                        cc.affected_rows
+                    };
+
+                    return Ok(Some(Either::Left(PgQueryResult { rows_affected })));
+                }
+            }
 
             // Indicates that all queries have finished executing
             Message::ReadyForQuery => {
@@ -95,7 +181,7 @@ async fn next<'a, 'c: 'a, 'q: 'a>(
                 // NOTE: This is only encountered for unprepared statements
                 let rd = RowDescription::read(conn.stream.buffer())?;
                 cursor.statement = Arc::new(
-                    conn.parse_row_description(rd, Default::default(), None, false)
+                    conn.parse_row_description(cursor.sql, rd, Default::default(), None, false)
                         .await?,
                 );
             }
@@ -103,10 +189,10 @@ async fn next<'a, 'c: 'a, 'q: 'a>(
             Message::DataRow => {
                 let data = DataRow::read(conn.stream.buffer(), &mut conn.current_row_values)?;
 
-                return Ok(Some(PgRow {
-                    statement: Arc::clone(&cursor.statement),
+                return Ok(Some(Either::Right(RawRow {
+                    statement: &cursor.statement,
                     data,
-                }));
+                })));
             }
 
             message => {
@@ -115,5 +201,7 @@ async fn next<'a, 'c: 'a, 'q: 'a>(
         }
     }
 
+    cursor.log.finish();
+
     Ok(None)
 }
@@ -3,7 +3,8 @@
 use crate::cursor::HasCursor;
 use crate::database::Database;
 use crate::postgres::{
-    PgArguments, PgConnection, PgCursor, PgError, PgRawBuffer, PgRow, PgTypeInfo, PgValue,
+    PgArguments, PgConnection, PgCursor, PgError, PgQueryResult, PgRawBuffer, PgRow, PgTypeInfo,
+    PgValue,
 };
 use crate::row::HasRow;
 use crate::value::HasRawValue;
@@ -23,6 +24,8 @@ impl Database for Postgres {
 
     type RawBuffer = PgRawBuffer;
 
+    type QueryResult = PgQueryResult;
+
     type Error = PgError;
 }
 
@@ -7,6 +7,28 @@ use crate::postgres::protocol::Response;
 #[derive(Debug)]
 pub struct PgError(pub(super) Response);
 
+impl PgError {
+    /// If this error was raised while copying rows in with a
+    /// [`PgCopyInWriter`](crate::postgres::PgCopyInWriter), the number of the row that could not
+    /// be copied, starting at 1.
+    pub fn copy_row(&self) -> Option<u64> {
+        // the context of the error; e.g., `COPY users, line 3, column name: "..."`
+        self.0
+            .where_
+            .as_deref()?
+            .lines()
+            .filter_map(|context| context.strip_prefix("COPY "))
+            .find_map(|context| {
+                let line = &context[context.find(", line ")? + 7..];
+                let end = line
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(line.len());
+
+                line[..end].parse().ok()
+            })
+    }
+}
+
 impl DatabaseError for PgError {
     fn message(&self) -> &str {
         &self.0.message
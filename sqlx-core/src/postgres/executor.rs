@@ -1,4 +1,3 @@
-use std::collections::HashMap;
 use std::fmt::Write;
 use std::sync::Arc;
 
@@ -9,6 +8,7 @@ use crate::arguments::Arguments;
 use crate::cursor::Cursor;
 use crate::describe::{Column, Describe};
 use crate::executor::{Execute, Executor, RefExecutor};
+use crate::postgres::copy;
 use crate::postgres::protocol::{
     self, CommandComplete, Message, ParameterDescription, ReadyForQuery, RowDescription,
     StatementId, TypeFormat, TypeId,
@@ -22,6 +22,7 @@ use crate::postgres::{
 };
 use crate::query_as::query_as;
 use crate::row::Row;
+use crate::statement::Statement as StatementInfo;
 
 impl PgConnection {
     pub(crate) fn write_simple_query(&mut self, query: &str) {
@@ -34,8 +35,12 @@ impl PgConnection {
         args: &PgArguments,
     ) -> crate::Result<StatementId> {
         if let Some(&id) = self.cache_statement_id.get(query) {
+            self.cache_metrics.hits += 1;
+
             Ok(id)
         } else {
+            self.cache_metrics.misses += 1;
+
             let id = StatementId(self.next_statement_id);
 
             self.next_statement_id += 1;
@@ -80,7 +85,7 @@ impl PgConnection {
             let pd = self.expect_param_desc().await?;
 
             // expecting a `RowDescription` next (or `NoData` for an empty statement)
-            let statement = self.expect_row_desc(pd).await?;
+            let statement = self.expect_row_desc(query, pd).await?;
 
             // cache statement ID and statement description
             self.cache_statement_id.insert(query.into(), id);
@@ -97,7 +102,8 @@ impl PgConnection {
         let mut params = Vec::with_capacity(pd.ids.len());
 
         for ty in pd.ids.iter() {
-            let type_info = self.get_type_info_by_oid(ty.0, true).await?;
+            // parameter types are only needed by `describe`, which fetches any missing names
+            let type_info = self.get_type_info_by_oid(ty.0, false).await?;
 
             params.push(type_info);
         }
@@ -107,30 +113,32 @@ impl PgConnection {
 
     pub(crate) async fn parse_row_description(
         &mut self,
+        sql: &str,
         mut rd: RowDescription,
         params: Box<[PgTypeInfo]>,
         type_format: Option<TypeFormat>,
         fetch_type_info: bool,
     ) -> crate::Result<Statement> {
-        let mut names = HashMap::new();
-        let mut columns = Vec::new();
-
-        columns.reserve(rd.fields.len());
-        names.reserve(rd.fields.len());
-
-        for (index, field) in rd.fields.iter_mut().enumerate() {
-            let name = if let Some(name) = field.name.take() {
-                let name = SharedStr::from(name.into_string());
-                names.insert(name.clone(), index);
-                Some(name)
-            } else {
-                None
-            };
+        let mut columns = Vec::with_capacity(rd.fields.len());
+        let mut info = Vec::with_capacity(rd.fields.len());
+
+        for field in rd.fields.iter_mut() {
+            let name = field
+                .name
+                .take()
+                .map(|name| SharedStr::from(name.into_string()));
 
             let type_info = self
                 .get_type_info_by_oid(field.type_id.0, fetch_type_info)
                 .await?;
 
+            info.push(Column {
+                name: name.as_deref().map(Into::into),
+                table_id: field.table_id,
+                type_info: Some(type_info.clone()),
+                non_null: None,
+            });
+
             columns.push(StatementColumn {
                 type_info,
                 name,
@@ -143,7 +151,7 @@ impl PgConnection {
         Ok(Statement {
             params,
             columns: columns.into_boxed_slice(),
-            names,
+            info: Arc::new(StatementInfo::new(sql, info, self.sql_redaction)),
         })
     }
 
@@ -163,7 +171,11 @@ impl PgConnection {
 
     // Used to describe the incoming results
     // We store the column map in an Arc and share it among all rows
-    async fn expect_row_desc(&mut self, pd: ParameterDescription) -> crate::Result<Statement> {
+    async fn expect_row_desc(
+        &mut self,
+        sql: &str,
+        pd: ParameterDescription,
+    ) -> crate::Result<Statement> {
         let description: Option<_> = match self.stream.receive().await? {
             Message::RowDescription => Some(RowDescription::read(self.stream.buffer())?),
 
@@ -179,13 +191,13 @@ impl PgConnection {
         let params = self.parse_parameter_description(pd).await?;
 
         if let Some(description) = description {
-            self.parse_row_description(description, params, Some(TypeFormat::Binary), true)
+            self.parse_row_description(sql, description, params, Some(TypeFormat::Binary), true)
                 .await
         } else {
             Ok(Statement {
                 params,
-                names: HashMap::new(),
                 columns: Default::default(),
+                info: Arc::new(StatementInfo::new(sql, Vec::new(), self.sql_redaction)),
             })
         }
     }
@@ -202,10 +214,32 @@ impl PgConnection {
     ) -> crate::Result<()> {
         args.buffer.patch_type_holes(self).await?;
 
+        // a value for a parameter of an unspecified type is sent as text, for postgres to parse
+        // as whichever type it inferred
+        let formats: Vec<TypeFormat>;
+        let formats = if args
+            .types
+            .iter()
+            .any(|ty| ty.id == Some(TypeId::UNSPECIFIED))
+        {
+            formats = args
+                .types
+                .iter()
+                .map(|ty| match ty.id {
+                    Some(TypeId::UNSPECIFIED) => TypeFormat::Text,
+                    _ => TypeFormat::Binary,
+                })
+                .collect();
+
+            &*formats
+        } else {
+            &[TypeFormat::Binary]
+        };
+
         self.stream.write(protocol::Bind {
             portal,
             statement,
-            formats: &[TypeFormat::Binary],
+            formats,
             values_len: args.types.len() as i16,
             values: &*args.buffer,
             result_formats: &[TypeFormat::Binary],
@@ -230,11 +264,35 @@ impl PgConnection {
         // the previous query
 
         if !self.is_ready {
+            // anything still buffered (such as the [CopyFail] of a dropped copy) must reach
+            // postgres before it can finish
+            self.stream.flush().await?;
+
             loop {
-                if let Message::ReadyForQuery = self.stream.receive().await? {
-                    // we are now ready to go
-                    self.is_ready = true;
-                    break;
+                match self.stream.receive().await {
+                    Ok(Message::ReadyForQuery) if self.queued_queries > 0 => {
+                        // there is still a queued [ROLLBACK] to wait on
+                        self.queued_queries -= 1;
+                    }
+
+                    Ok(Message::ReadyForQuery) => {
+                        // we are now ready to go
+                        self.is_ready = true;
+                        self.aborting_copy = false;
+                        break;
+                    }
+
+                    // the copy that was dropped (see [PgCopyInWriter::drop]) was failed by
+                    // postgres as we asked it to; no one is waiting for that error
+                    Err(crate::Error::Database(error))
+                        if self.aborting_copy && copy::is_copy_fail(&*error, copy::DROPPED) =>
+                    {
+                        self.aborting_copy = false;
+                    }
+
+                    Ok(_) => {}
+
+                    Err(error) => return Err(error),
                 }
             }
         }
@@ -251,8 +309,15 @@ impl PgConnection {
     pub(crate) async fn run(
         &mut self,
         query: &str,
-        arguments: Option<PgArguments>,
+        mut arguments: Option<PgArguments>,
     ) -> crate::Result<Option<StatementId>> {
+        if let Some(error) = arguments
+            .as_mut()
+            .and_then(|arguments| arguments.error.take())
+        {
+            return Err(crate::Error::Encode(error));
+        }
+
         let statement = if let Some(mut arguments) = arguments {
             // Check the statement cache for a statement ID that matches the given query
             // If it doesn't exist, we generate a new statement ID and write out [Parse] to the
@@ -293,16 +358,24 @@ impl PgConnection {
         query: &'q str,
     ) -> crate::Result<Describe<Postgres>> {
         let statement_id = self.write_prepare(query, &Default::default()).await?;
-        let statement = &self.cache_statement[&statement_id];
+        let statement = Arc::clone(&self.cache_statement[&statement_id]);
         let columns = statement.columns.to_vec();
 
+        let mut param_types = Vec::with_capacity(statement.params.len());
+
+        for info in statement.params.iter() {
+            // the names of non-builtin types are only looked up once they are asked for
+            let info = match info.id {
+                Some(id) if info.name.is_empty() => self.get_type_info_by_oid(id.0, true).await?,
+                _ => info.clone(),
+            };
+
+            param_types.push(Some(info));
+        }
+
         Ok(Describe {
-            param_types: statement
-                .params
-                .iter()
-                .map(|info| Some(info.clone()))
-                .collect::<Vec<_>>()
-                .into_boxed_slice(),
+            param_names: vec![None; param_types.len()].into_boxed_slice(),
+            param_types: param_types.into_boxed_slice(),
             result_columns: self.map_result_columns(columns).await?.into_boxed_slice(),
         })
     }
@@ -488,7 +561,7 @@ impl Executor for super::PgConnection {
     where
         E: Execute<'q, Self::Database>,
     {
-        log_execution!(query, {
+        log_execution!(self.log_settings, query, {
             Box::pin(async move {
                 let (query, arguments) = query.into_parts();
 
@@ -502,7 +575,9 @@ impl Executor for super::PgConnection {
     where
         E: Execute<'q, Self::Database>,
     {
-        log_execution!(query, { PgCursor::from_connection(self, query) })
+        log_execution!(self.log_settings, query, {
+            PgCursor::from_connection(self, query)
+        })
     }
 
     #[doc(hidden)]
@@ -524,6 +599,8 @@ impl<'c> RefExecutor<'c> for &'c mut super::PgConnection {
     where
         E: Execute<'q, Self::Database>,
     {
-        log_execution!(query, { PgCursor::from_connection(self, query) })
+        log_execution!(self.log_settings, query, {
+            PgCursor::from_connection(self, query)
+        })
     }
 }
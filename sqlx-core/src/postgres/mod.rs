@@ -3,23 +3,30 @@
 pub use arguments::PgArguments;
 pub use buffer::PgRawBuffer;
 pub use connection::PgConnection;
+pub use copy::{PgCopyInWriter, PgCopyOutReader, PgCopyRow};
 pub use cursor::PgCursor;
 pub use database::Postgres;
 pub use error::PgError;
 pub use listen::{PgListener, PgNotification};
+pub use options::PgConnectOptions;
+pub use query_result::PgQueryResult;
 pub use row::PgRow;
 pub use type_info::PgTypeInfo;
 pub use value::{PgData, PgValue};
 
 mod arguments;
 mod buffer;
+mod columnar;
 mod connection;
+mod copy;
 mod cursor;
 mod database;
 mod error;
 mod executor;
 mod listen;
+mod options;
 mod protocol;
+mod query_result;
 mod row;
 mod sasl;
 mod stream;
@@ -0,0 +1,34 @@
+use crate::io::BufMut;
+use crate::postgres::protocol::Write;
+use byteorder::NetworkEndian;
+
+// https://www.postgresql.org/docs/12/protocol-flow.html#id-1.10.5.7.9
+#[derive(Debug)]
+pub struct CancelRequest {
+    pub process_id: u32,
+    pub secret_key: u32,
+}
+
+impl Write for CancelRequest {
+    fn write(&self, buf: &mut Vec<u8>) {
+        // packet length: 16 bytes including self
+        buf.put_u32::<NetworkEndian>(16);
+        // 1234 in high 16 bits, 5678 in low 16
+        buf.put_u32::<NetworkEndian>((1234 << 16) | 5678);
+        buf.put_u32::<NetworkEndian>(self.process_id);
+        buf.put_u32::<NetworkEndian>(self.secret_key);
+    }
+}
+
+#[test]
+fn test_cancel_request() {
+    let mut buf = Vec::new();
+
+    CancelRequest {
+        process_id: 10182,
+        secret_key: 2303903019,
+    }
+    .write(&mut buf);
+
+    assert_eq!(&buf, b"\x00\x00\x00\x10\x04\xd2\x16.\0\0'\xc6\x89R\xc5+");
+}
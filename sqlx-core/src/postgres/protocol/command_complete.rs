@@ -4,6 +4,10 @@ use crate::postgres::database::Postgres;
 #[derive(Debug)]
 pub(crate) struct CommandComplete {
     pub(crate) affected_rows: u64,
+
+    // The count is of the rows the command returned or skipped over rather than affected, as
+    // for a SELECT, FETCH or MOVE
+    pub(crate) returned_rows: bool,
 }
 
 impl CommandComplete {
@@ -12,8 +16,13 @@ impl CommandComplete {
         // If it can't be parsed, the tag is probably "CREATE TABLE" or something
         // and we should return 0 rows
 
-        let rows = buf
-            .get_str_nul()?
+        let tag = buf.get_str_nul()?;
+
+        let returned_rows = ["SELECT ", "FETCH ", "MOVE "]
+            .iter()
+            .any(|command| tag.starts_with(command));
+
+        let rows = tag
             .rsplit(' ')
             .next()
             .and_then(|s| s.parse().ok())
@@ -21,6 +30,7 @@ impl CommandComplete {
 
         Ok(Self {
             affected_rows: rows,
+            returned_rows,
         })
     }
 }
@@ -33,6 +43,7 @@ mod tests {
     const COMMAND_COMPLETE_UPDATE: &[u8] = b"UPDATE 512\0";
     const COMMAND_COMPLETE_CREATE_TABLE: &[u8] = b"CREATE TABLE\0";
     const COMMAND_COMPLETE_BEGIN: &[u8] = b"BEGIN\0";
+    const COMMAND_COMPLETE_SELECT: &[u8] = b"SELECT 24\0";
 
     #[test]
     fn it_reads_command_complete_for_insert() {
@@ -46,6 +57,7 @@ mod tests {
         let message = CommandComplete::read(COMMAND_COMPLETE_UPDATE).unwrap();
 
         assert_eq!(message.affected_rows, 512);
+        assert!(!message.returned_rows);
     }
 
     #[test]
@@ -61,4 +73,12 @@ mod tests {
 
         assert_eq!(message.affected_rows, 0);
     }
+
+    #[test]
+    fn it_reads_command_complete_for_select() {
+        let message = CommandComplete::read(COMMAND_COMPLETE_SELECT).unwrap();
+
+        assert_eq!(message.affected_rows, 24);
+        assert!(message.returned_rows);
+    }
 }
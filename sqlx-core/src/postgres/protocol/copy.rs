@@ -0,0 +1,90 @@
+use byteorder::NetworkEndian;
+
+use crate::io::{Buf, BufMut};
+use crate::postgres::protocol::{TypeFormat, Write};
+
+// https://www.postgresql.org/docs/12/protocol-message-formats.html
+
+/// A chunk of `COPY` data.
+pub struct CopyData<'a>(pub &'a [u8]);
+
+impl Write for CopyData<'_> {
+    fn write(&self, buf: &mut Vec<u8>) {
+        buf.push(b'd');
+        buf.put_i32::<NetworkEndian>((4 + self.0.len()) as i32);
+        buf.extend_from_slice(self.0);
+    }
+}
+
+/// All of the data of a `COPY ... FROM STDIN` has been sent.
+pub struct CopyDone;
+
+impl Write for CopyDone {
+    fn write(&self, buf: &mut Vec<u8>) {
+        buf.push(b'c');
+        buf.put_i32::<NetworkEndian>(4);
+    }
+}
+
+/// Abort a `COPY ... FROM STDIN`, failing it with the given error message.
+pub struct CopyFail<'a>(pub &'a str);
+
+impl Write for CopyFail<'_> {
+    fn write(&self, buf: &mut Vec<u8>) {
+        buf.push(b'f');
+
+        // len + message + nul
+        buf.put_i32::<NetworkEndian>((4 + self.0.len() + 1) as i32);
+        buf.put_str_nul(self.0);
+    }
+}
+
+/// The body of a `CopyInResponse` or `CopyOutResponse`.
+#[derive(Debug)]
+pub(crate) struct CopyResponse {
+    // the format of the copy as a whole; if it is TEXT, so are all of the columns
+    pub(crate) format: TypeFormat,
+    pub(crate) columns: u16,
+}
+
+impl CopyResponse {
+    pub(crate) fn read(mut buf: &[u8]) -> crate::Result<Self> {
+        let format = match buf.get_i8()? {
+            0 => TypeFormat::Text,
+            1 => TypeFormat::Binary,
+
+            format => {
+                return Err(protocol_err!("unknown COPY format: {}", format).into());
+            }
+        };
+
+        let columns = buf.get_u16::<NetworkEndian>()?;
+
+        Ok(Self { format, columns })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::postgres::protocol::Write;
+
+    #[test]
+    fn it_writes_copy_messages() {
+        let mut buf = Vec::new();
+
+        CopyData(b"\x00\x01").write(&mut buf);
+        CopyDone.write(&mut buf);
+        CopyFail("no").write(&mut buf);
+
+        assert_eq!(buf, b"d\0\0\0\x06\x00\x01c\0\0\0\x04f\0\0\0\x07no\0");
+    }
+
+    #[test]
+    fn it_reads_copy_response() {
+        let response = CopyResponse::read(b"\x01\x00\x02\x00\x01\x00\x01").unwrap();
+
+        assert!(matches!(response.format, TypeFormat::Binary));
+        assert_eq!(response.columns, 2);
+    }
+}
@@ -9,6 +9,10 @@ pub enum Message {
     BindComplete,
     CloseComplete,
     CommandComplete,
+    CopyData,
+    CopyDone,
+    CopyInResponse,
+    CopyOutResponse,
     DataRow,
     EmptyQueryResponse,
     ErrorResponse,
@@ -37,6 +41,10 @@ impl TryFrom<u8> for Message {
             b'R' => Message::Authentication,
             b'K' => Message::BackendKeyData,
             b'C' => Message::CommandComplete,
+            b'd' => Message::CopyData,
+            b'c' => Message::CopyDone,
+            b'G' => Message::CopyInResponse,
+            b'H' => Message::CopyOutResponse,
             b'A' => Message::NotificationResponse,
             b'1' => Message::ParseComplete,
             b'2' => Message::BindComplete,
@@ -12,6 +12,9 @@ pub struct TypeId(pub(crate) u32);
 
 #[allow(dead_code)]
 impl TypeId {
+    // Lets postgres infer the type of a parameter
+    pub(crate) const UNSPECIFIED: TypeId = TypeId(0);
+
     // Scalar
 
     pub(crate) const BOOL: TypeId = TypeId(16);
@@ -39,6 +42,7 @@ impl TypeId {
     pub(crate) const TIME: TypeId = TypeId(1083);
     pub(crate) const TIMESTAMP: TypeId = TypeId(1114);
     pub(crate) const TIMESTAMPTZ: TypeId = TypeId(1184);
+    pub(crate) const INTERVAL: TypeId = TypeId(1186);
 
     pub(crate) const BYTEA: TypeId = TypeId(17);
 
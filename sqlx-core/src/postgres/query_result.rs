@@ -0,0 +1,17 @@
+/// The result of a statement run by [`Executor::fetch_many`] on a **Postgres** connection.
+///
+/// [`Executor::fetch_many`]: crate::executor::Executor::fetch_many
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PgQueryResult {
+    pub(super) rows_affected: u64,
+}
+
+impl PgQueryResult {
+    /// Returns the number of rows inserted, updated or deleted by the statement.
+    ///
+    /// This is `0` for a `SELECT` (or `FETCH` or `MOVE`), whose command tag counts the rows that
+    /// it returned instead.
+    pub fn rows_affected(&self) -> u64 {
+        self.rows_affected
+    }
+}
@@ -1,4 +1,3 @@
-use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::postgres::protocol::{DataRow, TypeFormat};
@@ -6,6 +5,7 @@ use crate::postgres::type_info::SharedStr;
 use crate::postgres::value::PgValue;
 use crate::postgres::{PgTypeInfo, Postgres};
 use crate::row::{ColumnIndex, Row};
+use crate::statement::Statement as StatementInfo;
 
 // A statement has 0 or more columns being returned from the database
 // For Postgres, each column has an OID and a format (binary or text)
@@ -27,11 +27,11 @@ pub(crate) struct Statement {
     // paramaters
     pub(crate) params: Box<[PgTypeInfo]>,
 
-    // column name -> position
-    pub(crate) names: HashMap<SharedStr, usize>,
-
     // all columns
     pub(crate) columns: Box<[Column]>,
+
+    // what is exposed as [Row::statement], which also maps each column name to its position
+    pub(crate) info: Arc<StatementInfo<Postgres>>,
 }
 
 pub struct PgRow<'c> {
@@ -52,6 +52,11 @@ impl<'c> Row<'c> for PgRow<'c> {
         self.data.len()
     }
 
+    #[inline]
+    fn statement(&self) -> &Arc<StatementInfo<Postgres>> {
+        &self.statement.info
+    }
+
     #[doc(hidden)]
     fn try_get_raw<I>(&self, index: I) -> crate::Result<PgValue<'c>>
     where
@@ -85,6 +90,7 @@ impl<'c> ColumnIndex<'c, PgRow<'c>> for usize {
 impl<'c> ColumnIndex<'c, PgRow<'c>> for str {
     fn index(&self, row: &PgRow<'c>) -> crate::Result<usize> {
         row.statement
+            .info
             .names
             .get(self)
             .ok_or_else(|| crate::Error::ColumnNotFound((*self).into()))
@@ -5,7 +5,7 @@ use byteorder::NetworkEndian;
 use futures_channel::mpsc::UnboundedSender;
 
 use crate::io::{Buf, BufStream, MaybeTlsStream};
-use crate::postgres::protocol::{Message, NotificationResponse, Response, Write};
+use crate::postgres::protocol::{Message, NotificationResponse, Response, Terminate, Write};
 use crate::postgres::PgError;
 
 use crate::url::Url;
@@ -19,6 +19,12 @@ pub struct PgStream {
     // Is referenced by our buffered stream
     // Is initialized to ReadyForQuery/0 at the start
     pub(super) message: (Message, u32),
+
+    // Should `Terminate` be sent when this is dropped
+    // Is set once startup completes and cleared by an explicit close
+    pub(super) terminate_on_drop: bool,
+    // Count of flushes that sent anything; see `StatementCacheMetrics::round_trips`
+    pub(super) round_trips: u64,
 }
 
 impl PgStream {
@@ -45,11 +51,17 @@ impl PgStream {
         #[cfg(not(unix))]
         let stream = MaybeTlsStream::connect(host.unwrap_or("localhost"), port).await?;
 
-        Ok(Self {
+        Ok(Self::from_stream(stream))
+    }
+
+    pub(super) fn from_stream(stream: MaybeTlsStream) -> Self {
+        Self {
             notifications: None,
             stream: BufStream::new(stream),
             message: (Message::ReadyForQuery, 0),
-        })
+            terminate_on_drop: false,
+            round_trips: 0,
+        }
     }
 
     pub(super) fn shutdown(&self) -> crate::Result<()> {
@@ -66,6 +78,10 @@ impl PgStream {
 
     #[inline]
     pub(super) async fn flush(&mut self) -> crate::Result<()> {
+        if !self.stream.buffer_mut().is_empty() {
+            self.round_trips += 1;
+        }
+
         Ok(self.stream.flush().await?)
     }
 
@@ -140,3 +156,18 @@ impl PgStream {
         &self.stream.buffer()[..(self.message.1 as usize)]
     }
 }
+
+// Best-effort graceful close for a connection that was not explicitly closed
+// The write is attempted only if the socket can take it right now; we can't wait here as
+// the executor may already be gone (e.g., the runtime is shutting down)
+impl Drop for PgStream {
+    fn drop(&mut self) {
+        if self.terminate_on_drop {
+            // anything left in the buffer belongs to an abandoned request and must not be sent
+            self.stream.buffer_mut().clear();
+            self.write(Terminate);
+
+            let _ = self.stream.flush_now();
+        }
+    }
+}
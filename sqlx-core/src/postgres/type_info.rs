@@ -1,6 +1,6 @@
 use crate::postgres::protocol::TypeId;
 use crate::types::TypeInfo;
-use std::borrow::Borrow;
+use std::borrow::{Borrow, Cow};
 use std::fmt;
 use std::fmt::Display;
 use std::hash::{Hash, Hasher};
@@ -33,6 +33,20 @@ impl PgTypeInfo {
         }
     }
 
+    // Used by the query macros to rebuild the type of a parameter in their expansion
+    #[doc(hidden)]
+    pub const fn with_oid(oid: u32, name: &'static str) -> Self {
+        Self {
+            id: Some(TypeId(oid)),
+            name: SharedStr::Static(name),
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn oid(&self) -> Option<u32> {
+        self.id.map(|id| id.0)
+    }
+
     #[doc(hidden)]
     pub fn type_feature_gate(&self) -> Option<&'static str> {
         match self.id? {
@@ -63,6 +77,10 @@ impl PartialEq<PgTypeInfo> for PgTypeInfo {
 }
 
 impl TypeInfo for PgTypeInfo {
+    fn name(&self) -> Cow<'_, str> {
+        Cow::Borrowed(&self.name)
+    }
+
     fn compatible(&self, other: &Self) -> bool {
         if let (Some(self_id), Some(other_id)) = (self.id, other.id) {
             return match (self_id, other_id) {
@@ -1,5 +1,7 @@
 //! Encoding and decoding of Postgres arrays.
 
+use std::error::Error as StdError;
+
 use crate::database::Database;
 use crate::decode::Decode;
 use crate::encode::Encode;
@@ -21,6 +23,10 @@ where
 
         encoder.finish();
     }
+
+    fn check(&self) -> Result<(), Box<dyn StdError + Send + Sync>> {
+        self.iter().try_for_each(Encode::check)
+    }
 }
 
 impl<T> Encode<Postgres> for Vec<T>
@@ -31,6 +37,10 @@ where
     fn encode(&self, buf: &mut PgRawBuffer) {
         self.as_slice().encode(buf)
     }
+
+    fn check(&self) -> Result<(), Box<dyn StdError + Send + Sync>> {
+        self.as_slice().check()
+    }
 }
 
 impl<'de, T> Decode<'de, Postgres> for Vec<T>
@@ -54,6 +64,10 @@ where
     fn type_info() -> DB::TypeInfo {
         <[T] as Type<DB>>::type_info()
     }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <[T] as Type<DB>>::compatible(ty)
+    }
 }
 
 impl<T, DB> Type<DB> for [Option<T>]
@@ -65,4 +79,8 @@ where
     fn type_info() -> DB::TypeInfo {
         <[T] as Type<DB>>::type_info()
     }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <[T] as Type<DB>>::compatible(ty)
+    }
 }
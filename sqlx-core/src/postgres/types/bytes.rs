@@ -44,10 +44,7 @@ impl<'de> Decode<'de, Postgres> for Vec<u8> {
     fn decode(value: PgValue<'de>) -> crate::Result<Self> {
         match value.try_get()? {
             PgData::Binary(buf) => Ok(buf.to_vec()),
-            PgData::Text(s) => {
-                // BYTEA is formatted as \x followed by hex characters
-                hex::decode(&s[2..]).map_err(crate::Error::decode)
-            }
+            PgData::Text(s) => decode_text(s),
         }
     }
 }
@@ -64,3 +61,69 @@ impl<'de> Decode<'de, Postgres> for &'de [u8] {
         }
     }
 }
+
+// https://www.postgresql.org/docs/current/datatype-binary.html
+//
+// A BYTEA in text is either `\x` followed by 2 hex digits for each byte, when `bytea_output` is
+// `hex` (the default), or the bytes themselves with `\\` for a backslash and `\NNN` (in octal)
+// for any other byte that is not printable ASCII, when it is `escape`
+fn decode_text(s: &str) -> crate::Result<Vec<u8>> {
+    if let Some(digits) = s.strip_prefix("\\x") {
+        return hex::decode(digits)
+            .map_err(|err| decode_err!("invalid BYTEA in the hex format: {}", err));
+    }
+
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut rest = s.as_bytes();
+
+    while let Some((&byte, tail)) = rest.split_first() {
+        rest = tail;
+
+        if byte != b'\\' {
+            bytes.push(byte);
+            continue;
+        }
+
+        match rest {
+            [b'\\', tail @ ..] => {
+                bytes.push(b'\\');
+                rest = tail;
+            }
+
+            [a @ b'0'..=b'3', b @ b'0'..=b'7', c @ b'0'..=b'7', tail @ ..] => {
+                bytes.push((a - b'0') << 6 | (b - b'0') << 3 | (c - b'0'));
+                rest = tail;
+            }
+
+            _ => {
+                return Err(crate::Error::Decode(
+                    "invalid BYTEA in the escape format: a backslash must be followed by \
+                     another backslash or 3 octal digits"
+                        .into(),
+                ));
+            }
+        }
+    }
+
+    Ok(bytes)
+}
+
+#[test]
+fn test_decode_bytea_text() {
+    let decode = |s| -> crate::Result<Vec<u8>> { Decode::<Postgres>::decode(PgValue::from_str(s)) };
+
+    assert_eq!(decode("\\x").unwrap(), b"");
+    assert_eq!(decode("\\x00dEaD5cff").unwrap(), b"\0\xDE\xAD\\\xFF");
+
+    assert_eq!(decode("").unwrap(), b"");
+    assert_eq!(decode("abc").unwrap(), b"abc");
+    assert_eq!(decode("\\000\\336\\\\\\377").unwrap(), b"\0\xDE\\\xFF");
+
+    let hex_err = |s| decode(s).unwrap_err().to_string();
+
+    assert!(hex_err("\\x0").starts_with("invalid BYTEA in the hex format"));
+    assert!(hex_err("\\xZZ").starts_with("invalid BYTEA in the hex format"));
+    assert!(decode("\\").is_err());
+    assert!(decode("\\400").is_err());
+    assert!(decode("\\12").is_err());
+}
@@ -12,6 +12,17 @@
 //! | `f64`                                 | DOUBLE PRECISION, FLOAT8                             |
 //! | `&str`, `String`                      | VARCHAR, CHAR(N), TEXT, NAME                         |
 //! | `&[u8]`, `Vec<u8>`                    | BYTEA                                                |
+//! | `std::time::SystemTime`               | TIMESTAMPTZ                                          |
+//! | `std::time::Duration`                 | INTERVAL                                             |
+//!
+//! `NonZeroI16`, `NonZeroI32`, `NonZeroI64` and `NonZeroU32` from `std::num` are encoded and
+//! decoded as the integer they wrap; decoding a `0` as one of them is an error. The query macros
+//! never choose these, but `query_as!()` accepts them as the type of a field.
+//!
+//! `SystemTime` and `Duration` need no extra dependencies but are only chosen by the query
+//! macros when neither the `chrono` nor the `time` feature is enabled. Both are truncated to
+//! microseconds. Times before the UNIX epoch are supported. An `INTERVAL` can only be decoded
+//! as a `Duration` if it is not negative and has no months or years as their length varies.
 //!
 //! ### [`chrono`](https://crates.io/crates/chrono)
 //!
@@ -122,6 +133,11 @@
 //! enum Mood { Sad = 0, Ok = 1, Happy = 2 }
 //! ```
 //!
+//! # Parameters of an unspecified type
+//!
+//! A string wrapped in [`PgUnknown`] is bound to a parameter without a type, leaving it to
+//! Postgres to infer the type of the parameter from the query; see there for the tradeoffs.
+//!
 //! # Nullable
 //!
 //! In addition, `Option<T>` is supported where `T` implements `Type`. An `Option<T>` represents
@@ -138,7 +154,11 @@ mod bytes;
 mod float;
 mod int;
 mod record;
+mod std_time;
 mod str;
+mod unknown;
+
+pub use unknown::PgUnknown;
 
 // internal types used by other types to encode or decode related formats
 #[doc(hidden)]
@@ -206,6 +226,7 @@ pub(crate) fn try_resolve_type_name(oid: u32) -> Option<&'static str> {
         TypeId::TIME => "TIME",
         TypeId::TIMESTAMP => "TIMESTAMP",
         TypeId::TIMESTAMPTZ => "TIMESTAMPTZ",
+        TypeId::INTERVAL => "INTERVAL",
 
         TypeId::BYTEA => "BYTEA",
 
@@ -2,7 +2,7 @@ use crate::decode::Decode;
 use crate::io::Buf;
 use crate::postgres::protocol::TypeId;
 use crate::postgres::{PgData, PgTypeInfo, PgValue, Postgres};
-use crate::types::{Type, TypeInfo};
+use crate::types::Type;
 use byteorder::BigEndian;
 
 pub(crate) struct PgSequenceDecoder<'de> {
@@ -63,7 +63,7 @@ impl<'de> PgSequenceDecoder<'de> {
                     let element_oid = buf.get_u32::<BigEndian>()?;
                     let expected_ty = PgTypeInfo::new(TypeId(element_oid), "");
 
-                    if !expected_ty.compatible(&T::type_info()) {
+                    if !T::compatible(&expected_ty) {
                         return Err(crate::Error::mismatched_types::<Postgres, T>(expected_ty));
                     }
 
@@ -0,0 +1,286 @@
+use std::convert::TryFrom;
+use std::error::Error as StdError;
+use std::mem;
+use std::time::{Duration, SystemTime};
+
+use byteorder::BigEndian;
+
+use crate::civil::{self, DateTime};
+use crate::decode::Decode;
+use crate::encode::Encode;
+use crate::io::Buf;
+use crate::postgres::protocol::TypeId;
+use crate::postgres::{PgData, PgRawBuffer, PgTypeInfo, PgValue, Postgres};
+use crate::types::Type;
+
+// 2000-01-01 00:00:00 UTC as seconds since the UNIX epoch
+const POSTGRES_EPOCH: i64 = 946_684_800;
+
+const MICROS_PER_SEC: i64 = 1_000_000;
+const MICROS_PER_DAY: i128 = 86_400 * MICROS_PER_SEC as i128;
+
+impl Type<Postgres> for SystemTime {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::new(TypeId::TIMESTAMPTZ, "TIMESTAMPTZ")
+    }
+}
+
+impl Type<Postgres> for Duration {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::new(TypeId::INTERVAL, "INTERVAL")
+    }
+}
+
+impl<'de> Decode<'de, Postgres> for SystemTime {
+    fn decode(value: PgValue<'de>) -> crate::Result<Self> {
+        match value.try_get()? {
+            PgData::Binary(mut buf) => {
+                let micros = buf.get_i64::<BigEndian>()?;
+
+                if micros == i64::MAX || micros == i64::MIN {
+                    return Err(crate::Error::Decode(
+                        "infinite TIMESTAMPTZ cannot be decoded as SystemTime".into(),
+                    ));
+                }
+
+                civil::from_unix_timestamp(
+                    POSTGRES_EPOCH + micros.div_euclid(MICROS_PER_SEC),
+                    micros.rem_euclid(MICROS_PER_SEC) as u32 * 1000,
+                )
+            }
+
+            PgData::Text(s) => {
+                let (secs, nanos) = DateTime::parse(s)?.unix_timestamp();
+
+                civil::from_unix_timestamp(secs, nanos)
+            }
+        }
+    }
+}
+
+/// Postgres only stores microseconds; any further precision is truncated.
+///
+/// A time more than `i64::MAX` microseconds from 2000-01-01 cannot be encoded.
+impl Encode<Postgres> for SystemTime {
+    fn encode(&self, buf: &mut PgRawBuffer) {
+        // a time out of range is reported by [check] and never sent
+        let micros = to_micros(self).unwrap_or_default();
+
+        Encode::<Postgres>::encode(&micros, buf);
+    }
+
+    fn check(&self) -> Result<(), Box<dyn StdError + Send + Sync>> {
+        to_micros(self).map(drop)
+    }
+
+    fn size_hint(&self) -> usize {
+        mem::size_of::<i64>()
+    }
+}
+
+// The microseconds of a `SystemTime` since the Postgres epoch
+fn to_micros(time: &SystemTime) -> Result<i64, Box<dyn StdError + Send + Sync>> {
+    civil::unix_timestamp(*time)
+        .and_then(|(secs, nanos)| {
+            (secs.checked_sub(POSTGRES_EPOCH)?)
+                .checked_mul(MICROS_PER_SEC)?
+                .checked_add(nanos as i64 / 1000)
+        })
+        .ok_or_else(|| format!("SystemTime out of range for Postgres: {:?}", time).into())
+}
+
+impl<'de> Decode<'de, Postgres> for Duration {
+    fn decode(value: PgValue<'de>) -> crate::Result<Self> {
+        let micros = match value.try_get()? {
+            PgData::Binary(mut buf) => {
+                let micros = buf.get_i64::<BigEndian>()?;
+                let days = buf.get_i32::<BigEndian>()?;
+                let months = buf.get_i32::<BigEndian>()?;
+
+                if months != 0 {
+                    return Err(decode_err!(
+                        "INTERVAL of {} months cannot be decoded as Duration as months vary in length",
+                        months
+                    ));
+                }
+
+                days as i128 * MICROS_PER_DAY + micros as i128
+            }
+
+            PgData::Text(s) => parse_interval(s)?,
+        };
+
+        if micros < 0 {
+            return Err(crate::Error::Decode(
+                "negative INTERVAL cannot be decoded as Duration".into(),
+            ));
+        }
+
+        let micros = u64::try_from(micros)
+            .map_err(|_| crate::Error::Decode("INTERVAL out of range for Duration".into()))?;
+
+        Ok(Duration::from_micros(micros))
+    }
+}
+
+/// Postgres only stores microseconds; any further precision is truncated.
+///
+/// A duration longer than `i64::MAX` microseconds cannot be encoded.
+impl Encode<Postgres> for Duration {
+    fn encode(&self, buf: &mut PgRawBuffer) {
+        // a duration out of range is reported by [check] and never sent
+        let micros = i64::try_from(self.as_micros()).unwrap_or_default();
+
+        Encode::<Postgres>::encode(&micros, buf);
+        Encode::<Postgres>::encode(&0_i32, buf);
+        Encode::<Postgres>::encode(&0_i32, buf);
+    }
+
+    fn check(&self) -> Result<(), Box<dyn StdError + Send + Sync>> {
+        i64::try_from(self.as_micros())
+            .map(drop)
+            .map_err(|_| format!("Duration out of range for Postgres: {:?}", self).into())
+    }
+
+    fn size_hint(&self) -> usize {
+        mem::size_of::<i64>() + 2 * mem::size_of::<i32>()
+    }
+}
+
+// The default `IntervalStyle` of `postgres`; e.g., `1 year 2 mons -3 days +04:05:06.5`
+// https://www.postgresql.org/docs/12/datatype-datetime.html#DATATYPE-INTERVAL-OUTPUT
+fn parse_interval(s: &str) -> crate::Result<i128> {
+    let invalid = || decode_err!("invalid INTERVAL: {:?}", s);
+
+    let mut micros = 0_i128;
+    let mut parts = s.split_whitespace();
+
+    while let Some(part) = parts.next() {
+        if part.contains(':') {
+            let (sign, clock) = match part.as_bytes()[0] {
+                b'-' => (-1, &part[1..]),
+                b'+' => (1, &part[1..]),
+                _ => (1, part),
+            };
+
+            let (clock, fraction) = match clock.find('.') {
+                Some(at) => (&clock[..at], &clock[at + 1..]),
+                None => (clock, ""),
+            };
+
+            let mut fields = clock.splitn(3, ':');
+            let mut field = || -> crate::Result<i128> {
+                fields
+                    .next()
+                    .and_then(|field| field.parse().ok())
+                    .ok_or_else(invalid)
+            };
+
+            let (hours, minutes, seconds) = (field()?, field()?, field()?);
+
+            let mut fraction_micros = 0;
+
+            if !fraction.is_empty() {
+                let digits = &fraction[..fraction.len().min(6)];
+                let value: i128 = digits.parse().map_err(|_| invalid())?;
+
+                fraction_micros = value * 10_i128.pow(6 - digits.len() as u32);
+            }
+
+            micros += sign
+                * ((hours * 3600 + minutes * 60 + seconds) * MICROS_PER_SEC as i128
+                    + fraction_micros);
+
+            continue;
+        }
+
+        let quantity: i128 = part.parse().map_err(|_| invalid())?;
+        let unit = parts.next().ok_or_else(invalid)?;
+
+        match unit {
+            "day" | "days" => micros += quantity * MICROS_PER_DAY,
+
+            "year" | "years" | "mon" | "mons" if quantity == 0 => {}
+
+            "year" | "years" | "mon" | "mons" => {
+                return Err(decode_err!(
+                    "INTERVAL {:?} cannot be decoded as Duration as months vary in length",
+                    s
+                ));
+            }
+
+            _ => return Err(invalid()),
+        }
+    }
+
+    Ok(micros)
+}
+
+#[test]
+fn test_decode_interval_text() {
+    let decode =
+        |s| -> crate::Result<Duration> { Decode::<Postgres>::decode(PgValue::from_str(s)) };
+
+    assert_eq!(decode("00:00:01.5").unwrap(), Duration::from_millis(1500));
+
+    assert_eq!(
+        decode("2 days 03:00:00.000001").unwrap(),
+        Duration::from_micros(2 * 86_400_000_000 + 3 * 3_600_000_000 + 1)
+    );
+
+    assert_eq!(
+        decode("1 day -01:00:00").unwrap(),
+        Duration::from_secs(23 * 3600)
+    );
+
+    assert!(decode("1 mon").is_err());
+    assert!(decode("-00:00:01").is_err());
+}
+
+#[test]
+fn test_encode_system_time() {
+    let mut buf = PgRawBuffer::default();
+
+    let time = std::time::UNIX_EPOCH + Duration::from_secs(POSTGRES_EPOCH as u64 + 1);
+    Encode::<Postgres>::encode(&time, &mut buf);
+    assert_eq!(&**buf, 1_000_000_i64.to_be_bytes());
+    buf.clear();
+
+    // before the UNIX epoch, 1969-12-31 23:59:59.5
+    let time = std::time::UNIX_EPOCH - Duration::from_millis(500);
+    let expected = -POSTGRES_EPOCH * 1_000_000 - 500_000;
+    Encode::<Postgres>::encode(&time, &mut buf);
+    assert_eq!(&**buf, expected.to_be_bytes());
+}
+
+#[test]
+fn test_encode_out_of_range() {
+    use crate::arguments::Arguments;
+    use crate::postgres::PgArguments;
+
+    let max_secs = (i64::MAX / MICROS_PER_SEC) as u64;
+    let max = Duration::new(max_secs, 775_807_000);
+
+    assert!(Encode::<Postgres>::check(&max).is_ok());
+
+    let duration = max + Duration::from_micros(1);
+    let err = Encode::<Postgres>::check(&duration).unwrap_err();
+    assert!(err.to_string().contains("out of range"));
+
+    let mut arguments = PgArguments::default();
+    arguments.add(duration);
+    assert!(arguments.error.is_some());
+
+    // the largest time is `i64::MAX` microseconds after 2000-01-01
+    let max = std::time::UNIX_EPOCH + Duration::from_secs(POSTGRES_EPOCH as u64) + max;
+
+    assert!(Encode::<Postgres>::check(&max).is_ok());
+
+    let time = max + Duration::from_micros(1);
+    let err = Encode::<Postgres>::check(&time).unwrap_err();
+    assert!(err.to_string().contains("out of range"));
+
+    let mut arguments = PgArguments::default();
+    arguments.add(Some(time));
+    assert!(arguments.error.is_some());
+}
@@ -0,0 +1,41 @@
+use crate::encode::Encode;
+use crate::postgres::protocol::TypeId;
+use crate::postgres::{PgRawBuffer, PgTypeInfo, Postgres};
+use crate::types::Type;
+
+/// A string bound to a parameter without a type, for Postgres to infer one from how the parameter
+/// is used in the query.
+///
+/// The string is sent as text, so it must be valid input for whichever type Postgres infers.
+///
+/// ```rust,ignore
+/// // `$1` would otherwise be a `TEXT` and `coalesce` would fail to mix it with an `INT4`
+/// sqlx::query("SELECT coalesce($1, id) FROM users")
+///     .bind(PgUnknown("42"))
+/// ```
+///
+/// This is what `psql` and many other clients do for every parameter but, as it is up to
+/// Postgres, the type may not be the one you had in mind; e.g., a parameter compared to an
+/// `INT4` column is inferred to be an `INT4` and `PgUnknown("1.5")` fails to parse as one.
+/// Prefer a typed value (or a cast in SQL, such as `$1::jsonb`) where there is a choice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PgUnknown<T>(pub T);
+
+impl<T> Type<Postgres> for PgUnknown<T> {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::new(TypeId::UNSPECIFIED, "UNSPECIFIED")
+    }
+}
+
+impl<T> Encode<Postgres> for PgUnknown<T>
+where
+    T: AsRef<str>,
+{
+    fn encode(&self, buf: &mut PgRawBuffer) {
+        buf.extend_from_slice(self.0.as_ref().as_bytes());
+    }
+
+    fn size_hint(&self) -> usize {
+        self.0.as_ref().len()
+    }
+}
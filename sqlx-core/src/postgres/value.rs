@@ -53,7 +53,6 @@ impl<'c> PgValue<'c> {
         })
     }
 
-    #[cfg(test)]
     pub(crate) fn from_bytes(buf: &'c [u8]) -> Self {
         Self {
             type_info: None,
@@ -6,10 +6,13 @@ use futures_util::future::ready;
 use futures_util::TryFutureExt;
 
 use crate::arguments::Arguments;
-use crate::cursor::{Cursor, HasCursor};
+use crate::cancel::CancelHandle;
+use crate::columnar::{Columnar, ColumnarResult};
+use crate::cursor::{discard_remaining, Cursor, HasCursor};
 use crate::database::Database;
 use crate::encode::Encode;
 use crate::executor::{Execute, Executor, RefExecutor};
+use crate::logging::LevelFilter;
 use crate::row::HasRow;
 use crate::types::Type;
 
@@ -21,6 +24,8 @@ where
 {
     pub(crate) query: &'q str,
     pub(crate) arguments: DB::Arguments,
+    log_level: Option<LevelFilter>,
+    cancel_handle: Option<CancelHandle>,
     database: PhantomData<DB>,
 }
 
@@ -56,6 +61,16 @@ where
     fn query_string(&self) -> &'q str {
         self.query
     }
+
+    #[doc(hidden)]
+    fn log_level(&self) -> Option<LevelFilter> {
+        self.log_level
+    }
+
+    #[doc(hidden)]
+    fn cancel_handle(&self) -> Option<&CancelHandle> {
+        self.cancel_handle.as_ref()
+    }
 }
 
 impl<'q, DB> Query<'q, DB>
@@ -84,9 +99,45 @@ where
         Query {
             query: self.query,
             arguments,
+            log_level: self.log_level,
+            cancel_handle: self.cancel_handle,
             database: PhantomData,
         }
     }
+
+    /// Log this query at `level` instead of the level set for the connection in its
+    /// [`LogSettings`]; `None` goes back to that default.
+    ///
+    /// A level of `LevelFilter::Off` is the same as [`no_logging`](#method.no_logging).
+    ///
+    /// [`LogSettings`]: crate::logging::LogSettings
+    pub fn log_level(mut self, level: Option<LevelFilter>) -> Self {
+        self.log_level = level;
+        self
+    }
+
+    /// Never log this query, not even if it is slow or if it fails.
+    ///
+    /// This is meant for queries that run so often that their logs would drown out the others,
+    /// such as a health check, or that must never end up in a log.
+    pub fn no_logging(self) -> Self {
+        self.log_level(Some(LevelFilter::Off))
+    }
+
+    /// Returns this query along with a [`CancelHandle`], which can cancel the query from another
+    /// task while it runs. The query then resolves with [`Error::Cancelled`].
+    ///
+    /// ```rust,ignore
+    /// let (query, handle) = sqlx::query("SELECT * FROM events").cancellable();
+    /// ```
+    ///
+    /// [`Error::Cancelled`]: crate::Error::Cancelled
+    pub fn cancellable(mut self) -> (Self, CancelHandle) {
+        let handle = CancelHandle::default();
+        self.cancel_handle = Some(handle.clone());
+
+        (self, handle)
+    }
 }
 
 impl<'q, DB> Query<'q, DB>
@@ -141,6 +192,62 @@ where
     }
 }
 
+impl<'q, DB> Query<'q, DB>
+where
+    DB: Columnar,
+    Self: Execute<'q, DB>,
+{
+    /// Execute the query and collect the results column-by-column.
+    ///
+    /// Values are decoded straight from the buffers of the connection into one typed buffer per
+    /// column, without building a row for each of them. Booleans, integers, floats, text and
+    /// bytes are supported; a column of any other type (or a column that changes type between
+    /// rows, which is possible in SQLite) returns an error that names the column.
+    ///
+    /// ```rust,ignore
+    /// let result = sqlx::query("SELECT id, score FROM players")
+    ///     .fetch_columnar(&mut conn)
+    ///     .await?;
+    ///
+    /// if let ColumnValues::Int(ids) = result.columns()[0].values() {
+    ///     let ids: &[i64] = ids.values();
+    /// }
+    /// ```
+    pub async fn fetch_columnar<'e, E>(self, executor: E) -> crate::Result<ColumnarResult>
+    where
+        E: RefExecutor<'e, Database = DB>,
+        'q: 'e,
+    {
+        DB::fetch_columnar(executor.fetch_by_ref(self)).await
+    }
+}
+
+impl<'q, DB, F> Map<'q, DB, F>
+where
+    DB: Database,
+{
+    /// Log this query at `level` instead of the default of the connection; see
+    /// [Query::log_level].
+    pub fn log_level(mut self, level: Option<LevelFilter>) -> Self {
+        self.query = self.query.log_level(level);
+        self
+    }
+
+    /// Never log this query; see [Query::no_logging].
+    pub fn no_logging(mut self) -> Self {
+        self.query = self.query.no_logging();
+        self
+    }
+
+    /// Returns this query along with a [`CancelHandle`] for it; see [Query::cancellable].
+    pub fn cancellable(mut self) -> (Self, CancelHandle) {
+        let (query, handle) = self.query.cancellable();
+        self.query = query;
+
+        (self, handle)
+    }
+}
+
 impl<'q, DB, F> Map<'q, DB, F>
 where
     DB: Database,
@@ -167,7 +274,10 @@ where
         })
     }
 
-    /// Get the first row in the result
+    /// Get the first row in the result.
+    ///
+    /// Any other rows are read and discarded before this returns, so the connection can be
+    /// used right away.
     pub async fn fetch_optional<'e, E>(self, executor: E) -> crate::Result<Option<F::Output>>
     where
         E: RefExecutor<'e, Database = DB>,
@@ -177,9 +287,18 @@ where
         let mut cursor = executor.fetch_by_ref(self.query);
         let mut mapper = self.mapper;
         let val = cursor.next().await?;
-        val.map(|row| mapper.try_map_row(row)).transpose()
+        let val = val.map(|row| mapper.try_map_row(row)).transpose()?;
+
+        discard_remaining(&mut cursor).await?;
+
+        Ok(val)
     }
 
+    /// Get the first row in the result, or [`Error::RowNotFound`] if there are none.
+    ///
+    /// Like [`fetch_optional`](Self::fetch_optional), any other rows are discarded.
+    ///
+    /// [`Error::RowNotFound`]: crate::Error::RowNotFound
     pub async fn fetch_one<'e, E>(self, executor: E) -> crate::Result<F::Output>
     where
         E: RefExecutor<'e, Database = DB>,
@@ -251,6 +370,8 @@ where
     Query {
         database: PhantomData,
         arguments: Default::default(),
+        log_level: None,
+        cancel_handle: None,
         query: sql,
     }
 }
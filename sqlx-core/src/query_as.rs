@@ -1,9 +1,11 @@
 use core::marker::PhantomData;
 
 use crate::arguments::Arguments;
+use crate::cancel::CancelHandle;
 use crate::database::Database;
 use crate::encode::Encode;
 use crate::executor::Execute;
+use crate::logging::LevelFilter;
 use crate::types::Type;
 
 /// Raw SQL query with bind parameters, mapped to a concrete type
@@ -16,6 +18,8 @@ where
 {
     query: &'q str,
     arguments: <DB as Database>::Arguments,
+    log_level: Option<LevelFilter>,
+    cancel_handle: Option<CancelHandle>,
     database: PhantomData<DB>,
     output: PhantomData<O>,
 }
@@ -34,6 +38,27 @@ where
         self.arguments.add(value);
         self
     }
+
+    /// Log this query at `level` instead of the default of the connection; see
+    /// [`Query::log_level`](crate::query::Query::log_level).
+    pub fn log_level(mut self, level: Option<LevelFilter>) -> Self {
+        self.log_level = level;
+        self
+    }
+
+    /// Never log this query; see [`Query::no_logging`](crate::query::Query::no_logging).
+    pub fn no_logging(self) -> Self {
+        self.log_level(Some(LevelFilter::Off))
+    }
+
+    /// Returns this query along with a [`CancelHandle`] for it; see
+    /// [`Query::cancellable`](crate::query::Query::cancellable).
+    pub fn cancellable(mut self) -> (Self, CancelHandle) {
+        let handle = CancelHandle::default();
+        self.cancel_handle = Some(handle.clone());
+
+        (self, handle)
+    }
 }
 
 impl<'q, DB, O: Send> Execute<'q, DB> for QueryAs<'q, DB, O>
@@ -50,6 +75,18 @@ where
     fn query_string(&self) -> &'q str {
         self.query
     }
+
+    #[inline]
+    #[doc(hidden)]
+    fn log_level(&self) -> Option<LevelFilter> {
+        self.log_level
+    }
+
+    #[inline]
+    #[doc(hidden)]
+    fn cancel_handle(&self) -> Option<&CancelHandle> {
+        self.cancel_handle.as_ref()
+    }
 }
 
 /// Construct a raw SQL query that is mapped to a concrete type
@@ -63,6 +100,8 @@ where
     QueryAs {
         query: sql,
         arguments: Default::default(),
+        log_level: None,
+        cancel_handle: None,
         database: PhantomData,
         output: PhantomData,
     }
@@ -153,8 +192,11 @@ macro_rules! make_query_as {
                 Box::pin(async move {
                     let mut cursor = executor.fetch_by_ref(self);
                     let row = cursor.next().await?;
+                    let obj = row.as_ref().map(O::from_row).transpose()?;
+
+                    crate::cursor::discard_remaining(&mut cursor).await?;
 
-                    row.as_ref().map(O::from_row).transpose()
+                    Ok(obj)
                 })
             }
 
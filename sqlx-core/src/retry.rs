@@ -0,0 +1,304 @@
+//! Retrying a connection attempt that failed for a reason that is likely to go away by itself,
+//! such as the database server not accepting connections _yet_.
+
+use std::error::Error as StdError;
+use std::fmt::{self, Display};
+use std::future::Future;
+use std::io;
+use std::time::Duration;
+
+use rand::Rng;
+
+use crate::url::Url;
+use crate::Error;
+
+// The delay before a retry doubles with each attempt, up to this many times
+const MAX_BACKOFF_DOUBLINGS: u32 = 5;
+
+/// How often and how patiently to retry a connection attempt that failed with a transient error.
+///
+/// This is read from the `connect_retries` and `connect_retry_backoff` (in milliseconds)
+/// parameters of the connection URL, unless it is set on the connect options of the driver
+/// (see [`RetryOptions`]). Retrying is opt-in; by default there are no retries.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct RetryPolicy {
+    retries: u32,
+    backoff: Duration,
+}
+
+/// The retries set on the connect options of a driver, which take precedence over the
+/// parameters of the connection URL.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub(crate) struct RetryOptions {
+    pub(crate) retries: Option<u32>,
+    pub(crate) backoff: Option<Duration>,
+}
+
+impl RetryPolicy {
+    pub(crate) fn new(url: &Url, options: RetryOptions) -> crate::Result<Self> {
+        let policy = Self::from_url(url)?;
+
+        Ok(Self {
+            retries: options.retries.unwrap_or(policy.retries),
+            backoff: options.backoff.unwrap_or(policy.backoff),
+        })
+    }
+
+    pub(crate) fn from_url(url: &Url) -> crate::Result<Self> {
+        let retries = match url.param("connect_retries") {
+            Some(value) => value
+                .parse()
+                .map_err(|_| invalid_param("connect_retries", &value))?,
+            None => 0,
+        };
+
+        let backoff = match url.param("connect_retry_backoff") {
+            Some(value) => Duration::from_millis(
+                value
+                    .parse()
+                    .map_err(|_| invalid_param("connect_retry_backoff", &value))?,
+            ),
+
+            None => Duration::from_millis(100),
+        };
+
+        Ok(Self { retries, backoff })
+    }
+
+    /// Run `connect` until it succeeds, fails with an error that is not transient or we run out
+    /// of retries; then, the error reports how many attempts were made.
+    pub(crate) async fn run<T, F, Fut>(&self, mut connect: F) -> crate::Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = crate::Result<T>>,
+    {
+        let mut attempt = 0;
+
+        loop {
+            attempt += 1;
+
+            match connect().await {
+                Err(error) if is_transient(&error) && self.retries > 0 => {
+                    if attempt > self.retries {
+                        return Err(Error::ConnectRetriesExhausted {
+                            attempts: attempt,
+                            error: Box::new(error),
+                        });
+                    }
+
+                    log::debug!(
+                        "connection attempt {} of {} failed, retrying: {}",
+                        attempt,
+                        self.retries + 1,
+                        error
+                    );
+
+                    crate::runtime::sleep(self.delay(attempt)).await;
+                }
+
+                result => return result,
+            }
+        }
+    }
+
+    // The delay after the given (failed) attempt, with a random jitter of up to half of it
+    // so that many clients started at once do not all retry in lockstep
+    fn delay(&self, attempt: u32) -> Duration {
+        let delay = self.backoff * 2_u32.pow((attempt - 1).min(MAX_BACKOFF_DOUBLINGS));
+
+        delay / 2 + delay.mul_f64(rand::thread_rng().gen_range(0.0, 0.5))
+    }
+}
+
+/// Returns `true` if connecting again a little later may well succeed.
+///
+/// Authentication and TLS errors are never transient, and neither is a host name that does
+/// not resolve to any address.
+pub(crate) fn is_transient(error: &Error) -> bool {
+    match error {
+        Error::Io(error) => match error.kind() {
+            io::ErrorKind::ConnectionRefused
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::TimedOut => true,
+
+            _ => error
+                .get_ref()
+                .and_then(|error| error.downcast_ref::<ResolveError>())
+                .is_some_and(ResolveError::is_temporary),
+        },
+
+        // [postgres] the database system is starting up (or in recovery)
+        Error::Database(error) => error.code() == Some("57P03"),
+
+        _ => false,
+    }
+}
+
+/// The host name of the server could not be resolved.
+#[derive(Debug)]
+pub(crate) struct ResolveError(io::Error);
+
+// What the C library describes `EAI_AGAIN` as: glibc and the BSDs, then musl. Without a call
+// to `setlocale`, as in any Rust program that does not make one itself, these are not translated
+#[cfg(unix)]
+const EAI_AGAIN_MESSAGES: &[&str] = &["Temporary failure in name resolution", "Try again"];
+
+// `WSATRY_AGAIN`
+#[cfg(windows)]
+const WSATRY_AGAIN: i32 = 11002;
+
+impl ResolveError {
+    pub(crate) fn wrap(error: io::Error) -> io::Error {
+        io::Error::new(error.kind(), ResolveError(error))
+    }
+
+    // Whether the name server could not be reached or did not answer in time (`EAI_AGAIN`), as
+    // opposed to the host not existing (`EAI_NONAME`) or not having an address (`EAI_NODATA`)
+    fn is_temporary(&self) -> bool {
+        // std reports a failure of `getaddrinfo` by its message alone
+        #[cfg(unix)]
+        {
+            let message = self.0.to_string();
+
+            EAI_AGAIN_MESSAGES
+                .iter()
+                .any(|eai_again| message.ends_with(eai_again))
+        }
+
+        #[cfg(windows)]
+        {
+            self.0.raw_os_error() == Some(WSATRY_AGAIN)
+        }
+
+        #[cfg(not(any(unix, windows)))]
+        {
+            false
+        }
+    }
+}
+
+impl Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl StdError for ResolveError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(&self.0)
+    }
+}
+
+fn invalid_param(name: &str, value: &str) -> Error {
+    Error::Io(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!(
+            "invalid value for `{}` in connection URL: {:?}",
+            name, value
+        ),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryInto;
+
+    use super::*;
+
+    #[test]
+    fn it_reads_the_policy_from_the_url() {
+        let url: Url = "postgres://localhost/db?connect_retries=5&connect_retry_backoff=250"
+            .try_into()
+            .unwrap();
+
+        assert_eq!(
+            RetryPolicy::from_url(&url).unwrap(),
+            RetryPolicy {
+                retries: 5,
+                backoff: Duration::from_millis(250)
+            }
+        );
+
+        let url: Url = "postgres://localhost/db?connect_retries=many"
+            .try_into()
+            .unwrap();
+
+        assert!(RetryPolicy::from_url(&url).is_err());
+    }
+
+    #[test]
+    fn it_backs_off_with_jitter() {
+        let policy = RetryPolicy {
+            retries: 10,
+            backoff: Duration::from_millis(100),
+        };
+
+        for &(attempt, max) in &[(1, 100), (2, 200), (3, 400), (6, 3200), (10, 3200)] {
+            let delay = policy.delay(attempt);
+            let max = Duration::from_millis(max);
+
+            assert!(delay >= max / 2 && delay <= max, "{:?}", delay);
+        }
+    }
+
+    #[test]
+    fn it_lets_the_options_override_the_url() {
+        let url: Url = "postgres://localhost/db?connect_retries=5&connect_retry_backoff=250"
+            .try_into()
+            .unwrap();
+
+        let options = RetryOptions {
+            retries: Some(2),
+            backoff: None,
+        };
+
+        assert_eq!(
+            RetryPolicy::new(&url, options).unwrap(),
+            RetryPolicy {
+                retries: 2,
+                backoff: Duration::from_millis(250)
+            }
+        );
+
+        assert_eq!(
+            RetryPolicy::new(&url, RetryOptions::default()).unwrap(),
+            RetryPolicy::from_url(&url).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_only_retries_transient_errors() {
+        assert!(is_transient(&io::ErrorKind::ConnectionRefused.into()));
+        assert!(is_transient(&io::ErrorKind::TimedOut.into()));
+
+        assert!(!is_transient(&io::ErrorKind::PermissionDenied.into()));
+        assert!(!is_transient(&Error::Io(io::Error::new(
+            io::ErrorKind::Other,
+            "Temporary failure in name resolution",
+        ))));
+        assert!(!is_transient(&Error::Tls("certificate has expired".into())));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn it_only_retries_temporary_failures_to_resolve_the_host() {
+        let resolve_error = |message: &str| {
+            Error::Io(ResolveError::wrap(io::Error::new(
+                io::ErrorKind::Other,
+                format!("failed to lookup address information: {}", message),
+            )))
+        };
+
+        assert!(is_transient(&resolve_error(
+            "Temporary failure in name resolution"
+        )));
+        assert!(is_transient(&resolve_error("Try again")));
+
+        assert!(!is_transient(&resolve_error("Name or service not known")));
+        assert!(!is_transient(&resolve_error(
+            "nodename nor servname provided, or not known"
+        )));
+        assert!(!is_transient(&resolve_error("Name does not resolve")));
+    }
+}
@@ -1,8 +1,12 @@
 //! Contains the `ColumnIndex`, `Row`, and `FromRow` traits.
 
+use std::sync::Arc;
+
 use crate::database::Database;
 use crate::decode::Decode;
-use crate::types::{Type, TypeInfo};
+use crate::error::UnexpectedNullError;
+use crate::statement::Statement;
+use crate::types::Type;
 use crate::value::{HasRawValue, RawValue};
 
 /// A type that can be used to index into a [`Row`].
@@ -31,7 +35,7 @@ where
 
 impl<'c, R, I> ColumnIndex<'c, R> for &'_ I
 where
-    R: Row<'c>,
+    R: Row<'c> + ?Sized,
     I: ColumnIndex<'c, R> + ?Sized,
 {
     #[inline]
@@ -75,6 +79,11 @@ where
     /// Returns the number of columns in this row.
     fn len(&self) -> usize;
 
+    /// Returns the [`Statement`] this row was returned by, which is shared by all of its rows.
+    ///
+    /// [`Statement`]: crate::statement::Statement
+    fn statement(&self) -> &Arc<Statement<Self::Database>>;
+
     /// Index into the database row and decode a single value.
     ///
     /// A string index can be used to access a column by name and a `usize` index
@@ -134,9 +143,13 @@ where
     /// # Errors
     ///  * [`ColumnNotFound`] if the column by the given name was not found.
     ///  * [`ColumnIndexOutOfBounds`] if the `usize` index was greater than the number of columns in the row.
-    ///  * [`Decode`] if the value could not be decoded into the requested type.
+    ///  * [`ColumnDecode`] if the value could not be decoded into the requested type.
+    ///  * [`Decode`] with an [`UnexpectedNullError`] if the value was `NULL` but the requested
+    ///    type is not an `Option`.
     ///
+    /// [`ColumnDecode`]: crate::Error::ColumnDecode
     /// [`Decode`]: crate::Error::Decode
+    /// [`UnexpectedNullError`]: crate::error::UnexpectedNullError
     /// [`ColumnNotFound`]: crate::Error::ColumnNotFound
     /// [`ColumnIndexOutOfBounds`]: crate::Error::ColumnIndexOutOfBounds
     fn try_get<T, I>(&self, index: I) -> crate::Result<T>
@@ -145,21 +158,23 @@ where
         I: ColumnIndex<'c, Self>,
         T: Decode<'c, Self::Database>,
     {
-        let value = self.try_get_raw(index)?;
+        let value = self.try_get_raw(&index)?;
 
         if let Some(expected_ty) = value.type_info() {
             // NOTE: If there is no type, the value is NULL. This is fine. If the user tries
             //       to get this into a non-Option we catch that elsewhere and report as
             //       UnexpectedNullError.
 
-            if !expected_ty.compatible(&T::type_info()) {
-                return Err(crate::Error::mismatched_types::<Self::Database, T>(
-                    expected_ty,
+            if !T::compatible(&expected_ty) {
+                return Err(column_decode_error(
+                    self,
+                    &index,
+                    crate::Error::mismatched_types::<Self::Database, T>(expected_ty),
                 ));
             }
         }
 
-        T::decode(value)
+        T::decode(value).map_err(|error| column_decode_error(self, &index, error))
     }
 
     /// Index into the database row and decode a single value.
@@ -175,7 +190,9 @@ where
         I: ColumnIndex<'c, Self>,
         T: Decode<'c, Self::Database>,
     {
-        self.try_get_raw(index).and_then(T::decode)
+        self.try_get_raw(&index)
+            .and_then(T::decode)
+            .map_err(|error| column_decode_error(self, &index, error))
     }
 
     #[doc(hidden)]
@@ -187,6 +204,41 @@ where
         I: ColumnIndex<'c, Self>;
 }
 
+// Names the column and the statement a value could not be decoded from in [error]
+fn column_decode_error<'c, R, I>(row: &R, index: &I, error: crate::Error) -> crate::Error
+where
+    R: Row<'c> + ?Sized,
+    I: ColumnIndex<'c, R>,
+{
+    let source = match error {
+        // this is left as it is, to be turned into `None` for an `Option`
+        crate::Error::Decode(source) if source.is::<UnexpectedNullError>() => {
+            return crate::Error::Decode(source);
+        }
+
+        crate::Error::Decode(source) => source,
+
+        error => return error,
+    };
+
+    let statement = row.statement();
+    let index = match index.index(row) {
+        Ok(index) => index,
+        Err(error) => return error,
+    };
+
+    let index = match statement.columns().get(index).and_then(|c| c.name.as_ref()) {
+        Some(name) => format!("{:?}", name),
+        None => index.to_string(),
+    };
+
+    crate::Error::ColumnDecode {
+        index: index.into_boxed_str(),
+        source,
+        sql: Some(statement.sql().into()),
+    }
+}
+
 // Prevent users from implementing the `Row` trait.
 pub(crate) mod private_row {
     pub trait Sealed {}
@@ -224,8 +276,41 @@ pub trait HasRow<'c> {
 /// }
 /// ```
 ///
+/// ### Field attributes
+/// * `#[sqlx(rename = "name")]` reads the field from the column `name` instead.
+/// * `#[sqlx(try_from = "T")]` decodes the column as a `T` and converts that into the field with
+///   [`TryFrom`]; e.g., an `INT2` column into an enum that implements `TryFrom<i16>`.
+/// * `#[sqlx(with = "module")]` decodes the column as a `module::Value` and converts that into the
+///   field with `module::from_row_value`.
+///
+/// ```rust,ignore
+/// #[derive(sqlx::FromRow)]
+/// struct Link {
+///     #[sqlx(try_from = "i16")]
+///     kind: LinkKind,
+///
+///     #[sqlx(with = "url_from_text")]
+///     href: Url,
+/// }
+///
+/// mod url_from_text {
+///     // the type of the column has to be known up front, as the derived implementation
+///     // works with any database
+///     pub type Value = String;
+///
+///     pub fn from_row_value(value: String) -> Result<Url, url::ParseError> {
+///         value.parse()
+///     }
+/// }
+/// ```
+///
+/// If the conversion fails, its error is returned as an [`Error::ColumnDecode`] that names the
+/// column (and the field, if it is renamed).
+///
 /// [`query_as`]: crate::query_as
 /// [`Row::try_get`]: crate::row::Row::try_get
+/// [`TryFrom`]: std::convert::TryFrom
+/// [`Error::ColumnDecode`]: crate::Error::ColumnDecode
 pub trait FromRow<'c, R>
 where
     Self: Sized,
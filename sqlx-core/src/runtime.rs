@@ -32,3 +32,23 @@ pub(crate) use tokio::{
 
 #[cfg(all(feature = "runtime-tokio", feature = "postgres", unix))]
 pub(crate) use tokio::net::UnixStream;
+
+// Resolves the host name of a server, separately from connecting to it so that a failure to
+// resolve it can be told apart
+#[cfg(feature = "runtime-async-std")]
+pub(crate) async fn lookup_host(
+    host: &str,
+    port: u16,
+) -> std::io::Result<Vec<std::net::SocketAddr>> {
+    use async_std::net::ToSocketAddrs;
+
+    Ok((host, port).to_socket_addrs().await?.collect())
+}
+
+#[cfg(feature = "runtime-tokio")]
+pub(crate) async fn lookup_host(
+    host: &str,
+    port: u16,
+) -> std::io::Result<Vec<std::net::SocketAddr>> {
+    Ok(tokio::net::lookup_host((host, port)).await?.collect())
+}
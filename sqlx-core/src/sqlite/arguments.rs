@@ -1,6 +1,7 @@
 use core::ffi::c_void;
 use core::mem;
 
+use std::error::Error as StdError;
 use std::os::raw::{c_char, c_int};
 
 use libsqlite3_sys::{
@@ -36,6 +37,9 @@ pub enum SqliteArgumentValue {
 pub struct SqliteArguments {
     index: usize,
     values: Vec<SqliteArgumentValue>,
+
+    // The first bind value that cannot be encoded; the query fails with it before being run
+    pub(super) error: Option<Box<dyn StdError + Send + Sync>>,
 }
 
 impl SqliteArguments {
@@ -63,6 +67,10 @@ impl Arguments for SqliteArguments {
     where
         T: Encode<Self::Database> + Type<Self::Database>,
     {
+        if let Err(error) = value.check() {
+            self.error.get_or_insert(error);
+        }
+
         if let IsNull::Yes = value.encode_nullable(&mut self.values) {
             self.values.push(SqliteArgumentValue::Null);
         }
@@ -0,0 +1,123 @@
+use core::slice;
+
+use std::cmp::Ordering;
+use std::ffi::CString;
+use std::os::raw::{c_int, c_void};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Arc;
+
+use libsqlite3_sys::{sqlite3, sqlite3_create_collation_v2, SQLITE_OK, SQLITE_UTF8};
+
+use crate::sqlite::SqliteError;
+
+type CollateFn = Arc<dyn Fn(&str, &str) -> Ordering + Send + Sync + 'static>;
+
+/// A user-defined collating sequence that can be attached to a connection.
+#[derive(Clone)]
+pub(crate) struct Collation {
+    name: Arc<str>,
+    collate: CollateFn,
+}
+
+impl Collation {
+    pub(crate) fn new<N, F>(name: N, collate: F) -> Self
+    where
+        N: Into<Arc<str>>,
+        F: Fn(&str, &str) -> Ordering + Send + Sync + 'static,
+    {
+        Self {
+            name: name.into(),
+            collate: Arc::new(collate),
+        }
+    }
+
+    pub(crate) fn create(&self, handle: *mut sqlite3) -> crate::Result<()> {
+        let name = match CString::new(&*self.name) {
+            Ok(name) => name,
+            Err(_) => {
+                return Err(protocol_err!(
+                    "collation name {:?} contains an interior NUL byte",
+                    &*self.name
+                )
+                .into());
+            }
+        };
+
+        // SQLite owns this pointer from here on and hands it back to [free_collation] when
+        // the collation is replaced or the connection is closed
+        let data = Box::into_raw(Box::new(Arc::clone(&self.collate)));
+
+        // https://www.sqlite.org/c3ref/create_collation.html
+        let status = unsafe {
+            sqlite3_create_collation_v2(
+                handle,
+                name.as_ptr(),
+                // SQLite converts both operands to UTF-8 before invoking the callback, even if
+                // the database itself is encoded as UTF-16
+                SQLITE_UTF8,
+                data as *mut c_void,
+                Some(compare),
+                Some(free_collation),
+            )
+        };
+
+        if status != SQLITE_OK {
+            // The destructor is not invoked if sqlite3_create_collation_v2() fails
+            // https://www.sqlite.org/c3ref/create_collation.html
+            unsafe {
+                drop(Box::from_raw(data));
+            }
+
+            return Err(SqliteError::from_connection(handle).into());
+        }
+
+        Ok(())
+    }
+}
+
+extern "C" fn compare(
+    data: *mut c_void,
+    left_len: c_int,
+    left_ptr: *const c_void,
+    right_len: c_int,
+    right_ptr: *const c_void,
+) -> c_int {
+    // A panic must not unwind across the FFI boundary. There is no way to report an error from
+    // a collating function so we log the panic and treat the operands as equal.
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        let collate = unsafe { &*(data as *const CollateFn) };
+
+        let left = unsafe { bytes(left_ptr, left_len) };
+        let right = unsafe { bytes(right_ptr, right_len) };
+
+        collate(
+            &String::from_utf8_lossy(left),
+            &String::from_utf8_lossy(right),
+        )
+    }));
+
+    match result {
+        Ok(Ordering::Less) => -1,
+        Ok(Ordering::Equal) => 0,
+        Ok(Ordering::Greater) => 1,
+
+        Err(_) => {
+            log::error!("collating function panicked; treating operands as equal");
+            0
+        }
+    }
+}
+
+unsafe fn bytes<'a>(ptr: *const c_void, len: c_int) -> &'a [u8] {
+    if ptr.is_null() || len <= 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(ptr as *const u8, len as usize)
+    }
+}
+
+extern "C" fn free_collation(data: *mut c_void) {
+    unsafe {
+        drop(Box::from_raw(data as *mut CollateFn));
+    }
+}
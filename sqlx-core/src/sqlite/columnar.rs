@@ -0,0 +1,139 @@
+use futures_core::future::BoxFuture;
+
+use crate::columnar::{ColumnBuilder, Columnar, ColumnarResult, ColumnarResultBuilder};
+use crate::cursor::Either;
+use crate::row::Row;
+use crate::sqlite::cursor::{self, SqliteCursor};
+use crate::sqlite::type_info::SqliteType;
+use crate::sqlite::value::SqliteValue;
+use crate::sqlite::Sqlite;
+
+impl Columnar for Sqlite {
+    fn fetch_columnar<'c, 'q>(
+        mut cursor: SqliteCursor<'c, 'q>,
+    ) -> BoxFuture<'c, crate::Result<ColumnarResult>>
+    where
+        'q: 'c,
+    {
+        Box::pin(async move {
+            let mut result = ColumnarResultBuilder::new();
+
+            while let Some(next) = cursor::next(&mut cursor, false).await? {
+                let row = match next {
+                    Either::Right(row) => row,
+                    Either::Left(_) => continue,
+                };
+
+                // A row is only a position in the statement; the values are read from it
+                // directly, by index
+                let statement = row.raw_statement();
+
+                let columns = result.push_row(row.len(), || {
+                    (0..row.len())
+                        .map(|index| Some(statement.column_name(index).to_owned()))
+                        .collect()
+                })?;
+
+                for (index, column) in columns.iter_mut().enumerate() {
+                    let value = SqliteValue {
+                        statement,
+                        index: index as i32,
+                    };
+
+                    push_value(value, column)?;
+                }
+            }
+
+            Ok(result.finish())
+        })
+    }
+}
+
+fn push_value(value: SqliteValue<'_>, column: &mut ColumnBuilder) -> crate::Result<()> {
+    // SQLite is dynamically typed so we go by the storage class of each value
+    match value.r#type() {
+        None => {
+            column.push_null();
+            Ok(())
+        }
+
+        Some(SqliteType::Integer) | Some(SqliteType::Boolean) => column.push_int(value.int64()),
+        Some(SqliteType::Float) => column.push_float(value.double()),
+        Some(SqliteType::Text) => column.push_text(value.text().unwrap_or_default()),
+        Some(SqliteType::Blob) | Some(SqliteType::Any) => column.push_bytes(value.blob()),
+    }
+}
+
+#[cfg(all(feature = "bench", feature = "runtime-async-std"))]
+mod bench {
+    use crate::columnar::ColumnValues;
+    use crate::connection::Connect;
+    use crate::cursor::Cursor;
+    use crate::executor::Executor;
+    use crate::row::Row;
+    use crate::sqlite::SqliteConnection;
+
+    // A private, temporary database with 1M rows and 5 columns
+    // (two integers, a float, a nullable integer and text)
+    async fn setup() -> SqliteConnection {
+        let mut conn = SqliteConnection::connect("sqlite://").await.unwrap();
+
+        conn.execute(
+            r#"
+CREATE TABLE bench (a INTEGER, b INTEGER, c REAL, d INTEGER, e TEXT);
+
+WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 1000000)
+INSERT INTO bench SELECT i, i * 2, i / 3.0, CASE WHEN i % 10 = 0 THEN NULL ELSE i END, 'row ' || i FROM n;
+            "#,
+        )
+        .await
+        .unwrap();
+
+        conn
+    }
+
+    #[bench]
+    fn bench_fetch_row_by_row_and_transpose(b: &mut test::Bencher) {
+        let mut conn = async_std::task::block_on(setup());
+
+        b.iter(|| {
+            async_std::task::block_on(async {
+                let mut a = Vec::new();
+                let mut b = Vec::new();
+                let mut c = Vec::new();
+                let mut d = Vec::new();
+                let mut e = Vec::new();
+
+                let mut cursor = conn.fetch("SELECT a, b, c, d, e FROM bench");
+
+                while let Some(row) = cursor.next().await.unwrap() {
+                    a.push(row.get::<i64, _>(0));
+                    b.push(row.get::<i64, _>(1));
+                    c.push(row.get::<f64, _>(2));
+                    d.push(row.get::<Option<i64>, _>(3));
+                    e.push(row.get::<Option<String>, _>(4));
+                }
+
+                (a, b, c, d, e)
+            })
+        });
+    }
+
+    #[bench]
+    fn bench_fetch_columnar(b: &mut test::Bencher) {
+        let mut conn = async_std::task::block_on(setup());
+
+        b.iter(|| {
+            async_std::task::block_on(async {
+                let result = crate::query::query("SELECT a, b, c, d, e FROM bench")
+                    .fetch_columnar(&mut conn)
+                    .await
+                    .unwrap();
+
+                assert!(matches!(result.columns()[0].values(), ColumnValues::Int(_)));
+
+                result
+            })
+        });
+    }
+}
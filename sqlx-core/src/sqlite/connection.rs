@@ -1,5 +1,6 @@
 use core::ptr::{null, null_mut, NonNull};
 
+use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::convert::TryInto;
 use std::ffi::CString;
@@ -7,16 +8,24 @@ use std::ffi::CString;
 use futures_core::future::BoxFuture;
 use futures_util::future;
 use libsqlite3_sys::{
-    sqlite3, sqlite3_close, sqlite3_extended_result_codes, sqlite3_open_v2, SQLITE_OK,
-    SQLITE_OPEN_CREATE, SQLITE_OPEN_NOMUTEX, SQLITE_OPEN_READWRITE, SQLITE_OPEN_SHAREDCACHE,
+    sqlite3, sqlite3_close, sqlite3_column_int64, sqlite3_exec, sqlite3_extended_result_codes,
+    sqlite3_finalize, sqlite3_interrupt, sqlite3_open_v2, sqlite3_prepare_v2, sqlite3_step,
+    SQLITE_OK, SQLITE_OPEN_CREATE, SQLITE_OPEN_NOMUTEX, SQLITE_OPEN_READWRITE,
+    SQLITE_OPEN_SHAREDCACHE, SQLITE_ROW,
 };
 
-use crate::connection::{Connect, Connection};
+use crate::connection::{Connect, Connection, StatementCacheMetrics};
+use crate::cursor::Cursor;
 use crate::executor::Executor;
+use crate::logging::LogSettings;
+use crate::row::Row;
+use crate::sqlite::collation::Collation;
 use crate::sqlite::statement::Statement;
 use crate::sqlite::worker::Worker;
+use crate::statement::SqlRedaction;
+use crate::transaction::Transaction;
 
-use crate::sqlite::SqliteError;
+use crate::sqlite::{Sqlite, SqliteError};
 use crate::url::Url;
 
 /// Thin wrapper around [sqlite3] to impl `Send`.
@@ -32,6 +41,13 @@ pub struct SqliteConnection {
     // Storage of persistent statements
     pub(super) statements: Vec<Statement>,
     pub(super) statement_by_query: HashMap<String, usize>,
+    // Hits and misses of [statement_by_query]
+    pub(super) cache_metrics: StatementCacheMetrics,
+    pub(super) log_settings: LogSettings,
+    pub(super) sql_redaction: SqlRedaction,
+    // The value of `PRAGMA data_version` when the connection was established, which the pool
+    // compares against when checking for staleness
+    pub(super) initial_data_version: i64,
 }
 
 // A SQLite3 handle is safe to send between threads, provided not more than
@@ -52,8 +68,8 @@ async fn establish(url: Result<Url, url::ParseError>) -> crate::Result<SqliteCon
     // TODO: Handle the error when there are internal NULs in the database URL
     let filename = CString::new(url?.path_decoded().to_string()).unwrap();
 
-    let handle = worker
-        .run(move || -> crate::Result<SqliteConnectionHandle> {
+    let (handle, initial_data_version) = worker
+        .run(move || -> crate::Result<(SqliteConnectionHandle, i64)> {
             let mut handle = null_mut();
 
             // [SQLITE_OPEN_NOMUTEX] will instruct [sqlite3_open_v2] to return an error if it
@@ -87,7 +103,20 @@ async fn establish(url: Result<Url, url::ParseError>) -> crate::Result<SqliteCon
                 sqlite3_extended_result_codes(handle, 1);
             }
 
-            Ok(SqliteConnectionHandle(NonNull::new(handle).unwrap()))
+            let data_version = unsafe { query_data_version(handle) };
+
+            if let Err(error) = data_version {
+                unsafe {
+                    let _ = sqlite3_close(handle);
+                }
+
+                return Err(error);
+            }
+
+            Ok((
+                SqliteConnectionHandle(NonNull::new(handle).unwrap()),
+                data_version?,
+            ))
         })
         .await?;
 
@@ -97,14 +126,92 @@ async fn establish(url: Result<Url, url::ParseError>) -> crate::Result<SqliteCon
         statement: None,
         statements: Vec::with_capacity(10),
         statement_by_query: HashMap::with_capacity(10),
+        cache_metrics: StatementCacheMetrics::default(),
+        log_settings: LogSettings::default(),
+        sql_redaction: SqlRedaction::default(),
+        initial_data_version,
     })
 }
 
+// Reads `PRAGMA data_version` on a newly opened [handle], before it is wrapped in a
+// [SqliteConnection] (and any of its statements would be cached or logged)
+unsafe fn query_data_version(handle: *mut sqlite3) -> crate::Result<i64> {
+    let mut statement = null_mut();
+
+    // <https://www.sqlite.org/c3ref/prepare.html>
+    let status = sqlite3_prepare_v2(
+        handle,
+        b"PRAGMA data_version\0".as_ptr() as *const _,
+        -1,
+        &mut statement,
+        null_mut(),
+    );
+
+    if status != SQLITE_OK {
+        return Err(SqliteError::from_connection(handle).into());
+    }
+
+    let version = if sqlite3_step(statement) == SQLITE_ROW {
+        Ok(sqlite3_column_int64(statement, 0))
+    } else {
+        Err(SqliteError::from_connection(handle).into())
+    };
+
+    let _ = sqlite3_finalize(statement);
+
+    version
+}
+
 impl SqliteConnection {
     #[inline]
     pub(super) fn handle(&mut self) -> *mut sqlite3 {
         self.handle.0.as_ptr()
     }
+
+    // Interrupts the statement that is being stepped on this connection; see `CancelHandle`
+    pub(super) fn cancel_request(&self) -> BoxFuture<'static, crate::Result<()>> {
+        let handle = self.handle;
+
+        Box::pin(async move {
+            // This is safe to call from any thread while the connection is open, which it is
+            // for as long as the cursor that awaits this borrows it
+            unsafe { sqlite3_interrupt(handle.0.as_ptr()) };
+
+            Ok(())
+        })
+    }
+
+    /// Returns the current value of `PRAGMA data_version`.
+    ///
+    /// The value changes whenever _another_ connection commits a change to the database; it
+    /// is a cheap way to detect external writes (e.g., by a different process).
+    ///
+    /// <https://www.sqlite.org/pragma.html#pragma_data_version>
+    pub async fn data_version(&mut self) -> crate::Result<i64> {
+        let mut cursor = self.fetch("PRAGMA data_version");
+
+        let version = match cursor.next().await? {
+            Some(row) => row.try_get(0)?,
+            None => return Err(crate::Error::RowNotFound),
+        };
+
+        // Step the statement to completion so it does not hold any locks
+        while cursor.next().await?.is_some() {}
+
+        Ok(version)
+    }
+
+    /// Register a collating sequence named `name` on this connection, replacing any existing
+    /// collation with the same name.
+    ///
+    /// To register a collation on every connection in a pool, use
+    /// [`SqliteConnectOptions::collation`](super::SqliteConnectOptions::collation).
+    pub fn create_collation<F>(&mut self, name: &str, collate: F) -> crate::Result<()>
+    where
+        F: Fn(&str, &str) -> Ordering + Send + Sync + 'static,
+    {
+        Collation::new(name, collate).create(self.handle())
+    }
 }
 
 impl Connect for SqliteConnection {
@@ -135,6 +242,10 @@ PRAGMA synchronous = NORMAL;
 }
 
 impl Connection for SqliteConnection {
+    fn begin(&mut self) -> BoxFuture<'_, crate::Result<Transaction<'_, Sqlite>>> {
+        Box::pin(Transaction::new(0, self))
+    }
+
     fn close(self) -> BoxFuture<'static, crate::Result<()>> {
         // All necessary behavior is handled on drop
         Box::pin(future::ok(()))
@@ -144,6 +255,52 @@ impl Connection for SqliteConnection {
         // For SQLite connections, PING does effectively nothing
         Box::pin(future::ok(()))
     }
+
+    fn statement_cache_metrics(&self) -> StatementCacheMetrics {
+        // There is no server to make round trips to
+        self.cache_metrics
+    }
+
+    fn reset_statement_cache_metrics(&mut self) {
+        self.cache_metrics = StatementCacheMetrics::default();
+    }
+
+    fn set_log_settings(&mut self, settings: LogSettings) {
+        self.log_settings = settings;
+    }
+
+    fn set_sql_redaction(&mut self, redaction: SqlRedaction) {
+        self.sql_redaction = redaction;
+    }
+
+    fn queue_rollback(&mut self, statement: &str) -> bool {
+        // There is nothing to wait on so this runs right away, from this thread, like [Drop]
+        // https://www.sqlite.org/c3ref/exec.html
+        let statement = match CString::new(statement) {
+            Ok(statement) => statement,
+            Err(_) => return false,
+        };
+
+        let status = unsafe {
+            sqlite3_exec(
+                self.handle(),
+                statement.as_ptr(),
+                None,
+                null_mut(),
+                null_mut(),
+            )
+        };
+
+        if status != SQLITE_OK {
+            let error = SqliteError::from_connection(self.handle());
+
+            log::warn!("failed to roll back a dropped transaction: {}", error);
+
+            return false;
+        }
+
+        true
+    }
 }
 
 impl Drop for SqliteConnection {
@@ -1,21 +1,37 @@
 use futures_core::future::BoxFuture;
+use futures_util::TryFutureExt;
 
+use crate::cancel::{next_cancellable, CancelHandle};
 use crate::connection::ConnectionSource;
-use crate::cursor::Cursor;
+use crate::cursor::{Cursor, Either};
 use crate::executor::Execute;
+use crate::logging::{Logged, QueryLog};
 use crate::pool::Pool;
 use crate::sqlite::statement::Step;
-use crate::sqlite::{Sqlite, SqliteArguments, SqliteConnection, SqliteRow};
+use crate::sqlite::{Sqlite, SqliteArguments, SqliteConnection, SqliteQueryResult, SqliteRow};
 
 pub struct SqliteCursor<'c, 'q> {
     pub(super) source: ConnectionSource<'c, SqliteConnection>,
     query: &'q str,
     arguments: Option<SqliteArguments>,
     pub(super) statement: Option<Option<usize>>,
+    // [sqlite3_total_changes] from before the current statement was run
+    total_changes: u64,
+    // the last statement of the query has finished
+    done: bool,
+    cancel_handle: Option<CancelHandle>,
+    log: QueryLog<'q>,
 }
 
 impl crate::cursor::private::Sealed for SqliteCursor<'_, '_> {}
 
+impl<'q> Logged<'q> for SqliteCursor<'_, 'q> {
+    fn logged(mut self, log: QueryLog<'q>) -> Self {
+        self.log = log;
+        self
+    }
+}
+
 impl<'c, 'q> Cursor<'c, 'q> for SqliteCursor<'c, 'q> {
     type Database = Sqlite;
 
@@ -25,13 +41,18 @@ impl<'c, 'q> Cursor<'c, 'q> for SqliteCursor<'c, 'q> {
         Self: Sized,
         E: Execute<'q, Sqlite>,
     {
+        let cancel_handle = query.cancel_handle().cloned();
         let (query, arguments) = query.into_parts();
 
         Self {
             source: ConnectionSource::Pool(pool.clone()),
             statement: None,
+            total_changes: 0,
+            done: false,
             query,
             arguments,
+            cancel_handle,
+            log: QueryLog::default(),
         }
     }
 
@@ -41,30 +62,81 @@ impl<'c, 'q> Cursor<'c, 'q> for SqliteCursor<'c, 'q> {
         Self: Sized,
         E: Execute<'q, Sqlite>,
     {
+        let cancel_handle = query.cancel_handle().cloned();
         let (query, arguments) = query.into_parts();
 
         Self {
             source: ConnectionSource::ConnectionRef(conn),
             statement: None,
+            total_changes: 0,
+            done: false,
             query,
             arguments,
+            cancel_handle,
+            log: QueryLog::default(),
         }
     }
 
     fn next(&mut self) -> BoxFuture<crate::Result<Option<SqliteRow<'_>>>> {
-        Box::pin(next(self))
+        Box::pin(next(self, false).map_ok(|result| result.and_then(Either::right)))
+    }
+
+    fn next_many(
+        &mut self,
+    ) -> BoxFuture<'_, crate::Result<Option<Either<SqliteQueryResult, SqliteRow<'_>>>>> {
+        Box::pin(next(self, true))
+    }
+}
+
+pub(super) async fn next<'a, 'c: 'a, 'q: 'a>(
+    cursor: &'a mut SqliteCursor<'c, 'q>,
+    many: bool,
+) -> crate::Result<Option<Either<SqliteQueryResult, SqliteRow<'a>>>> {
+    let handle = match &cursor.cancel_handle {
+        Some(handle) => handle.clone(),
+        None => return fetch_next(cursor, many).await,
+    };
+
+    let conn = cursor.source.resolve().await?;
+
+    if handle.is_cancelled() {
+        // Nothing is running between two steps, so there is nothing to interrupt; resetting
+        // the statement is enough for it to be done with
+        if let Some(key) = cursor.statement.take() {
+            conn.statement_mut(key).reset();
+        }
+
+        cursor.done = true;
+
+        return Err(crate::Error::Cancelled);
     }
+
+    let request = conn.cancel_request();
+
+    next_cancellable(&handle, fetch_next(cursor, many), request).await
 }
 
-async fn next<'a, 'c: 'a, 'q: 'a>(
+// With [many], the result of each statement is returned as well
+async fn fetch_next<'a, 'c: 'a, 'q: 'a>(
     cursor: &'a mut SqliteCursor<'c, 'q>,
-) -> crate::Result<Option<SqliteRow<'a>>> {
+    many: bool,
+) -> crate::Result<Option<Either<SqliteQueryResult, SqliteRow<'a>>>> {
+    cursor.log.start();
+
     let conn = cursor.source.resolve().await?;
 
+    if cursor.done {
+        cursor.log.finish();
+
+        return Ok(None);
+    }
+
     loop {
         if cursor.statement.is_none() {
             let key = conn.prepare(&mut cursor.query, cursor.arguments.is_some())?;
 
+            cursor.total_changes = conn.total_changes();
+
             if let Some(arguments) = &mut cursor.arguments {
                 conn.statement_mut(key).bind(arguments)?;
             }
@@ -79,20 +151,34 @@ async fn next<'a, 'c: 'a, 'q: 'a>(
 
         match step {
             Step::Row => {
-                return Ok(Some(SqliteRow {
+                return Ok(Some(Either::Right(SqliteRow {
                     values: statement.data_count(),
                     statement: key,
                     connection: conn,
-                }));
-            }
-
-            Step::Done if cursor.query.is_empty() => {
-                return Ok(None);
+                })));
             }
 
             Step::Done => {
                 cursor.statement = None;
-                // continue
+                cursor.done = cursor.query.is_empty();
+
+                if many {
+                    // [sqlite3_changes] is left as it was by any statement that is not an
+                    // INSERT, UPDATE or DELETE
+                    let rows_affected = if conn.total_changes() != cursor.total_changes {
+                        conn.changes()
+                    } else {
+                        0
+                    };
+
+                    return Ok(Some(Either::Left(SqliteQueryResult { rows_affected })));
+                }
+
+                if cursor.done {
+                    cursor.log.finish();
+
+                    return Ok(None);
+                }
             }
         }
     }
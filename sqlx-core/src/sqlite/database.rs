@@ -3,8 +3,8 @@ use crate::database::Database;
 use crate::row::HasRow;
 use crate::sqlite::error::SqliteError;
 use crate::sqlite::{
-    SqliteArgumentValue, SqliteArguments, SqliteConnection, SqliteCursor, SqliteRow,
-    SqliteTypeInfo, SqliteValue,
+    SqliteArgumentValue, SqliteArguments, SqliteConnection, SqliteCursor, SqliteQueryResult,
+    SqliteRow, SqliteTypeInfo, SqliteValue,
 };
 use crate::value::HasRawValue;
 
@@ -23,6 +23,8 @@ impl Database for Sqlite {
 
     type RawBuffer = Vec<SqliteArgumentValue>;
 
+    type QueryResult = SqliteQueryResult;
+
     type Error = SqliteError;
 }
 
@@ -1,13 +1,12 @@
 use futures_core::future::BoxFuture;
 
-use libsqlite3_sys::sqlite3_changes;
+use libsqlite3_sys::{sqlite3_changes, sqlite3_total_changes};
 
 use crate::cursor::Cursor;
 use crate::describe::{Column, Describe};
 use crate::executor::{Execute, Executor, RefExecutor};
 use crate::sqlite::cursor::SqliteCursor;
 use crate::sqlite::statement::{Statement, Step};
-use crate::sqlite::type_info::SqliteType;
 use crate::sqlite::{Sqlite, SqliteConnection, SqliteTypeInfo};
 
 impl SqliteConnection {
@@ -27,6 +26,8 @@ impl SqliteConnection {
         }
 
         if let Some(key) = self.statement_by_query.get(&**query) {
+            self.cache_metrics.hits += 1;
+
             let statement = &mut self.statements[*key];
 
             // Adjust the passed in query string as if [string3_prepare]
@@ -40,6 +41,8 @@ impl SqliteConnection {
             return Ok(Some(*key));
         }
 
+        self.cache_metrics.misses += 1;
+
         // Prepare a new statement object; ensuring to tell SQLite that this will be stored
         // for a "long" time and re-used multiple times
 
@@ -55,7 +58,7 @@ impl SqliteConnection {
     }
 
     // This is used for [affected_rows] in the public API.
-    fn changes(&mut self) -> u64 {
+    pub(super) fn changes(&mut self) -> u64 {
         // Returns the number of rows modified, inserted or deleted by the most recently
         // completed INSERT, UPDATE or DELETE statement.
 
@@ -64,6 +67,15 @@ impl SqliteConnection {
         changes as u64
     }
 
+    // Returns the number of rows modified, inserted or deleted by all INSERT, UPDATE or DELETE
+    // statements (including those run by triggers) since the connection was opened.
+
+    // https://www.sqlite.org/c3ref/total_changes.html
+    pub(super) fn total_changes(&mut self) -> u64 {
+        let changes = unsafe { sqlite3_total_changes(self.handle()) };
+        changes as u64
+    }
+
     #[inline]
     pub(super) fn statement(&self, key: Option<usize>) -> &Statement {
         match key {
@@ -91,7 +103,7 @@ impl Executor for SqliteConnection {
     where
         E: Execute<'q, Self::Database>,
     {
-        log_execution!(query, {
+        log_execution!(self.log_settings, query, {
             let (mut query, mut arguments) = query.into_parts();
 
             Box::pin(async move {
@@ -121,7 +133,9 @@ impl Executor for SqliteConnection {
     where
         E: Execute<'q, Self::Database>,
     {
-        log_execution!(query, { SqliteCursor::from_connection(self, query) })
+        log_execution!(self.log_settings, query, {
+            SqliteCursor::from_connection(self, query)
+        })
     }
 
     #[doc(hidden)]
@@ -137,44 +151,36 @@ impl Executor for SqliteConnection {
             let key = self.prepare(&mut query, false)?;
             let statement = self.statement_mut(key);
 
-            // First let's attempt to describe what we can about parameter types
-            // Which happens to just be the count, heh
+            // First let's attempt to describe what we can about parameters
+            // Which happens to just be the count and, for named parameters, the name
+            // Any value can be bound to any parameter
             let num_params = statement.params();
-            let params = vec![None; num_params].into_boxed_slice();
+            let params = vec![Some(SqliteTypeInfo::any()); num_params].into_boxed_slice();
+            let param_names = (0..num_params)
+                .map(|i| statement.param_name(i).map(Into::into))
+                .collect::<Vec<_>>()
+                .into_boxed_slice();
 
             // Next, collect (return) column types and names
             let num_columns = statement.column_count();
             let mut columns = Vec::with_capacity(num_columns);
             for i in 0..num_columns {
                 let name = statement.column_name(i).to_owned();
-                let decl = statement.column_decltype(i);
-
-                let r#type = match decl {
-                    None => None,
-                    Some(decl) => match &*decl.to_ascii_lowercase() {
-                        "bool" | "boolean" => Some(SqliteType::Boolean),
-                        "clob" | "text" => Some(SqliteType::Text),
-                        "blob" => Some(SqliteType::Blob),
-                        "real" | "double" | "double precision" | "float" => Some(SqliteType::Float),
-                        decl @ _ if decl.contains("int") => Some(SqliteType::Integer),
-                        decl @ _ if decl.contains("char") => Some(SqliteType::Text),
-                        _ => None,
-                    },
-                };
+                let type_info = statement
+                    .column_decltype(i)
+                    .and_then(SqliteTypeInfo::from_decltype);
 
                 columns.push(Column {
                     name: Some(name.into()),
                     non_null: statement.column_not_null(i)?,
                     table_id: None,
-                    type_info: r#type.map(|r#type| SqliteTypeInfo {
-                        r#type,
-                        affinity: None,
-                    }),
+                    type_info,
                 })
             }
 
             Ok(Describe {
                 param_types: params,
+                param_names,
                 result_columns: columns.into_boxed_slice(),
             })
         })
@@ -188,6 +194,8 @@ impl<'e> RefExecutor<'e> for &'e mut SqliteConnection {
     where
         E: Execute<'q, Self::Database>,
     {
-        log_execution!(query, { SqliteCursor::from_connection(self, query) })
+        log_execution!(self.log_settings, query, {
+            SqliteCursor::from_connection(self, query)
+        })
     }
 }
@@ -6,11 +6,15 @@
 #![allow(unsafe_code)]
 
 mod arguments;
+mod collation;
+mod columnar;
 mod connection;
 mod cursor;
 mod database;
 mod error;
 mod executor;
+mod options;
+mod query_result;
 mod row;
 mod statement;
 mod type_info;
@@ -23,6 +27,8 @@ pub use connection::SqliteConnection;
 pub use cursor::SqliteCursor;
 pub use database::Sqlite;
 pub use error::SqliteError;
+pub use options::SqliteConnectOptions;
+pub use query_result::SqliteQueryResult;
 pub use row::SqliteRow;
 pub use type_info::SqliteTypeInfo;
 pub use value::SqliteValue;
@@ -0,0 +1,134 @@
+use std::cmp::Ordering;
+use std::fmt::{self, Debug};
+
+use futures_core::future::BoxFuture;
+
+use crate::connection::Connect;
+use crate::pool::{Builder, Pool};
+use crate::sqlite::collation::Collation;
+use crate::sqlite::SqliteConnection;
+
+/// Options that are applied to every new [SqliteConnection] on top of those given in the URL.
+///
+/// ```rust,no_run
+/// # use sqlx_core::sqlite::SqliteConnectOptions;
+/// # async fn run() -> sqlx_core::Result<()> {
+/// let options = SqliteConnectOptions::new("sqlite://data.db")
+///     .collation("NOCASE_UNICODE", |a, b| a.to_lowercase().cmp(&b.to_lowercase()));
+///
+/// // a single connection
+/// let conn = options.connect().await?;
+///
+/// // or every connection opened by a pool
+/// let pool = sqlx_core::sqlite::SqlitePool::builder()
+///     .max_size(5)
+///     .build_with(options)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct SqliteConnectOptions {
+    url: String,
+    collations: Vec<Collation>,
+}
+
+impl SqliteConnectOptions {
+    /// Create options for connecting to the database at `url`.
+    ///
+    /// The URL syntax is the same as for [`SqliteConnection::connect`].
+    ///
+    /// [`SqliteConnection::connect`]: crate::connection::Connect::connect
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_owned(),
+            collations: Vec::new(),
+        }
+    }
+
+    /// Register a collating sequence named `name` on each new connection.
+    ///
+    /// Both operands are always given to `collate` as UTF-8, regardless of the encoding of
+    /// the database. A panic inside `collate` is caught and logged and the operands are then
+    /// treated as equal.
+    ///
+    /// <https://www.sqlite.org/datatype3.html#collation>
+    pub fn collation<N, F>(mut self, name: N, collate: F) -> Self
+    where
+        N: AsRef<str>,
+        F: Fn(&str, &str) -> Ordering + Send + Sync + 'static,
+    {
+        self.collations.push(Collation::new(name.as_ref(), collate));
+        self
+    }
+
+    /// Open a new connection with these options.
+    pub fn connect(&self) -> BoxFuture<'static, crate::Result<SqliteConnection>> {
+        self.connect_to(&self.url)
+    }
+
+    fn connect_to(&self, url: &str) -> BoxFuture<'static, crate::Result<SqliteConnection>> {
+        let connect = SqliteConnection::connect(url);
+        let collations = self.collations.clone();
+
+        Box::pin(async move {
+            let mut conn = connect.await?;
+
+            for collation in &collations {
+                collation.create(conn.handle())?;
+            }
+
+            Ok(conn)
+        })
+    }
+}
+
+impl Debug for SqliteConnectOptions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SqliteConnectOptions")
+            .field("url", &self.url)
+            .field("collations", &self.collations.len())
+            .finish()
+    }
+}
+
+impl Builder<SqliteConnection> {
+    /// Close idle connections instead of reusing them once [`data_version`] has advanced by at
+    /// least `threshold` since that connection was established.
+    ///
+    /// This is opt-in as every acquire of an idle connection will query `PRAGMA data_version`.
+    /// It is useful when another process rewrites the database or its schema out from
+    /// under long-lived connections.
+    ///
+    /// # Panics
+    ///
+    /// If `threshold` is `0`, as every idle connection would then be closed instead of reused.
+    ///
+    /// [`data_version`]: crate::sqlite::SqliteConnection::data_version
+    pub fn recycle_after_data_version_changes(self, threshold: u64) -> Self {
+        assert!(
+            threshold > 0,
+            "recycle_after_data_version_changes: the threshold must be at least 1"
+        );
+
+        self.recycle_if(move |conn: &mut SqliteConnection| {
+            Box::pin(async move {
+                let version = conn.data_version().await?;
+
+                Ok(version.wrapping_sub(conn.initial_data_version) as u64 >= threshold)
+            })
+        })
+    }
+
+    /// Spin up the connection pool, opening every connection with the given options.
+    pub async fn build_with(
+        self,
+        options: SqliteConnectOptions,
+    ) -> crate::Result<Pool<SqliteConnection>> {
+        let url = options.url.clone();
+
+        self.connector(move |url| options.connect_to(url))
+            .build(&url)
+            .await
+    }
+}
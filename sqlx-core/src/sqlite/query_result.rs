@@ -0,0 +1,15 @@
+/// The result of a statement run by [`Executor::fetch_many`] on a **SQLite** connection.
+///
+/// [`Executor::fetch_many`]: crate::executor::Executor::fetch_many
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SqliteQueryResult {
+    pub(super) rows_affected: u64,
+}
+
+impl SqliteQueryResult {
+    /// Returns the number of rows inserted, updated or deleted by the statement, not counting
+    /// those changed by triggers.
+    pub fn rows_affected(&self) -> u64 {
+        self.rows_affected
+    }
+}
@@ -1,7 +1,10 @@
+use std::sync::Arc;
+
 use crate::row::{ColumnIndex, Row};
 use crate::sqlite::statement::Statement;
 use crate::sqlite::value::SqliteValue;
 use crate::sqlite::{Sqlite, SqliteConnection};
+use crate::statement::Statement as StatementInfo;
 
 pub struct SqliteRow<'c> {
     pub(super) values: usize,
@@ -20,7 +23,7 @@ unsafe impl Sync for SqliteRow<'_> {}
 
 impl<'c> SqliteRow<'c> {
     #[inline]
-    fn statement(&self) -> &'c Statement {
+    pub(super) fn raw_statement(&self) -> &'c Statement {
         self.connection.statement(self.statement)
     }
 }
@@ -33,13 +36,18 @@ impl<'c> Row<'c> for SqliteRow<'c> {
         self.values
     }
 
+    #[inline]
+    fn statement(&self) -> &Arc<StatementInfo<Sqlite>> {
+        &self.raw_statement().info
+    }
+
     #[doc(hidden)]
     fn try_get_raw<I>(&self, index: I) -> crate::Result<SqliteValue<'c>>
     where
         I: ColumnIndex<'c, Self>,
     {
         Ok(SqliteValue {
-            statement: self.statement(),
+            statement: self.raw_statement(),
             index: index.index(self)? as i32,
         })
     }
@@ -59,10 +67,11 @@ impl<'c> ColumnIndex<'c, SqliteRow<'c>> for usize {
 
 impl<'c> ColumnIndex<'c, SqliteRow<'c>> for str {
     fn index(&self, row: &SqliteRow<'c>) -> crate::Result<usize> {
-        row.statement()
-            .columns
+        row.raw_statement()
+            .info
+            .names
             .get(self)
+            .copied()
             .ok_or_else(|| crate::Error::ColumnNotFound((*self).into()))
-            .map(|&index| index as usize)
     }
 }
@@ -1,23 +1,31 @@
 #![allow(unsafe_code)]
 
 use core::ptr::{null, null_mut, NonNull};
-use std::collections::HashMap;
+use std::borrow::Cow;
 use std::ffi::CStr;
+use std::mem;
 use std::os::raw::{c_char, c_int};
 use std::ptr;
+use std::sync::Arc;
 
 use libsqlite3_sys::{
-    sqlite3_bind_parameter_count, sqlite3_clear_bindings, sqlite3_column_count,
-    sqlite3_column_database_name, sqlite3_column_decltype, sqlite3_column_name,
-    sqlite3_column_origin_name, sqlite3_column_table_name, sqlite3_data_count, sqlite3_finalize,
-    sqlite3_prepare_v3, sqlite3_reset, sqlite3_step, sqlite3_stmt, sqlite3_table_column_metadata,
-    SQLITE_DONE, SQLITE_OK, SQLITE_PREPARE_NO_VTAB, SQLITE_PREPARE_PERSISTENT, SQLITE_ROW,
+    sqlite3, sqlite3_bind_parameter_count, sqlite3_bind_parameter_name, sqlite3_clear_bindings,
+    sqlite3_column_count, sqlite3_column_database_name, sqlite3_column_decltype,
+    sqlite3_column_name, sqlite3_column_origin_name, sqlite3_column_table_name, sqlite3_data_count,
+    sqlite3_finalize, sqlite3_prepare_v3, sqlite3_reset, sqlite3_sql, sqlite3_step, sqlite3_stmt,
+    sqlite3_stmt_status, sqlite3_table_column_metadata, SQLITE_DONE, SQLITE_OK,
+    SQLITE_PREPARE_NO_VTAB, SQLITE_PREPARE_PERSISTENT, SQLITE_ROW, SQLITE_SCHEMA,
+    SQLITE_STMTSTATUS_REPREPARE,
 };
 
+use crate::describe::Column;
 use crate::sqlite::connection::SqliteConnectionHandle;
 use crate::sqlite::worker::Worker;
 use crate::sqlite::SqliteError;
-use crate::sqlite::{SqliteArguments, SqliteConnection};
+use crate::sqlite::{
+    Sqlite, SqliteArgumentValue, SqliteArguments, SqliteConnection, SqliteTypeInfo,
+};
+use crate::statement::{SqlRedaction, Statement as StatementInfo};
 
 /// Return values from [SqliteStatement::step].
 pub(super) enum Step {
@@ -41,7 +49,16 @@ pub(super) struct Statement {
     pub(super) connection: SqliteConnectionHandle,
     pub(super) worker: Worker,
     pub(super) tail: usize,
-    pub(super) columns: HashMap<String, usize>,
+    // What is exposed as [Row::statement], which also maps each column name to its position
+    pub(super) info: Arc<StatementInfo<Sqlite>>,
+    // How much of the SQL is kept in [info], as the connection had it when this was prepared
+    redaction: SqlRedaction,
+    flags: u32,
+    // The number of times SQLite has re-prepared this statement as of the last time we
+    // built [info]
+    reprepared: c_int,
+    // The values bound to the parameters, in order, to bind again if we re-prepare it
+    bindings: Vec<SqliteArgumentValue>,
 }
 
 // SQLite3 statement objects are safe to send between threads, but *not* safe
@@ -55,60 +72,95 @@ impl Statement {
         query: &mut &str,
         persistent: bool,
     ) -> crate::Result<Self> {
-        // TODO: Error on queries that are too large
-        let query_ptr = query.as_bytes().as_ptr() as *const c_char;
-        let query_len = query.len() as i32;
-        let mut statement_handle: *mut sqlite3_stmt = null_mut();
-        let mut flags = SQLITE_PREPARE_NO_VTAB;
-        let mut tail: *const c_char = null();
+        let mut flags = SQLITE_PREPARE_NO_VTAB as u32;
 
         if persistent {
             // SQLITE_PREPARE_PERSISTENT
             //  The SQLITE_PREPARE_PERSISTENT flag is a hint to the query
             //  planner that the prepared statement will be retained for a long time
             //  and probably reused many times.
-            flags |= SQLITE_PREPARE_PERSISTENT;
+            flags |= SQLITE_PREPARE_PERSISTENT as u32;
         }
 
-        // <https://www.sqlite.org/c3ref/prepare.html>
-        let status = unsafe {
-            sqlite3_prepare_v3(
-                conn.handle(),
-                query_ptr,
-                query_len,
-                flags as u32,
-                &mut statement_handle,
-                &mut tail,
-            )
-        };
-
-        if status != SQLITE_OK {
-            return Err(SqliteError::from_connection(conn.handle()).into());
-        }
+        let (handle, tail) = prepare(conn.handle(), query, flags)?;
 
-        // If pzTail is not NULL then *pzTail is made to point to the first byte
-        // past the end of the first SQL statement in zSql.
-        let tail = (tail as usize) - (query_ptr as usize);
         *query = &query[tail..].trim();
 
         let mut self_ = Self {
             worker: conn.worker.clone(),
             connection: conn.handle,
-            handle: NonNull::new(statement_handle).map(SqliteStatementHandle),
-            columns: HashMap::new(),
+            handle,
+            info: Arc::default(),
+            redaction: conn.sql_redaction,
             tail,
+            flags,
+            reprepared: 0,
+            bindings: Vec::new(),
         };
 
-        // Prepare a column hash map for use in pulling values from a column by name
-        let count = self_.column_count();
-        self_.columns.reserve(count);
+        self_.load_columns();
+
+        Ok(self_)
+    }
+
+    // Describe the columns, including a hash map for use in pulling values from a column by name
+    fn load_columns(&mut self) {
+        let count = self.column_count();
+        let mut columns = Vec::with_capacity(count);
 
         for i in 0..count {
-            let name = self_.column_name(i).to_owned();
-            self_.columns.insert(name, i);
+            let name = self.column_name(i).into();
+            let type_info = self
+                .column_decltype(i)
+                .and_then(SqliteTypeInfo::from_decltype);
+
+            columns.push(Column {
+                name: Some(name),
+                table_id: None,
+                type_info,
+                non_null: None,
+            });
         }
 
-        Ok(self_)
+        self.info = Arc::new(StatementInfo::new(&self.sql(), columns, self.redaction));
+
+        self.reprepared = unsafe {
+            self.handle().map_or(0, |handle| {
+                sqlite3_stmt_status(handle, SQLITE_STMTSTATUS_REPREPARE, 0)
+            })
+        };
+    }
+
+    // Compile this statement again from its original SQL, keeping any bound parameters.
+    fn reprepare(&mut self) -> crate::Result<()> {
+        let old = match self.handle {
+            Some(handle) => handle.0.as_ptr(),
+            None => return Ok(()),
+        };
+
+        let sql = unsafe { CStr::from_ptr(sqlite3_sql(old)) }
+            .to_str()
+            .map_err(|_| protocol_err!("the SQL of a statement to re-prepare is not valid UTF-8"))?
+            .to_owned();
+
+        let (handle, _) = prepare(self.connection.0.as_ptr(), &sql, self.flags)?;
+
+        unsafe {
+            let _ = sqlite3_finalize(old);
+        }
+
+        self.handle = handle;
+
+        let bindings = mem::take(&mut self.bindings);
+
+        for (index, value) in bindings.iter().enumerate() {
+            value.bind(self, index + 1)?;
+        }
+
+        self.bindings = bindings;
+        self.load_columns();
+
+        Ok(())
     }
 
     /// Returns a pointer to the raw C pointer backing this statement.
@@ -117,6 +169,17 @@ impl Statement {
         self.handle.map(|handle| handle.0.as_ptr())
     }
 
+    // The SQL this statement was compiled from
+    // That came from a `&str` but it is read lossily so this can never panic
+    fn sql(&self) -> Cow<'_, str> {
+        unsafe {
+            self.handle().map_or(Cow::Borrowed(""), |handle| {
+                // https://www.sqlite.org/c3ref/expanded_sql.html
+                CStr::from_ptr(sqlite3_sql(handle)).to_string_lossy()
+            })
+        }
+    }
+
     pub(super) fn data_count(&mut self) -> usize {
         // https://sqlite.org/c3ref/data_count.html
 
@@ -137,7 +200,7 @@ impl Statement {
         }
     }
 
-    pub(super) fn column_name(&mut self, index: usize) -> &str {
+    pub(super) fn column_name(&self, index: usize) -> &str {
         unsafe {
             self.handle()
                 .map(|handle| {
@@ -235,10 +298,36 @@ impl Statement {
         }
     }
 
+    // A name that is not valid UTF-8 has its invalid sequences replaced with U+FFFD
+    pub(super) fn param_name(&mut self, index: usize) -> Option<Cow<'_, str>> {
+        unsafe {
+            self.handle()
+                .and_then(|handle| {
+                    // https://www.sqlite.org/c3ref/bind_parameter_name.html
+                    // NOTE: parameters are indexed from 1 and a plain `?` has no name
+                    let ptr = sqlite3_bind_parameter_name(handle, (index + 1) as c_int);
+
+                    if ptr.is_null() {
+                        None
+                    } else {
+                        Some(CStr::from_ptr(ptr))
+                    }
+                })
+                .map(CStr::to_string_lossy)
+        }
+    }
+
     pub(super) fn bind(&mut self, arguments: &mut SqliteArguments) -> crate::Result<()> {
+        if let Some(error) = arguments.error.take() {
+            return Err(crate::Error::Encode(error));
+        }
+
+        self.bindings.clear();
+
         for index in 0..self.params() {
             if let Some(value) = arguments.next() {
                 value.bind(self, index + 1)?;
+                self.bindings.push(value);
             } else {
                 break;
             }
@@ -248,6 +337,8 @@ impl Statement {
     }
 
     pub(super) fn reset(&mut self) {
+        self.bindings.clear();
+
         let handle = unsafe {
             if let Some(handle) = self.handle() {
                 handle
@@ -271,12 +362,40 @@ impl Statement {
         // https://sqlite.org/c3ref/step.html
 
         if let Some(handle) = self.handle {
-            let status = unsafe {
+            let mut status = unsafe {
                 self.worker
                     .run(move || sqlite3_step(handle.0.as_ptr()))
                     .await
             };
 
+            // SQLITE_SCHEMA means the database schema changed underneath the statement
+            // and SQLite could not recompile it by itself; the documented response is to
+            // prepare the statement again and retry, so we do that _once_
+            // https://www.sqlite.org/rescode.html#schema
+            if status & 0xff == SQLITE_SCHEMA {
+                self.reprepare()?;
+
+                if let Some(handle) = self.handle {
+                    status = unsafe {
+                        self.worker
+                            .run(move || sqlite3_step(handle.0.as_ptr()))
+                            .await
+                    };
+                }
+            }
+
+            // Statements prepared with `sqlite3_prepare_v3` are usually re-prepared _by SQLite_
+            // when the schema changes; if that happened the columns may be different now
+            let reprepared = unsafe {
+                self.handle().map_or(0, |handle| {
+                    sqlite3_stmt_status(handle, SQLITE_STMTSTATUS_REPREPARE, 0)
+                })
+            };
+
+            if reprepared != self.reprepared {
+                self.load_columns();
+            }
+
             match status {
                 SQLITE_DONE => Ok(Step::Done),
 
@@ -293,6 +412,43 @@ impl Statement {
     }
 }
 
+fn prepare(
+    conn: *mut sqlite3,
+    query: &str,
+    flags: u32,
+) -> crate::Result<(Option<SqliteStatementHandle>, usize)> {
+    // TODO: Error on queries that are too large
+    let query_ptr = query.as_bytes().as_ptr() as *const c_char;
+    let query_len = query.len() as i32;
+    let mut statement_handle: *mut sqlite3_stmt = null_mut();
+    let mut tail: *const c_char = null();
+
+    // <https://www.sqlite.org/c3ref/prepare.html>
+    let status = unsafe {
+        sqlite3_prepare_v3(
+            conn,
+            query_ptr,
+            query_len,
+            flags,
+            &mut statement_handle,
+            &mut tail,
+        )
+    };
+
+    if status != SQLITE_OK {
+        return Err(SqliteError::from_connection(conn).into());
+    }
+
+    // If pzTail is not NULL then *pzTail is made to point to the first byte
+    // past the end of the first SQL statement in zSql.
+    let tail = (tail as usize) - (query_ptr as usize);
+
+    Ok((
+        NonNull::new(statement_handle).map(SqliteStatementHandle),
+        tail,
+    ))
+}
+
 impl Drop for Statement {
     fn drop(&mut self) {
         // https://sqlite.org/c3ref/finalize.html
@@ -303,3 +459,140 @@ impl Drop for Statement {
         }
     }
 }
+
+#[cfg(all(test, feature = "runtime-async-std"))]
+mod tests {
+    use std::ffi::{CStr, CString};
+    use std::os::raw::{c_char, c_int, c_void};
+    use std::ptr::{null, null_mut};
+
+    use libsqlite3_sys::{
+        sqlite3, sqlite3_close, sqlite3_column_text, sqlite3_exec, sqlite3_open_v2,
+        sqlite3_set_authorizer, SQLITE_OK, SQLITE_OPEN_PRIVATECACHE, SQLITE_OPEN_READWRITE,
+        SQLITE_READ,
+    };
+
+    use super::{Statement, Step};
+    use crate::arguments::Arguments;
+    use crate::connection::Connect;
+    use crate::executor::Executor;
+    use crate::sqlite::{SqliteArguments, SqliteConnection};
+
+    // SQLite re-prepares a statement by itself this many times before `sqlite3_step` returns
+    // SQLITE_SCHEMA; see SQLITE_MAX_SCHEMA_RETRY
+    const MAX_SCHEMA_RETRY: usize = 50;
+
+    // Changes the schema from [other] whenever a statement that reads `items.name` is compiled
+    // on the connection that this authorizes, until it did so [changes] times; this is only
+    // checked once the schema has been loaded for the statement, so the statement always ends
+    // up compiled for an older schema
+    struct SchemaChanger {
+        other: *mut sqlite3,
+        changes: usize,
+        changed: usize,
+        failed: bool,
+    }
+
+    extern "C" fn change_schema(
+        changer: *mut c_void,
+        action: c_int,
+        _table: *const c_char,
+        column: *const c_char,
+        _: *const c_char,
+        _: *const c_char,
+    ) -> c_int {
+        let changer = unsafe { &mut *(changer as *mut SchemaChanger) };
+
+        // https://www.sqlite.org/c3ref/c_alter_table.html
+        let reads_name =
+            action == SQLITE_READ && unsafe { CStr::from_ptr(column) }.to_bytes() == b"name";
+
+        if reads_name && changer.changed < changer.changes {
+            changer.changed += 1;
+
+            let sql = CString::new(format!(
+                "CREATE TABLE change_{} (id INTEGER)",
+                changer.changed
+            ))
+            .unwrap();
+
+            let status =
+                unsafe { sqlite3_exec(changer.other, sql.as_ptr(), None, null_mut(), null_mut()) };
+
+            changer.failed |= status != SQLITE_OK;
+        }
+
+        SQLITE_OK
+    }
+
+    #[test]
+    fn it_reprepares_when_sqlite_returns_sqlite_schema() {
+        async_std::task::block_on(async {
+            let path = std::env::temp_dir().join("sqlx-it-reprepares-on-sqlite-schema.db");
+            let _ = std::fs::remove_file(&path);
+
+            let url = format!("sqlite://{}", path.display());
+            let mut conn = SqliteConnection::connect(&*url).await.unwrap();
+
+            conn.execute("CREATE TABLE items (id INTEGER PRIMARY KEY, name TEXT NOT NULL)")
+                .await
+                .unwrap();
+
+            conn.execute("INSERT INTO items (id, name) VALUES (1, 'one')")
+                .await
+                .unwrap();
+
+            let mut other = null_mut();
+            let filename = CString::new(path.to_str().unwrap()).unwrap();
+
+            // a cache of its own, as a connection of another process would have
+            let flags = SQLITE_OPEN_READWRITE | SQLITE_OPEN_PRIVATECACHE;
+            let status = unsafe { sqlite3_open_v2(filename.as_ptr(), &mut other, flags, null()) };
+
+            assert_eq!(status, SQLITE_OK);
+
+            // once when the statement is prepared and then every time SQLite re-prepares it,
+            // so it gives up; the statement we prepare after that runs as it is
+            let mut changer = SchemaChanger {
+                other,
+                changes: MAX_SCHEMA_RETRY + 1,
+                changed: 0,
+                failed: false,
+            };
+
+            unsafe {
+                sqlite3_set_authorizer(
+                    conn.handle(),
+                    Some(change_schema),
+                    &mut changer as *mut SchemaChanger as *mut c_void,
+                );
+            }
+
+            let mut query = "SELECT name FROM items WHERE id = ?";
+            let mut statement = Statement::new(&mut conn, &mut query, false).unwrap();
+
+            let mut arguments = SqliteArguments::default();
+            arguments.add(1_i32);
+
+            statement.bind(&mut arguments).unwrap();
+
+            // the parameter is bound again once the statement is re-prepared
+            assert!(matches!(statement.step().await.unwrap(), Step::Row));
+
+            let name = unsafe {
+                CStr::from_ptr(sqlite3_column_text(statement.handle().unwrap(), 0) as *const c_char)
+            };
+
+            assert_eq!(name.to_str().unwrap(), "one");
+            assert_eq!(changer.changed, MAX_SCHEMA_RETRY + 1);
+            assert!(!changer.failed);
+
+            drop(statement);
+
+            unsafe {
+                sqlite3_set_authorizer(conn.handle(), None, null_mut());
+                sqlite3_close(other);
+            }
+        });
+    }
+}
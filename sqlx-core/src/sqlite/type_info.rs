@@ -1,4 +1,6 @@
+use std::borrow::Cow;
 use std::fmt::{self, Display};
+use std::sync::Arc;
 
 use crate::types::TypeInfo;
 
@@ -12,6 +14,9 @@ pub(crate) enum SqliteType {
 
     // Non-standard extensions
     Boolean,
+
+    // The type of a parameter; SQLite will take any value
+    Any,
 }
 
 // https://www.sqlite.org/datatype3.html#type_affinity
@@ -28,6 +33,9 @@ pub(crate) enum SqliteTypeAffinity {
 pub struct SqliteTypeInfo {
     pub(crate) r#type: SqliteType,
     pub(crate) affinity: Option<SqliteTypeAffinity>,
+
+    // The type as written in `CREATE TABLE`, for a column of a table
+    pub(crate) decltype: Option<Arc<str>>,
 }
 
 impl SqliteTypeInfo {
@@ -35,19 +43,54 @@ impl SqliteTypeInfo {
         Self {
             r#type,
             affinity: Some(affinity),
+            decltype: None,
+        }
+    }
+
+    pub(crate) fn any() -> Self {
+        Self {
+            r#type: SqliteType::Any,
+            affinity: None,
+            decltype: None,
         }
     }
+
+    // The type of a column declared (in `CREATE TABLE`) with [decltype], if we recognize it
+    pub(crate) fn from_decltype(decltype: &str) -> Option<Self> {
+        let r#type = match &*decltype.to_ascii_lowercase() {
+            "bool" | "boolean" => SqliteType::Boolean,
+            "clob" | "text" => SqliteType::Text,
+            "blob" => SqliteType::Blob,
+            "real" | "double" | "double precision" | "float" => SqliteType::Float,
+            decl @ _ if decl.contains("int") => SqliteType::Integer,
+            decl @ _ if decl.contains("char") => SqliteType::Text,
+            _ => return None,
+        };
+
+        Some(Self {
+            r#type,
+            affinity: None,
+            decltype: Some(decltype.into()),
+        })
+    }
 }
 
-impl Display for SqliteTypeInfo {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str(match self.r#type {
+impl SqliteType {
+    fn as_str(self) -> &'static str {
+        match self {
             SqliteType::Text => "TEXT",
             SqliteType::Boolean => "BOOLEAN",
             SqliteType::Integer => "INTEGER",
             SqliteType::Float => "DOUBLE",
             SqliteType::Blob => "BLOB",
-        })
+            SqliteType::Any => "ANY",
+        }
+    }
+}
+
+impl Display for SqliteTypeInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.r#type.as_str())
     }
 }
 
@@ -58,6 +101,15 @@ impl PartialEq<SqliteTypeInfo> for SqliteTypeInfo {
 }
 
 impl TypeInfo for SqliteTypeInfo {
+    /// Returns the declared type of a column, if it has one, e.g. `varchar(255)`.
+    fn name(&self) -> Cow<'_, str> {
+        Cow::Borrowed(
+            self.decltype
+                .as_deref()
+                .unwrap_or_else(|| self.r#type.as_str()),
+        )
+    }
+
     #[inline]
     fn compatible(&self, _other: &Self) -> bool {
         // All types are compatible with all other types in SQLite
@@ -12,6 +12,17 @@
 //! | `f64`                                 | REAL                                                 |
 //! | `&str`, `String`                      | TEXT                                                 |
 //! | `&[u8]`, `Vec<u8>`                    | BLOB                                                 |
+//! | `std::time::SystemTime`               | TEXT                                                 |
+//!
+//! `NonZeroI32` and `NonZeroI64` from `std::num` are encoded and decoded as the integer they
+//! wrap; decoding a `0` as one of them is an error. The query macros never choose these, but
+//! `query_as!()` accepts them as the type of a field.
+//!
+//! A `SystemTime` is stored as ISO-8601 text in UTC (`YYYY-MM-DD HH:MM:SS.SSS`), which is
+//! understood by the [date and time functions] of SQLite. Times before the UNIX epoch are
+//! supported; precision is kept to the nanosecond.
+//!
+//! [date and time functions]: https://www.sqlite.org/lang_datefunc.html
 //!
 //! # Nullable
 //!
@@ -27,6 +38,7 @@ mod bool;
 mod bytes;
 mod float;
 mod int;
+mod std_time;
 mod str;
 
 impl<'de, T> Decode<'de, Sqlite> for Option<T>
@@ -0,0 +1,51 @@
+use std::error::Error as StdError;
+use std::time::SystemTime;
+
+use crate::civil::{self, DateTime};
+use crate::decode::Decode;
+use crate::encode::Encode;
+use crate::error::UnexpectedNullError;
+use crate::sqlite::type_info::{SqliteType, SqliteTypeAffinity};
+use crate::sqlite::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValue};
+use crate::types::Type;
+
+impl Type<Sqlite> for SystemTime {
+    fn type_info() -> SqliteTypeInfo {
+        SqliteTypeInfo::new(SqliteType::Text, SqliteTypeAffinity::Text)
+    }
+}
+
+// `YYYY-MM-DD HH:MM:SS.SSS` in UTC, as understood by the date and time functions of SQLite,
+// but with as many fractional digits as needed
+// https://www.sqlite.org/lang_datefunc.html
+impl Encode<Sqlite> for SystemTime {
+    fn encode(&self, values: &mut Vec<SqliteArgumentValue>) {
+        // a time out of range is reported by [check] and never bound
+        values.push(match to_datetime(self) {
+            Ok(dt) => SqliteArgumentValue::Text(dt.to_string()),
+            Err(_) => SqliteArgumentValue::Null,
+        });
+    }
+
+    fn check(&self) -> Result<(), Box<dyn StdError + Send + Sync>> {
+        to_datetime(self).map(drop)
+    }
+}
+
+fn to_datetime(time: &SystemTime) -> Result<DateTime, Box<dyn StdError + Send + Sync>> {
+    civil::unix_timestamp(*time)
+        .and_then(|(secs, nanos)| DateTime::from_unix_timestamp(secs, nanos))
+        .ok_or_else(|| format!("SystemTime out of range for SQLite: {:?}", time).into())
+}
+
+impl<'de> Decode<'de, Sqlite> for SystemTime {
+    fn decode(value: SqliteValue<'de>) -> crate::Result<Self> {
+        let text = value
+            .text()
+            .ok_or_else(|| crate::Error::decode(UnexpectedNullError))?;
+
+        let (secs, nanos) = DateTime::parse(text)?.unix_timestamp();
+
+        civil::from_unix_timestamp(secs, nanos)
+    }
+}
@@ -1,6 +1,5 @@
 use core::slice;
 
-use std::ffi::CStr;
 use std::str::from_utf8_unchecked;
 
 use libsqlite3_sys::{
@@ -30,7 +29,7 @@ impl<'c> SqliteValue<'c> {
         self.r#type().is_none()
     }
 
-    fn r#type(&self) -> Option<SqliteType> {
+    pub(super) fn r#type(&self) -> Option<SqliteType> {
         let type_code = unsafe {
             if let Some(handle) = self.statement.handle() {
                 sqlite3_column_type(handle, self.index)
@@ -81,16 +80,19 @@ impl<'c> SqliteValue<'c> {
 
     /// Returns the UTF-8 TEXT result.
     pub(super) fn text(&self) -> Option<&'c str> {
-        unsafe {
-            self.statement.handle().and_then(|handle| {
-                let ptr = sqlite3_column_text(handle, self.index);
-
-                if ptr.is_null() {
-                    None
-                } else {
-                    Some(from_utf8_unchecked(CStr::from_ptr(ptr as _).to_bytes()))
-                }
-            })
+        // [sqlite3_column_text] always returns UTF-8, converting from the text encoding of
+        // the database if needed (e.g., a database created with `PRAGMA encoding = 'UTF-16'`).
+
+        // [sqlite3_column_bytes] must be called *after* [sqlite3_column_text] so that it
+        // reports the length of the converted value.
+        // https://www.sqlite.org/c3ref/column_blob.html
+
+        let ptr = unsafe { sqlite3_column_text(self.statement.handle()?, self.index) };
+
+        if ptr.is_null() {
+            None
+        } else {
+            Some(unsafe { from_utf8_unchecked(slice::from_raw_parts(ptr, self.bytes())) })
         }
     }
 
@@ -130,6 +132,7 @@ impl<'c> RawValue<'c> for SqliteValue<'c> {
         Some(SqliteTypeInfo {
             r#type: self.r#type()?,
             affinity: None,
+            decltype: None,
         })
     }
 }
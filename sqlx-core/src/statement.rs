@@ -0,0 +1,212 @@
+//! Contains the `Statement` type.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt::{self, Debug};
+
+use crate::database::Database;
+use crate::describe::Column;
+
+/// Information about the statement a [`Row`] was returned by.
+///
+/// This is shared by all of the rows of a statement, and is what they use to look up
+/// their columns by name, so holding onto it (through [`Row::statement`]) is cheap.
+///
+/// ```rust,ignore
+/// let total: i64 = row
+///     .try_get("total")
+///     .with_context(|| format!("in the results of `{}`", row.statement().sql()))?;
+/// ```
+///
+/// [`Row`]: crate::row::Row
+/// [`Row::statement`]: crate::row::Row::statement
+pub struct Statement<DB>
+where
+    DB: Database,
+{
+    id: u64,
+    sql: Box<str>,
+    columns: Box<[Column<DB>]>,
+
+    // column name -> position
+    pub(crate) names: HashMap<Box<str>, usize>,
+}
+
+impl<DB> Statement<DB>
+where
+    DB: Database,
+{
+    pub(crate) fn new(sql: &str, columns: Vec<Column<DB>>, redaction: SqlRedaction) -> Self {
+        let mut names = HashMap::with_capacity(columns.len());
+
+        for (index, column) in columns.iter().enumerate() {
+            if let Some(name) = &column.name {
+                names.insert(name.clone(), index);
+            }
+        }
+
+        let id = fnv1a(sql.as_bytes());
+
+        Self {
+            id,
+            sql: redaction.redact(sql, id),
+            columns: columns.into_boxed_slice(),
+            names,
+        }
+    }
+
+    /// Returns an identifier for the SQL of this statement.
+    ///
+    /// This is the same for every statement with the same SQL, whichever connection it is run on.
+    /// It is the 64-bit [FNV-1a] hash of the SQL, which does not depend on the version of SQLx
+    /// or Rust it was computed by, so it can be stored or compared between processes.
+    ///
+    /// [FNV-1a]: http://www.isthe.com/chongo/tech/comp/fnv/index.html#FNV-1a
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Returns the SQL of this statement, as kept by the [`SqlRedaction`] of the connection it
+    /// was prepared on; by default, all of it.
+    ///
+    /// Depending on the database, this is either the statement itself or the whole query for a
+    /// query of several statements.
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    /// Returns the columns of the rows this statement returns.
+    ///
+    /// Whether a column can be `NULL` is not known here; see `Executor::describe` instead.
+    pub fn columns(&self) -> &[Column<DB>] {
+        &self.columns
+    }
+}
+
+impl<DB> Default for Statement<DB>
+where
+    DB: Database,
+{
+    fn default() -> Self {
+        Self::new("", Vec::new(), SqlRedaction::Full)
+    }
+}
+
+impl<DB> Debug for Statement<DB>
+where
+    DB: Database,
+    Column<DB>: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Statement")
+            .field("id", &self.id)
+            .field("sql", &self.sql)
+            .field("columns", &self.columns)
+            .finish()
+    }
+}
+
+/// How much of the SQL of a statement is kept for [`Statement::sql`], and so for the errors that
+/// include it (such as [`Error::ColumnDecode`]); e.g., to keep the values written into the SQL
+/// of a query out of the logs that errors end up in.
+///
+/// This is set for a single connection with [`Connection::set_sql_redaction`], or for every
+/// connection of a pool with [`Builder::sql_redaction`].
+///
+/// [`Error::ColumnDecode`]: crate::Error::ColumnDecode
+/// [`Connection::set_sql_redaction`]: crate::connection::Connection::set_sql_redaction
+/// [`Builder::sql_redaction`]: crate::pool::Builder::sql_redaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SqlRedaction {
+    /// Keep all of the SQL. This is the default.
+    Full,
+
+    /// Keep no more than the first `n` bytes of the SQL, followed by how many more there were.
+    Truncate(usize),
+
+    /// Keep none of the SQL, only its [`id`](Statement::id).
+    Hash,
+}
+
+impl SqlRedaction {
+    fn redact(self, sql: &str, id: u64) -> Box<str> {
+        match self {
+            SqlRedaction::Full => sql.into(),
+            SqlRedaction::Truncate(len) => truncate(sql, len).into(),
+            SqlRedaction::Hash => format!("<SQL with the id {:#018x}>", id).into_boxed_str(),
+        }
+    }
+}
+
+impl Default for SqlRedaction {
+    fn default() -> Self {
+        SqlRedaction::Full
+    }
+}
+
+// The first `len` bytes of `sql` (or less, to end on a character) followed by how many more there
+// were, or all of `sql` if it is no longer than that
+pub(crate) fn truncate(sql: &str, len: usize) -> Cow<'_, str> {
+    if sql.len() <= len {
+        return Cow::Borrowed(sql);
+    }
+
+    let end = (0..=len)
+        .rev()
+        .find(|&end| sql.is_char_boundary(end))
+        .unwrap_or(0);
+
+    Cow::Owned(format!(
+        "{} ... ({} more bytes)",
+        &sql[..end],
+        sql.len() - end
+    ))
+}
+
+// http://www.isthe.com/chongo/tech/comp/fnv/index.html#FNV-1a
+fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fnv1a, truncate, SqlRedaction};
+
+    #[test]
+    fn it_hashes_with_fnv1a() {
+        // test vectors of http://www.isthe.com/chongo/src/fnv/test_fnv.c
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv1a(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+
+    #[test]
+    fn it_redacts_the_sql() {
+        let sql = "SELECT * FROM users WHERE email = 'jane@example.com'";
+        let id = fnv1a(sql.as_bytes());
+
+        assert_eq!(&*SqlRedaction::Full.redact(sql, id), sql);
+        assert_eq!(
+            &*SqlRedaction::Truncate(19).redact(sql, id),
+            "SELECT * FROM users ... (33 more bytes)"
+        );
+        assert_eq!(&*SqlRedaction::Truncate(100).redact(sql, id), sql);
+        assert_eq!(
+            &*SqlRedaction::Hash.redact(sql, id),
+            format!("<SQL with the id {:#018x}>", id)
+        );
+    }
+
+    #[test]
+    fn it_truncates_on_a_character_boundary() {
+        // `é` takes the 2nd and 3rd bytes
+        assert_eq!(truncate("héllo", 2), "h ... (5 more bytes)");
+        assert_eq!(truncate("héllo", 3), "hé ... (3 more bytes)");
+    }
+}
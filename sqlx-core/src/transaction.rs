@@ -7,14 +7,16 @@ use crate::cursor::HasCursor;
 use crate::database::Database;
 use crate::describe::Describe;
 use crate::executor::{Execute, Executor, RefExecutor};
-use crate::runtime::spawn;
+use crate::pool::PoolConnection;
 
 /// Represents an in-progress database transaction.
 ///
-/// A transaction ends with a call to [`commit`] or [`rollback`] in which the wrapped connection (
-/// or outer transaction) is returned. If neither are called before the transaction
-/// goes out-of-scope, [`rollback`] is called. In other words, [`rollback`] is called on `drop`
-/// if the transaction is still in-progress.
+/// A transaction borrows the connection (or outer transaction) it was started on for as long
+/// as it is in progress; a transaction started from a [`Pool`] holds onto the connection it
+/// acquired instead. It ends with a call to [`commit`] or [`rollback`], after which the
+/// connection can be used again. If neither are called before the transaction goes
+/// out-of-scope, it is rolled back. In other words, it is rolled back on `drop` if it is
+/// still in-progress.
 ///
 /// ```rust,ignore
 /// // Acquire a new connection and immediately begin a transaction
@@ -29,144 +31,179 @@ use crate::runtime::spawn;
 ///     .await?;
 /// ```
 ///
+/// As there is no way to wait for anything in `drop`, the `ROLLBACK` of a transaction that is
+/// dropped is only sent to the database along with whatever is next run on the connection. A
+/// connection of a [`Pool`] sends it as it is returned to the pool instead, without waiting for
+/// its result, which is read the next time the connection is acquired; if it cannot be sent
+/// right away, the connection is closed instead.
+///
+/// [`Pool`]: crate::pool::Pool
 /// [`commit`]: #method.commit
 /// [`rollback`]: #method.rollback
-// Transaction<PoolConnection<PgConnection>>
-// Transaction<PgConnection>
 #[must_use = "transaction rolls back if not explicitly `.commit()`ed"]
-pub struct Transaction<C>
+pub struct Transaction<'c, DB>
 where
-    C: Connection,
+    DB: Database,
 {
-    inner: Option<C>,
+    connection: MaybePoolConnection<'c, DB>,
+
+    // the number of transactions (including this one) that the connection is in
     depth: u32,
+
+    // false once committed or rolled back
+    open: bool,
 }
 
-impl<C> Transaction<C>
+pub(crate) enum MaybePoolConnection<'c, DB>
 where
-    C: Connection,
+    DB: Database,
 {
-    pub(crate) async fn new(depth: u32, mut inner: C) -> crate::Result<Self> {
+    Connection(&'c mut DB::Connection),
+    PoolConnection(PoolConnection<DB::Connection>),
+}
+
+impl<'c, DB> Transaction<'c, DB>
+where
+    DB: Database,
+{
+    pub(crate) async fn new(
+        depth: u32,
+        connection: impl Into<MaybePoolConnection<'c, DB>>,
+    ) -> crate::Result<Self> {
+        let mut connection = connection.into();
+
         if depth == 0 {
-            inner.execute("BEGIN").await?;
+            connection.execute("BEGIN").await?;
         } else {
             let stmt = format!("SAVEPOINT _sqlx_savepoint_{}", depth);
 
-            inner.execute(&*stmt).await?;
+            connection.execute(&*stmt).await?;
         }
 
         Ok(Self {
-            inner: Some(inner),
+            connection,
             depth: depth + 1,
+            open: true,
         })
     }
 
     /// Creates a new save point in the current transaction and returns
     /// a new `Transaction` object to manage its scope.
-    pub async fn begin(self) -> crate::Result<Transaction<Transaction<C>>> {
-        Transaction::new(self.depth, self).await
+    ///
+    /// The new transaction borrows this one until it is committed, rolled back or dropped.
+    pub async fn begin(&mut self) -> crate::Result<Transaction<'_, DB>> {
+        Transaction::new(self.depth, &mut **self).await
     }
 
     /// Commits the current transaction or save point.
-    /// Returns the inner connection or transaction.
-    pub async fn commit(mut self) -> crate::Result<C> {
-        let mut inner = self.inner.take().expect(ERR_FINALIZED);
+    pub async fn commit(mut self) -> crate::Result<()> {
         let depth = self.depth;
 
         if depth == 1 {
-            inner.execute("COMMIT").await?;
+            self.connection.execute("COMMIT").await?;
         } else {
             let stmt = format!("RELEASE SAVEPOINT _sqlx_savepoint_{}", depth - 1);
 
-            inner.execute(&*stmt).await?;
+            self.connection.execute(&*stmt).await?;
         }
 
-        Ok(inner)
+        self.open = false;
+
+        Ok(())
     }
 
     /// Rollback the current transaction or save point.
-    /// Returns the inner connection or transaction.
-    pub async fn rollback(mut self) -> crate::Result<C> {
-        let mut inner = self.inner.take().expect(ERR_FINALIZED);
-        let depth = self.depth;
+    pub async fn rollback(mut self) -> crate::Result<()> {
+        self.connection
+            .execute(&*rollback_statement(self.depth))
+            .await?;
 
-        if depth == 1 {
-            inner.execute("ROLLBACK").await?;
-        } else {
-            let stmt = format!("ROLLBACK TO SAVEPOINT _sqlx_savepoint_{}", depth - 1);
+        self.open = false;
 
-            inner.execute(&*stmt).await?;
-        }
+        Ok(())
+    }
+}
 
-        Ok(inner)
+fn rollback_statement(depth: u32) -> String {
+    if depth == 1 {
+        "ROLLBACK".into()
+    } else {
+        format!("ROLLBACK TO SAVEPOINT _sqlx_savepoint_{}", depth - 1)
+    }
+}
+
+impl<'c, DB> From<&'c mut DB::Connection> for MaybePoolConnection<'c, DB>
+where
+    DB: Database,
+{
+    fn from(connection: &'c mut DB::Connection) -> Self {
+        MaybePoolConnection::Connection(connection)
     }
 }
 
-const ERR_FINALIZED: &str = "(bug) transaction already finalized";
+impl<'c, DB> From<PoolConnection<DB::Connection>> for MaybePoolConnection<'c, DB>
+where
+    DB: Database,
+{
+    fn from(connection: PoolConnection<DB::Connection>) -> Self {
+        MaybePoolConnection::PoolConnection(connection)
+    }
+}
 
-impl<C> Deref for Transaction<C>
+impl<'c, DB> Deref for MaybePoolConnection<'c, DB>
 where
-    C: Connection,
+    DB: Database,
 {
-    type Target = C;
+    type Target = DB::Connection;
 
     fn deref(&self) -> &Self::Target {
-        self.inner.as_ref().expect(ERR_FINALIZED)
+        match self {
+            MaybePoolConnection::Connection(connection) => connection,
+            MaybePoolConnection::PoolConnection(connection) => connection,
+        }
     }
 }
 
-impl<C> DerefMut for Transaction<C>
+impl<'c, DB> DerefMut for MaybePoolConnection<'c, DB>
 where
-    C: Connection,
+    DB: Database,
 {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        self.inner.as_mut().expect(ERR_FINALIZED)
+        match self {
+            MaybePoolConnection::Connection(connection) => connection,
+            MaybePoolConnection::PoolConnection(connection) => connection,
+        }
     }
 }
 
-impl<C> Connection for Transaction<C>
+impl<'c, DB> Deref for Transaction<'c, DB>
 where
-    C: Connection,
+    DB: Database,
 {
-    // Close is equivalent to
-    fn close(mut self) -> BoxFuture<'static, crate::Result<()>> {
-        Box::pin(async move {
-            let mut inner = self.inner.take().expect(ERR_FINALIZED);
-
-            if self.depth == 1 {
-                // This is the root transaction, call rollback
-                let res = inner.execute("ROLLBACK").await;
-
-                // No matter the result of the above, call close
-                let _ = inner.close().await;
-
-                // Now raise the error if there was one
-                res?;
-            } else {
-                // This is not the root transaction, forward to a nested
-                // transaction (to eventually call rollback)
-                inner.close().await?
-            }
+    type Target = DB::Connection;
 
-            Ok(())
-        })
+    fn deref(&self) -> &Self::Target {
+        &self.connection
     }
+}
 
-    #[inline]
-    fn ping(&mut self) -> BoxFuture<'_, crate::Result<()>> {
-        self.deref_mut().ping()
+impl<'c, DB> DerefMut for Transaction<'c, DB>
+where
+    DB: Database,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.connection
     }
 }
 
-impl<DB, C> Executor for Transaction<C>
+impl<'c, DB> Executor for Transaction<'c, DB>
 where
     DB: Database,
-    C: Connection<Database = DB>,
 {
-    type Database = C::Database;
+    type Database = DB;
 
-    fn execute<'e, 'q: 'e, 'c: 'e, E: 'e>(
-        &'c mut self,
+    fn execute<'e, 'q: 'e, 't: 'e, E: 'e>(
+        &'t mut self,
         query: E,
     ) -> BoxFuture<'e, crate::Result<u64>>
     where
@@ -194,10 +231,9 @@ where
     }
 }
 
-impl<'e, DB, C> RefExecutor<'e> for &'e mut Transaction<C>
+impl<'e, 'c, DB> RefExecutor<'e> for &'e mut Transaction<'c, DB>
 where
     DB: Database,
-    C: Connection<Database = DB>,
 {
     type Database = DB;
 
@@ -209,16 +245,25 @@ where
     }
 }
 
-impl<C> Drop for Transaction<C>
+impl<'c, DB> Drop for Transaction<'c, DB>
 where
-    C: Connection,
+    DB: Database,
 {
     fn drop(&mut self) {
-        if self.depth > 0 {
-            if let Some(inner) = self.inner.take() {
-                spawn(async move {
-                    let _ = inner.close().await;
-                });
+        if self.open {
+            // we cannot wait on the database here so the connection runs this first
+            // thing the next time it is used
+            let stmt = rollback_statement(self.depth);
+
+            if !self.connection.queue_rollback(&stmt) {
+                match &mut self.connection {
+                    // the transaction goes away with the connection
+                    MaybePoolConnection::PoolConnection(connection) => connection.discard(),
+
+                    MaybePoolConnection::Connection(_) => {
+                        log::warn!("a transaction was dropped on a connection that cannot roll it back; it stays open until the connection is closed");
+                    }
+                }
             }
         }
     }
@@ -11,6 +11,7 @@
 //! for convenience as downstream users need to use a compatible version of the external crate
 //! to take advantage of the implementation.
 
+use std::borrow::Cow;
 use std::fmt::{Debug, Display};
 
 use crate::database::Database;
@@ -108,15 +109,39 @@ pub trait TypeInfo: PartialEq<Self> + Debug + Display + Clone {
     /// Compares type information to determine if `other` is compatible at the Rust level
     /// with `self`.
     fn compatible(&self, other: &Self) -> bool;
+
+    /// Returns the name the database uses for this type, e.g. `INT8` or `TEXT[]` in Postgres.
+    ///
+    /// This is the same for the parameters and the result columns of
+    /// [`Describe`][crate::describe::Describe]. Defaults to the [`Display`] of the type.
+    fn name(&self) -> Cow<'_, str> {
+        Cow::Owned(self.to_string())
+    }
 }
 
 /// Indicates that a SQL type is supported for a database.
+///
+/// This decides which SQL types a value of the Rust type can be decoded from: [`Row::try_get`]
+/// checks the type of the column with [`compatible`], and so do the query macros when they pick
+/// the Rust type of a column or a parameter.
+///
+/// [`Row::try_get`]: crate::row::Row::try_get
+/// [`compatible`]: Type::compatible
 pub trait Type<DB>
 where
     DB: Database,
 {
     /// Returns the canonical type information on the database for the type `T`.
     fn type_info() -> DB::TypeInfo;
+
+    /// Returns `true` if a value of the SQL type `ty` can be decoded as `T`.
+    ///
+    /// This defaults to [`TypeInfo::compatible`] with the canonical type of `T`. A type that
+    /// decodes from several SQL types the database does not consider compatible with each other,
+    /// looking at the type of each value, overrides it to accept all of them.
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        ty.compatible(&Self::type_info())
+    }
 }
 
 // For references to types in Rust, the underlying SQL type information
@@ -129,6 +154,10 @@ where
     fn type_info() -> DB::TypeInfo {
         <T as Type<DB>>::type_info()
     }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <T as Type<DB>>::compatible(ty)
+    }
 }
 
 // For optional types in Rust, the underlying SQL type information
@@ -141,4 +170,107 @@ where
     fn type_info() -> DB::TypeInfo {
         <T as Type<DB>>::type_info()
     }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <T as Type<DB>>::compatible(ty)
+    }
 }
+
+// The integer types from `std::num` that can never be zero are encoded and decoded as the
+// integer they wrap, wherever the database supports it
+mod nonzero {
+    use std::num::{
+        NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroU16, NonZeroU32, NonZeroU64,
+        NonZeroU8,
+    };
+
+    use super::Type;
+    use crate::database::Database;
+    use crate::decode::Decode;
+    use crate::encode::{Encode, IsNull};
+    use crate::value::HasRawValue;
+
+    /// An integer type from `std::num` that can never be zero, such as `NonZeroI64`.
+    #[doc(hidden)]
+    pub trait NonZeroInt: Sized {
+        /// The integer type that this wraps, such as `i64`.
+        type Int;
+
+        /// Returns an error if `int` is zero.
+        fn try_from_int(int: Self::Int) -> crate::Result<Self>;
+    }
+
+    macro_rules! impl_non_zero {
+        ($($ty:ident($int:ty)),* $(,)?) => {$(
+            impl NonZeroInt for $ty {
+                type Int = $int;
+
+                fn try_from_int(int: $int) -> crate::Result<Self> {
+                    $ty::new(int).ok_or_else(|| {
+                        decode_err!("the database returned 0, which is not a valid {}", stringify!($ty))
+                    })
+                }
+            }
+
+            impl<DB> Type<DB> for $ty
+            where
+                DB: Database,
+                $int: Type<DB>,
+            {
+                fn type_info() -> DB::TypeInfo {
+                    <$int as Type<DB>>::type_info()
+                }
+
+                fn compatible(ty: &DB::TypeInfo) -> bool {
+                    <$int as Type<DB>>::compatible(ty)
+                }
+            }
+
+            impl<DB> Encode<DB> for $ty
+            where
+                DB: Database,
+                $int: Encode<DB>,
+            {
+                fn encode(&self, buf: &mut DB::RawBuffer) {
+                    self.get().encode(buf)
+                }
+
+                fn encode_nullable(&self, buf: &mut DB::RawBuffer) -> IsNull {
+                    self.get().encode_nullable(buf)
+                }
+
+                fn check(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+                    self.get().check()
+                }
+
+                fn size_hint(&self) -> usize {
+                    self.get().size_hint()
+                }
+            }
+
+            impl<'de, DB> Decode<'de, DB> for $ty
+            where
+                DB: Database,
+                $int: Decode<'de, DB>,
+            {
+                fn decode(value: <DB as HasRawValue<'de>>::RawValue) -> crate::Result<Self> {
+                    Self::try_from_int(<$int as Decode<DB>>::decode(value)?)
+                }
+            }
+        )*};
+    }
+
+    impl_non_zero!(
+        NonZeroI8(i8),
+        NonZeroI16(i16),
+        NonZeroI32(i32),
+        NonZeroI64(i64),
+        NonZeroU8(u8),
+        NonZeroU16(u16),
+        NonZeroU32(u32),
+        NonZeroU64(u64),
+    );
+}
+
+#[doc(hidden)]
+pub use self::nonzero::NonZeroInt;
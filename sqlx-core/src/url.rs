@@ -1,7 +1,7 @@
 use std::borrow::Cow;
 use std::convert::{TryFrom, TryInto};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Url(url::Url);
 
 impl TryFrom<String> for Url {
@@ -1,3 +1,4 @@
+use proc_macro2::TokenStream;
 use sqlx::database::Database;
 
 #[derive(PartialEq, Eq)]
@@ -7,11 +8,23 @@ pub enum ParamChecking {
     Weak,
 }
 
+// How the parameters of a query are written
+#[derive(PartialEq, Eq, Clone, Copy)]
+#[allow(dead_code)]
+pub enum ParamStyle {
+    // `$1`, `$2`, ...
+    Numbered,
+    // `?`
+    Anonymous,
+}
+
 pub trait DatabaseExt: Database {
+    const NAME: &'static str;
     const DATABASE_PATH: &'static str;
     const ROW_PATH: &'static str;
 
     const PARAM_CHECKING: ParamChecking;
+    const PARAM_STYLE: ParamStyle;
 
     fn db_path() -> syn::Path {
         syn::parse_str(Self::DATABASE_PATH).unwrap()
@@ -21,11 +34,21 @@ pub trait DatabaseExt: Database {
         syn::parse_str(Self::ROW_PATH).unwrap()
     }
 
+    // The Rust type of a parameter or a column is the first of the types of the database that
+    // has the same SQL type, or else the first one that is `Type::compatible` with it, as
+    // `Row::try_get` checks at runtime
     fn param_type_for_id(id: &Self::TypeInfo) -> Option<&'static str>;
 
     fn return_type_for_id(id: &Self::TypeInfo) -> Option<&'static str>;
 
     fn get_feature_gate(info: &Self::TypeInfo) -> Option<&'static str>;
+
+    // An expression that rebuilds the type of a parameter, for the checks of the expansion
+    fn quote_type_info(info: &Self::TypeInfo) -> Option<TokenStream>;
+
+    // Statements that rebind `ty_check`, the type an argument `_expr` is checked against, for the
+    // arguments that are accepted in place of any type
+    fn quote_match_unknown(span: proc_macro2::Span) -> Option<TokenStream>;
 }
 
 macro_rules! impl_database_ext {
@@ -34,13 +57,19 @@ macro_rules! impl_database_ext {
             $($(#[$meta:meta])? $ty:ty $(| $input:ty)?),*$(,)?
         },
         ParamChecking::$param_checking:ident,
+        ParamStyle::$param_style:ident,
         feature-types: $name:ident => $get_gate:expr,
-        row = $row:path
+        quote-type-info: $info:ident => $quote_info:expr,
+        match-unknown: $span:ident => $match_unknown:expr,
+        row = $row:path,
+        name = $db_name:literal
     ) => {
         impl $crate::database::DatabaseExt for $database {
+            const NAME: &'static str = $db_name;
             const DATABASE_PATH: &'static str = stringify!($database);
             const ROW_PATH: &'static str = stringify!($row);
             const PARAM_CHECKING: $crate::database::ParamChecking = $crate::database::ParamChecking::$param_checking;
+            const PARAM_STYLE: $crate::database::ParamStyle = $crate::database::ParamStyle::$param_style;
 
             fn param_type_for_id(info: &Self::TypeInfo) -> Option<&'static str> {
                 match () {
@@ -50,7 +79,7 @@ macro_rules! impl_database_ext {
                     )*
                     $(
                         $(#[$meta])?
-                        _ if sqlx::types::TypeInfo::compatible(&<$ty as sqlx::types::Type<$database>>::type_info(), &info) => Some(input_ty!($ty $(, $input)?)),
+                        _ if <$ty as sqlx::types::Type<$database>>::compatible(info) => Some(input_ty!($ty $(, $input)?)),
                     )*
                     _ => None
                 }
@@ -64,7 +93,7 @@ macro_rules! impl_database_ext {
                     )*
                     $(
                         $(#[$meta])?
-                        _ if sqlx::types::TypeInfo::compatible(&<$ty as sqlx::types::Type<$database>>::type_info(), &info) => return Some(stringify!($ty)),
+                        _ if <$ty as sqlx::types::Type<$database>>::compatible(info) => return Some(stringify!($ty)),
                     )*
                     _ => None
                 }
@@ -73,6 +102,14 @@ macro_rules! impl_database_ext {
             fn get_feature_gate($name: &Self::TypeInfo) -> Option<&'static str> {
                 $get_gate
             }
+
+            fn quote_type_info($info: &Self::TypeInfo) -> Option<proc_macro2::TokenStream> {
+                $quote_info
+            }
+
+            fn quote_match_unknown($span: proc_macro2::Span) -> Option<proc_macro2::TokenStream> {
+                $match_unknown
+            }
         }
     }
 }
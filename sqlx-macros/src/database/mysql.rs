@@ -41,10 +41,21 @@ impl_database_ext! {
         #[cfg(feature = "time")]
         sqlx::types::time::OffsetDateTime,
 
+        #[cfg(not(any(feature = "chrono", feature = "time")))]
+        std::time::SystemTime,
+
+        #[cfg(not(any(feature = "chrono", feature = "time")))]
+        std::time::Duration,
+
         #[cfg(feature = "bigdecimal")]
         sqlx::types::BigDecimal,
     },
     ParamChecking::Weak,
+    ParamStyle::Anonymous,
     feature-types: info => info.type_feature_gate(),
-    row = sqlx::mysql::MySqlRow
+    // the types of parameters are only checked for Postgres
+    quote-type-info: _info => None,
+    match-unknown: _span => None,
+    row = sqlx::mysql::MySqlRow,
+    name = "MySQL"
 }
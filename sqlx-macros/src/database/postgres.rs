@@ -39,6 +39,12 @@ impl_database_ext! {
         #[cfg(feature = "time")]
         sqlx::types::time::OffsetDateTime,
 
+        #[cfg(not(any(feature = "chrono", feature = "time")))]
+        std::time::SystemTime,
+
+        #[cfg(not(any(feature = "chrono", feature = "time")))]
+        std::time::Duration,
+
         #[cfg(feature = "bigdecimal")]
         sqlx::types::BigDecimal,
 
@@ -96,6 +102,22 @@ impl_database_ext! {
 
     },
     ParamChecking::Strong,
+    ParamStyle::Numbered,
     feature-types: info => info.type_feature_gate(),
-    row = sqlx::postgres::PgRow
+    quote-type-info: info => info.oid().map(|oid| {
+        let name = info.to_string();
+
+        quote::quote!(sqlx::postgres::PgTypeInfo::with_oid(#oid, #name))
+    }),
+    // a `PgUnknown` is accepted wherever postgres inferred a type different from `TEXT`
+    match-unknown: span => Some(quote::quote_spanned!(span =>
+        let (ty_check, match_unknown) = sqlx::ty_match::MatchUnknown::new(ty_check, &_expr);
+        let ty_check = {
+            use sqlx::ty_match::MatchUnknownExt as _;
+
+            match_unknown.match_unknown()
+        };
+    )),
+    row = sqlx::postgres::PgRow,
+    name = "PostgreSQL"
 }
@@ -9,6 +9,11 @@ impl_database_ext! {
         Vec<u8>,
     },
     ParamChecking::Weak,
+    ParamStyle::Anonymous,
     feature-types: _info => None,
-    row = sqlx::sqlite::SqliteRow
+    // the types of parameters are only checked for Postgres
+    quote-type-info: _info => None,
+    match-unknown: _span => None,
+    row = sqlx::sqlite::SqliteRow,
+    name = "SQLite"
 }
@@ -1,7 +1,9 @@
 use proc_macro2::Ident;
 use syn::punctuated::Punctuated;
 use syn::token::Comma;
-use syn::{Attribute, DeriveInput, Field, Lit, Meta, MetaNameValue, NestedMeta, Variant};
+use syn::{
+    Attribute, DeriveInput, Field, Lit, Meta, MetaNameValue, NestedMeta, Path, Type, Variant,
+};
 
 macro_rules! assert_attribute {
     ($e:expr, $err:expr, $input:expr) => {
@@ -41,6 +43,8 @@ pub struct SqlxContainerAttributes {
 
 pub struct SqlxChildAttributes {
     pub rename: Option<String>,
+    pub try_from: Option<Type>,
+    pub with: Option<Path>,
 }
 
 pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContainerAttributes> {
@@ -114,6 +118,8 @@ pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContai
 
 pub fn parse_child_attributes(input: &[Attribute]) -> syn::Result<SqlxChildAttributes> {
     let mut rename = None;
+    let mut try_from = None;
+    let mut with = None;
 
     for attr in input {
         let meta = attr
@@ -131,6 +137,20 @@ pub fn parse_child_attributes(input: &[Attribute]) -> syn::Result<SqlxChildAttri
                                 ..
                             }) if path.is_ident("rename") => try_set!(rename, val.value(), value),
 
+                            Meta::NameValue(MetaNameValue {
+                                path,
+                                lit: Lit::Str(val),
+                                ..
+                            }) if path.is_ident("try_from") => {
+                                try_set!(try_from, val.parse()?, value)
+                            }
+
+                            Meta::NameValue(MetaNameValue {
+                                path,
+                                lit: Lit::Str(val),
+                                ..
+                            }) if path.is_ident("with") => try_set!(with, val.parse()?, value),
+
                             u => fail!(u, "unexpected attribute"),
                         },
                         u => fail!(u, "unexpected attribute"),
@@ -141,7 +161,15 @@ pub fn parse_child_attributes(input: &[Attribute]) -> syn::Result<SqlxChildAttri
         }
     }
 
-    Ok(SqlxChildAttributes { rename })
+    if let (Some(_), Some(with)) = (&try_from, &with) {
+        fail!(with, "`try_from` and `with` cannot be used together")
+    }
+
+    Ok(SqlxChildAttributes {
+        rename,
+        try_from,
+        with,
+    })
 }
 
 pub fn check_transparent_attributes(input: &DeriveInput, field: &Field) -> syn::Result<()> {
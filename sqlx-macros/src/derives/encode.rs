@@ -77,6 +77,9 @@ fn expand_derive_encode_transparent(
             fn encode_nullable(&self, buf: &mut DB::RawBuffer) -> sqlx::encode::IsNull {
                 sqlx::encode::Encode::encode_nullable(&self.0, buf)
             }
+            fn check(&self) -> std::result::Result<(), std::boxed::Box<dyn std::error::Error + Send + Sync>> {
+                sqlx::encode::Encode::check(&self.0)
+            }
             fn size_hint(&self) -> usize {
                 sqlx::encode::Encode::size_hint(&self.0)
             }
@@ -192,6 +195,15 @@ fn expand_derive_encode_struct(
             )
         });
 
+        let checks = fields.iter().map(|field| -> Stmt {
+            let id = &field.ident;
+            let ty = &field.ty;
+
+            parse_quote!(
+                <#ty as sqlx::encode::Encode<sqlx::Postgres>>::check(&self. #id)?;
+            )
+        });
+
         let sizes = fields.iter().map(|field| -> Expr {
             let id = &field.ident;
             let ty = &field.ty;
@@ -211,6 +223,12 @@ fn expand_derive_encode_struct(
                     encoder.finish();
                 }
 
+                fn check(&self) -> std::result::Result<(), std::boxed::Box<dyn std::error::Error + Send + Sync>> {
+                    #(#checks)*
+
+                    Ok(())
+                }
+
                 fn size_hint(&self) -> usize {
                     #column_count * (4 + 4) // oid (int) and length (int) for each column
                         + #(#sizes)+* // sum of the size hints for each column
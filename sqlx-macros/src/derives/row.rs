@@ -1,8 +1,8 @@
 use proc_macro2::Span;
 use quote::quote;
 use syn::{
-    parse_quote, punctuated::Punctuated, token::Comma, Data, DataStruct, DeriveInput, Field,
-    Fields, FieldsNamed, Lifetime, Stmt,
+    parse_quote, punctuated::Punctuated, token::Comma, Data, DataStruct, DeriveInput, Expr, Field,
+    Fields, FieldsNamed, Lifetime, Stmt, Type,
 };
 
 use super::attributes::parse_child_attributes;
@@ -65,28 +65,72 @@ fn expand_derive_from_row_struct(
 
     predicates.push(parse_quote!(&#lifetime str: sqlx::row::ColumnIndex<#lifetime, R>));
 
+    let mut reads: Vec<Stmt> = Vec::with_capacity(fields.len());
+
     for field in fields {
+        let id = match &field.ident {
+            Some(id) => id,
+            None => continue,
+        };
+
+        let attributes = parse_child_attributes(&field.attrs)?;
+        let field_s = id.to_string().trim_start_matches("r#").to_owned();
+        let id_s = attributes.rename.unwrap_or_else(|| field_s.clone());
         let ty = &field.ty;
 
-        predicates.push(parse_quote!(#ty: sqlx::decode::Decode<#lifetime, R::Database>));
-        predicates.push(parse_quote!(#ty: sqlx::types::Type<R::Database>));
-    }
+        // how the column is named in a `ColumnDecode` error
+        let index_s = if id_s == field_s {
+            format!("{:?}", id_s)
+        } else {
+            format!("{:?} (field `{}`)", id_s, field_s)
+        };
 
-    let (impl_generics, _, where_clause) = generics.split_for_impl();
+        // the column is decoded as `value` first and converted into the field with `convert`
+        let (value, convert): (Type, Expr) = match (attributes.try_from, attributes.with) {
+            (Some(value), _) => {
+                predicates.push(parse_quote!(#ty: std::convert::TryFrom<#value>));
+                predicates.push(parse_quote!(
+                    <#ty as std::convert::TryFrom<#value>>::Error:
+                        Into<Box<dyn std::error::Error + Send + Sync>>
+                ));
+
+                let convert = parse_quote!(<#ty as std::convert::TryFrom<#value>>::try_from);
+
+                (value, convert)
+            }
 
-    let reads = fields.iter().filter_map(|field| -> Option<Stmt> {
-        let id = &field.ident.as_ref()?;
-        let attributes = parse_child_attributes(&field.attrs).unwrap();
-        let id_s = match attributes.rename {
-            Some(rename) => rename,
-            None => id.to_string().trim_start_matches("r#").to_owned(),
+            (None, Some(with)) => (
+                parse_quote!(#with::Value),
+                parse_quote!(#with::from_row_value),
+            ),
+
+            (None, None) => {
+                predicates.push(parse_quote!(#ty: sqlx::decode::Decode<#lifetime, R::Database>));
+                predicates.push(parse_quote!(#ty: sqlx::types::Type<R::Database>));
+
+                reads.push(parse_quote!(
+                    let #id: #ty = row.try_get(#id_s)?;
+                ));
+
+                continue;
+            }
         };
-        let ty = &field.ty;
 
-        Some(parse_quote!(
-            let #id: #ty = row.try_get(#id_s)?;
-        ))
-    });
+        predicates.push(parse_quote!(#value: sqlx::decode::Decode<#lifetime, R::Database>));
+        predicates.push(parse_quote!(#value: sqlx::types::Type<R::Database>));
+
+        reads.push(parse_quote!(
+            let #id: #ty = #convert(row.try_get::<#value, _>(#id_s)?).map_err(|error| {
+                sqlx::Error::ColumnDecode {
+                    index: #index_s.into(),
+                    source: error.into(),
+                    sql: Some(row.statement().sql().into()),
+                }
+            })?;
+        ));
+    }
+
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
 
     let names = fields.iter().map(|field| &field.ident);
 
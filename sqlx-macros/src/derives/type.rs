@@ -73,6 +73,10 @@ fn expand_derive_has_sql_type_transparent(
             fn type_info() -> DB::TypeInfo {
                 <#ty as sqlx::Type<DB>>::type_info()
             }
+
+            fn compatible(ty: &DB::TypeInfo) -> bool {
+                <#ty as sqlx::Type<DB>>::compatible(ty)
+            }
         }
     ))
 }
@@ -93,6 +97,10 @@ fn expand_derive_has_sql_type_weak_enum(
             fn type_info() -> DB::TypeInfo {
                 <#repr as sqlx::Type<DB>>::type_info()
             }
+
+            fn compatible(ty: &DB::TypeInfo) -> bool {
+                <#repr as sqlx::Type<DB>>::compatible(ty)
+            }
         }
     ))
 }
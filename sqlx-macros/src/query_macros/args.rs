@@ -35,7 +35,29 @@ pub fn quote_args<DB: DatabaseExt>(
                 // TODO: We could remove the ParamChecking flag and just filter to only test params that are non-null
                 let param_ty = param_ty.as_ref().unwrap();
 
-                let param_ty = get_type_override(expr)
+                let type_override = get_type_override(expr);
+
+                // a type given with `as T` is checked against the type of the parameter as the
+                // query is built, as only `Type::compatible` tells which SQL types `T` is for
+                let compatible_check = match (type_override, DB::quote_type_info(param_ty)) {
+                    (Some(ty), Some(type_info)) if !matches!(ty, syn::Type::Infer(_)) => {
+                        let param_num = i + 1;
+
+                        quote_spanned!(expr.span() =>
+                            sqlx::ty_match::assert_type_compatible::<#ty, #db_path>(
+                                #param_num,
+                                &#type_info,
+                            );
+                        )
+                    }
+
+                    _ => TokenStream::new(),
+                };
+
+                let match_unknown = DB::quote_match_unknown(expr.span()).unwrap_or_default();
+
+                let param_ty = type_override
+                    .map(ToTokens::to_token_stream)
                     .or_else(|| {
                         Some(
                             DB::param_type_for_id(&param_ty)?
@@ -66,6 +88,7 @@ pub fn quote_args<DB: DatabaseExt>(
 
                         // if `_expr` is `Option<T>`, get `Option<$ty>`, otherwise `$ty`
                         let ty_check = sqlx::ty_match::WrapSame::<#param_ty, _>::new(&_expr).wrap_same();
+                        #match_unknown
                         // if `_expr` is `&str`, convert `String` to `&str`
                         let (mut ty_check, match_borrow) = sqlx::ty_match::MatchBorrow::new(ty_check, &_expr);
 
@@ -74,6 +97,8 @@ pub fn quote_args<DB: DatabaseExt>(
                         // this causes move-analysis to effectively ignore this block
                         panic!();
                     }
+
+                    #compatible_check
                 ))
             })
             .collect::<crate::Result<TokenStream>>()?
@@ -98,10 +123,12 @@ pub fn quote_args<DB: DatabaseExt>(
     })
 }
 
-fn get_type_override(expr: &Expr) -> Option<TokenStream> {
+fn get_type_override(expr: &Expr) -> Option<&syn::Type> {
     match expr {
-        Expr::Cast(cast) => Some(cast.ty.to_token_stream()),
-        Expr::Type(ascription) => Some(ascription.ty.to_token_stream()),
+        Expr::Cast(cast) => Some(&cast.ty),
+        Expr::Type(ascription) => Some(&ascription.ty),
+        // an argument passed on by `macro_rules!`
+        Expr::Group(group) => get_type_override(&group.expr),
         _ => None,
     }
 }
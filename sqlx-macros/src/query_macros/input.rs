@@ -12,8 +12,11 @@ use syn::{ExprGroup, Token};
 use sqlx::connection::Connection;
 use sqlx::describe::Describe;
 
+use crate::database::DatabaseExt;
 use crate::runtime::fs;
 
+use super::placeholders;
+
 /// Macro input shared by `query!()` and `query_file!()`
 pub struct QueryMacroInput {
     pub(super) source: String,
@@ -78,27 +81,65 @@ impl QueryMacroInput {
 
     /// Run a parse/describe on the query described by this input and validate that it matches the
     /// passed number of args
+    ///
+    /// If either fails and the parameters of the query look like they were written for another
+    /// kind of database, the error says so first.
     pub async fn describe_validate<C: Connection>(
         &self,
         conn: &mut C,
-    ) -> crate::Result<Describe<C::Database>> {
+    ) -> crate::Result<Describe<C::Database>>
+    where
+        C::Database: DatabaseExt,
+    {
+        let hinted =
+            |message: String| match placeholders::mismatch_hint::<C::Database>(&self.source) {
+                Some(hint) => format!("{}\n\n{}", hint, message),
+                None => message,
+            };
+
         let describe = conn
             .describe(&*self.source)
             .await
-            .map_err(|e| syn::Error::new(self.source_span, e))?;
+            .map_err(|e| syn::Error::new(self.source_span, hinted(e.to_string())))?;
 
         if self.arg_names.len() != describe.param_types.len() {
             return Err(syn::Error::new(
                 Span::call_site(),
-                format!(
+                hinted(format!(
                     "expected {} parameters, got {}",
                     describe.param_types.len(),
                     self.arg_names.len()
-                ),
+                )),
             )
             .into());
         }
 
+        // SQLite takes a Postgres-style cast such as `$1::text` as part of the name of the
+        // parameter, which would only lead to confusing errors about its type later on; as that
+        // name could also be intended, the check can be turned off with `SQLX_NO_PARAM_CHECK`
+        if dotenv::var("SQLX_NO_PARAM_CHECK").is_ok() {
+            return Ok(describe);
+        }
+
+        if let Some(name) = describe
+            .param_names
+            .iter()
+            .flatten()
+            .find(|name| name.contains("::"))
+        {
+            if let Some(hint) = placeholders::mismatch_hint::<C::Database>(&self.source) {
+                return Err(syn::Error::new(
+                    self.source_span,
+                    format!(
+                        "{}\n\n`{}` was taken as the name of a single parameter \
+                         (set SQLX_NO_PARAM_CHECK to allow it)",
+                        hint, name
+                    ),
+                )
+                .into());
+            }
+        }
+
         Ok(describe)
     }
 }
@@ -14,6 +14,7 @@ use sqlx::database::Database;
 mod args;
 mod input;
 mod output;
+mod placeholders;
 mod query;
 
 pub async fn expand_query_file<C: Connection>(
@@ -0,0 +1,136 @@
+use crate::database::{DatabaseExt, ParamStyle};
+
+/// Returns a hint for a query that failed to check if its parameters look like they were
+/// written for a different kind of database than the one `DATABASE_URL` points at.
+///
+/// This is only a guess from scanning the query for `$1` and `?`, so it is only used to explain
+/// an error; `?` is an operator in Postgres and SQLite accepts `$1` as well, for example.
+pub(super) fn mismatch_hint<DB: DatabaseExt>(sql: &str) -> Option<String> {
+    let (numbered, anonymous) = count_placeholders(sql);
+
+    match DB::PARAM_STYLE {
+        ParamStyle::Numbered if anonymous > 0 && numbered == 0 => Some(format!(
+            "this query uses MySQL/SQLite-style parameters (`?`) but DATABASE_URL points at \
+             a {} database, which expects `$1`, `$2`, ...",
+            DB::NAME
+        )),
+
+        ParamStyle::Anonymous if numbered > 0 && anonymous == 0 => Some(format!(
+            "this query uses Postgres-style parameters (`$1`, `$2`, ...) but DATABASE_URL \
+             points at a {} database, which expects `?`",
+            DB::NAME
+        )),
+
+        _ => None,
+    }
+}
+
+// Counts the `$N` and the `?` in [sql] outside of strings, quoted identifiers and comments
+fn count_placeholders(sql: &str) -> (usize, usize) {
+    let mut numbered = 0;
+    let mut anonymous = 0;
+
+    let mut chars = sql.chars().peekable();
+    let mut prev = ' ';
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' | '"' | '`' => {
+                // an escaped quote ('') simply starts another string right away
+                for next in chars.by_ref() {
+                    if next == c {
+                        break;
+                    }
+                }
+            }
+
+            '-' if chars.peek() == Some(&'-') => {
+                for next in chars.by_ref() {
+                    if next == '\n' {
+                        break;
+                    }
+                }
+            }
+
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+
+                let mut last = ' ';
+
+                for next in chars.by_ref() {
+                    if last == '*' && next == '/' {
+                        break;
+                    }
+
+                    last = next;
+                }
+            }
+
+            // not the end of an identifier such as `price$1`
+            '$' if !(prev.is_alphanumeric() || prev == '_')
+                && chars.peek().map_or(false, char::is_ascii_digit) =>
+            {
+                numbered += 1;
+            }
+
+            '?' => {
+                anonymous += 1;
+            }
+
+            _ => {}
+        }
+
+        prev = c;
+    }
+
+    (numbered, anonymous)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::count_placeholders;
+
+    #[test]
+    fn it_counts_numbered_placeholders() {
+        assert_eq!(
+            count_placeholders("SELECT $1, $2 FROM t WHERE id = $10"),
+            (3, 0)
+        );
+        assert_eq!(count_placeholders("SELECT $1::int4, $2::text"), (2, 0));
+    }
+
+    #[test]
+    fn it_counts_anonymous_placeholders() {
+        assert_eq!(
+            count_placeholders("SELECT ?, ? FROM t WHERE id = ?"),
+            (0, 3)
+        );
+    }
+
+    #[test]
+    fn it_ignores_placeholders_in_quotes() {
+        assert_eq!(count_placeholders("SELECT '$1', 'it''s ?' FROM t"), (0, 0));
+        assert_eq!(
+            count_placeholders(r#"SELECT "$1?" FROM `t?` WHERE a = ?"#),
+            (0, 1)
+        );
+    }
+
+    #[test]
+    fn it_ignores_placeholders_in_comments() {
+        assert_eq!(
+            count_placeholders("SELECT 1 -- $1 or ?\nWHERE a = $1"),
+            (1, 0)
+        );
+        assert_eq!(count_placeholders("SELECT /* $1 ? */ ?"), (0, 1));
+    }
+
+    #[test]
+    fn it_ignores_dollars_that_are_not_placeholders() {
+        assert_eq!(
+            count_placeholders("SELECT price$1, $$body$$ FROM t"),
+            (0, 0)
+        );
+        assert_eq!(count_placeholders("SELECT 'a'::text, 1::int8"), (0, 0));
+    }
+}
@@ -1,4 +1,7 @@
+use log::{Level, LevelFilter, Log, Metadata, Record};
 use sqlx::{Connect, Database};
+use std::convert::TryFrom;
+use std::sync::{Mutex, Once};
 
 fn setup_if_needed() {
     let _ = dotenv::dotenv();
@@ -16,6 +19,107 @@ where
     Ok(DB::Connection::connect(dotenv::var("DATABASE_URL")?).await?)
 }
 
+// host:port of $DATABASE_URL, for tests that dial the server themselves
+pub fn database_addr(default_port: u16) -> anyhow::Result<String> {
+    setup_if_needed();
+
+    let url = dotenv::var("DATABASE_URL")?;
+    let rest = url.splitn(2, "://").nth(1).unwrap_or_default();
+    let authority = rest
+        .split(|c| c == '/' || c == '?')
+        .next()
+        .unwrap_or_default();
+    let host = authority.rsplit('@').next().unwrap_or_default();
+
+    Ok(if host.contains(':') {
+        host.to_owned()
+    } else {
+        format!("{}:{}", host, default_port)
+    })
+}
+
+// Keeps every record so the logging tests can look for the statements they ran; as the tests
+// of a file run at the same time, each of them runs statements that are unlike any of the others
+struct CapturedLogger(Mutex<Vec<(Level, String)>>);
+
+static LOGGER: CapturedLogger = CapturedLogger(Mutex::new(Vec::new()));
+
+impl Log for CapturedLogger {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        let message = record.args().to_string();
+
+        self.0.lock().unwrap().push((record.level(), message));
+    }
+
+    fn flush(&self) {}
+}
+
+// Install the logger that [logged] looks at, before `new()` would install [env_logger]
+pub fn capture_logs() {
+    static INIT: Once = Once::new();
+
+    INIT.call_once(|| {
+        log::set_logger(&LOGGER).unwrap();
+        log::set_max_level(LevelFilter::Trace);
+    });
+}
+
+// The levels of the records captured since [capture_logs] that mention `marker`
+pub fn logged(marker: &str) -> Vec<Level> {
+    LOGGER
+        .0
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(_, message)| message.contains(marker))
+        .map(|(level, _)| *level)
+        .collect()
+}
+
+// What a fake Postgres server answers a StartupMessage with when it accepts the connection:
+// AuthenticationOk, BackendKeyData and ReadyForQuery (idle)
+pub const PG_STARTUP_OK: &[u8] = b"R\0\0\0\x08\0\0\0\0K\0\0\0\x0c\0\0\0\x2a\0\0\0\x07Z\0\0\0\x05I";
+
+// A field decoded with `#[sqlx(try_from = "..")]` in the tests of `FromRow`
+#[derive(Debug, PartialEq)]
+pub enum Priority {
+    Low,
+    High,
+}
+
+impl TryFrom<i32> for Priority {
+    type Error = String;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Priority::Low),
+            1 => Ok(Priority::High),
+            _ => Err(format!("invalid priority: {}", value)),
+        }
+    }
+}
+
+impl TryFrom<i16> for Priority {
+    type Error = String;
+
+    fn try_from(value: i16) -> Result<Self, Self::Error> {
+        Priority::try_from(i32::from(value))
+    }
+}
+
+// A field decoded with `#[sqlx(with = "sqlx_test::comma_separated")]` in the tests of `FromRow`
+pub mod comma_separated {
+    pub type Value = String;
+
+    pub fn from_row_value(value: String) -> Result<Vec<i32>, std::num::ParseIntError> {
+        value.split(',').map(str::parse).collect()
+    }
+}
+
 // Test type encoding and decoding
 #[macro_export]
 macro_rules! test_type {
@@ -1,14 +1,18 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
 pub use sqlx_core::arguments;
-pub use sqlx_core::connection::{Connect, Connection};
-pub use sqlx_core::cursor::{self, Cursor};
+pub use sqlx_core::cancel::{self, CancelHandle};
+pub use sqlx_core::columnar;
+pub use sqlx_core::connection::{Connect, Connection, StatementCacheMetrics};
+pub use sqlx_core::cursor::{self, Cursor, Either};
 pub use sqlx_core::database::{self, Database};
 pub use sqlx_core::executor::{self, Execute, Executor};
+pub use sqlx_core::logging::{self, LogSettings};
 pub use sqlx_core::pool::{self, Pool};
 pub use sqlx_core::query::{self, query, Query};
 pub use sqlx_core::query_as::{query_as, QueryAs};
 pub use sqlx_core::row::{self, FromRow, Row};
+pub use sqlx_core::statement::{self, Statement};
 pub use sqlx_core::transaction::Transaction;
 pub use sqlx_core::value;
 
@@ -74,6 +74,15 @@
 /// null or is null, respectively. Note that `IS [NOT] NULL` cannot be bound as a parameter either;
 /// you must modify your query string instead.
 ///
+/// ## Type Overrides: Bind Parameters
+/// The type of a bind parameter can be given with `expr as T` instead of the one expected for its
+/// SQL type; e.g., for one of the `NonZero` integers. Only the type of the expression is checked
+/// against `T` at compile time. With Postgres, `T` must also be compatible with the SQL type
+/// of the parameter (see [`Type::compatible`]), which is checked as the query is built: if it is
+/// not, building the query panics before anything is sent.
+///
+/// [`Type::compatible`]: crate::types::Type::compatible
+///
 /// ## Nullability: Output Columns
 /// In most cases, the database engine can tell us whether or not a column may be `NULL`, and
 /// the `query!()` macro adjusts the field types of the returned struct accordingly.
@@ -109,6 +118,10 @@
 ///     * The schema of the database URL (e.g. `postgres://` or `mysql://`) will be used to
 ///       determine the database type.
 ///
+/// * A query for SQLite whose parameters are written for Postgres, such as `$1::text`, is
+/// rejected as SQLite would take the cast as part of the name of the parameter. Set the
+/// `SQLX_NO_PARAM_CHECK` environment variable at build-time if that name is intended.
+///
 /// [dotenv]: https://crates.io/crates/dotenv
 /// ## See Also
 /// * [query_as!] if you want to use a struct you can name,
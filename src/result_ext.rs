@@ -1,4 +1,9 @@
+use std::num::{
+    NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8,
+};
+
 use crate::error::{Error, UnexpectedNullError};
+use crate::types::NonZeroInt;
 
 pub trait ResultExt<T>: Sized {
     fn try_unwrap_optional(self) -> crate::Result<T>;
@@ -33,3 +38,46 @@ impl<T> ResultExt<Option<T>> for crate::Result<T> {
         }
     }
 }
+
+// `query_as!()` decodes a column as the integer type the database reports for it, which is then
+// checked here if the field is one of the `NonZero` types from `std::num`
+macro_rules! impl_result_ext_non_zero {
+    ($($ty:ident($int:ty)),* $(,)?) => {$(
+        impl ResultExt<$ty> for crate::Result<$int> {
+            fn try_unwrap_optional(self) -> crate::Result<$ty> {
+                <$ty as NonZeroInt>::try_from_int(self?)
+            }
+        }
+
+        impl ResultExt<Option<$ty>> for crate::Result<$int> {
+            fn try_unwrap_optional(self) -> crate::Result<Option<$ty>> {
+                ResultExt::<Option<_>>::try_unwrap_optional(self)?
+                    .map(<$ty as NonZeroInt>::try_from_int)
+                    .transpose()
+            }
+        }
+
+        impl ResultExt<$ty> for crate::Result<Option<$int>> {
+            fn try_unwrap_optional(self) -> crate::Result<$ty> {
+                <$ty as NonZeroInt>::try_from_int(ResultExt::<_>::try_unwrap_optional(self)?)
+            }
+        }
+
+        impl ResultExt<Option<$ty>> for crate::Result<Option<$int>> {
+            fn try_unwrap_optional(self) -> crate::Result<Option<$ty>> {
+                self?.map(<$ty as NonZeroInt>::try_from_int).transpose()
+            }
+        }
+    )*};
+}
+
+impl_result_ext_non_zero!(
+    NonZeroI8(i8),
+    NonZeroI16(i16),
+    NonZeroI32(i32),
+    NonZeroI64(i64),
+    NonZeroU8(u8),
+    NonZeroU16(u16),
+    NonZeroU32(u32),
+    NonZeroU64(u64),
+);
@@ -1,5 +1,11 @@
+use std::any::type_name;
 use std::marker::PhantomData;
 
+use sqlx_core::database::Database;
+#[cfg(feature = "postgres")]
+use sqlx_core::postgres::types::PgUnknown;
+use sqlx_core::types::Type;
+
 // These types allow the `query!()` and friends to compare a given parameter's type to
 // an expected parameter type even if the former is behind a reference or in `Option`.
 
@@ -85,6 +91,63 @@ impl<T, U> MatchBorrowExt for &'_ MatchBorrow<T, U> {
     type Matched = U;
 }
 
+// [postgres] a `PgUnknown` (or an `Option` of one) is accepted for a parameter of any type, as
+// postgres infers the type of the string from the query; this replaces the expected type with the
+// type of the argument when it is one, and leaves it alone otherwise
+#[cfg(feature = "postgres")]
+pub struct MatchUnknown<T, U>(PhantomData<T>, PhantomData<U>);
+
+#[cfg(feature = "postgres")]
+impl<T, U> MatchUnknown<T, U> {
+    pub fn new(t: T, _u: &U) -> (T, Self) {
+        (t, MatchUnknown(PhantomData, PhantomData))
+    }
+}
+
+#[cfg(feature = "postgres")]
+pub trait MatchUnknownExt: Sized {
+    type Matched;
+
+    fn match_unknown(self) -> Self::Matched {
+        panic!("only for type resolution")
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<T, U: AsRef<str>> MatchUnknownExt for MatchUnknown<T, PgUnknown<U>> {
+    type Matched = PgUnknown<U>;
+}
+
+#[cfg(feature = "postgres")]
+impl<T, U: AsRef<str>> MatchUnknownExt for MatchUnknown<Option<T>, Option<PgUnknown<U>>> {
+    type Matched = Option<PgUnknown<U>>;
+}
+
+#[cfg(feature = "postgres")]
+impl<T, U> MatchUnknownExt for &'_ MatchUnknown<T, U> {
+    type Matched = T;
+}
+
+// A parameter whose type is given with `expr as T` is only checked to be a `T`; this checks that
+// `T` can be bound in place of the type the database expects for the parameter, as the query is
+// built (and before anything is sent)
+pub fn assert_type_compatible<T, DB>(param: usize, expected: &DB::TypeInfo)
+where
+    T: Type<DB> + ?Sized,
+    DB: Database,
+{
+    if !T::compatible(expected) {
+        panic!(
+            "the type of param #{} is overridden as `{}` (SQL type {}), which is not compatible \
+             with its SQL type {}",
+            param,
+            type_name::<T>(),
+            T::type_info(),
+            expected
+        );
+    }
+}
+
 pub fn conjure_value<T>() -> T {
     panic!()
 }
@@ -120,3 +183,18 @@ fn test_match_borrow() {
         let _: &str = match_borrow.match_borrow();
     }
 }
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_match_unknown() {
+    if false {
+        let (_, match_unknown) = MatchUnknown::new(0i32, &PgUnknown("42"));
+        let _: PgUnknown<&str> = match_unknown.match_unknown();
+
+        let (_, match_unknown) = MatchUnknown::new(Some(0i32), &Some(PgUnknown(String::new())));
+        let _: Option<PgUnknown<String>> = match_unknown.match_unknown();
+
+        let (_, match_unknown) = MatchUnknown::new(0i32, &String::new());
+        let _: i32 = match_unknown.match_unknown();
+    }
+}
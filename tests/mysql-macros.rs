@@ -1,5 +1,6 @@
 use sqlx::MySql;
 use sqlx_test::new;
+use std::num::NonZeroI64;
 
 #[cfg_attr(feature = "runtime-async-std", async_std::test)]
 #[cfg_attr(feature = "runtime-tokio", tokio::test)]
@@ -97,3 +98,40 @@ async fn test_query_bytes() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_query_as_non_zero() -> anyhow::Result<()> {
+    #[derive(Debug)]
+    struct Account {
+        id: NonZeroI64,
+        parent_id: Option<NonZeroI64>,
+    }
+
+    let mut conn = new::<MySql>().await?;
+
+    let id = NonZeroI64::new(1).unwrap();
+
+    let account = sqlx::query_as!(
+        Account,
+        "SELECT * from (select cast(1 as signed) as id, cast(null as signed) as parent_id) accounts where id = ?",
+        id as NonZeroI64
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(account.id, id);
+    assert_eq!(account.parent_id, None);
+
+    let err = sqlx::query_as!(
+        Account,
+        "SELECT * from (select cast(0 as signed) as id, cast(null as signed) as parent_id) accounts"
+    )
+    .fetch_one(&mut conn)
+    .await
+    .unwrap_err();
+
+    assert!(err.to_string().contains("NonZeroI64"), "{}", err);
+
+    Ok(())
+}
@@ -1,7 +1,7 @@
 extern crate time_ as time;
 
 use sqlx::MySql;
-use sqlx_test::test_type;
+use sqlx_test::{new, test_type};
 
 test_type!(null(
     MySql,
@@ -52,6 +52,109 @@ test_type!(bytes(
         == vec![0_u8, 0, 0, 0, 0x52]
 ));
 
+mod non_zero {
+    use std::num::{NonZeroI64, NonZeroU64, NonZeroU8};
+
+    use sqlx::mysql::MySqlQueryAs;
+
+    use super::*;
+
+    test_type!(non_zero_u8(
+        MySql,
+        NonZeroU8,
+        "CAST(253 AS UNSIGNED)" == NonZeroU8::new(253).unwrap()
+    ));
+
+    test_type!(non_zero_u64(
+        MySql,
+        NonZeroU64,
+        "CAST(2141512 AS UNSIGNED)" == NonZeroU64::new(2141512).unwrap()
+    ));
+
+    test_type!(non_zero_i64(
+        MySql,
+        NonZeroI64,
+        "-2141512" == NonZeroI64::new(-2141512).unwrap()
+    ));
+
+    #[cfg_attr(feature = "runtime-async-std", async_std::test)]
+    #[cfg_attr(feature = "runtime-tokio", tokio::test)]
+    async fn it_rejects_zero_for_non_zero_integers() -> anyhow::Result<()> {
+        let mut conn = new::<MySql>().await?;
+
+        let (id,): (Option<NonZeroI64>,) = sqlx::query_as("SELECT CAST(NULL AS SIGNED)")
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(id, None);
+
+        let err = sqlx::query_as::<_, (NonZeroI64,)>("SELECT CAST(0 AS SIGNED)")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap_err();
+
+        assert!(err.to_string().contains("NonZeroI64"), "{}", err);
+
+        Ok(())
+    }
+}
+
+mod std_time {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use super::*;
+
+    test_type!(system_time(
+        MySql,
+        SystemTime,
+        "TIMESTAMP '2019-01-02 05:10:20.115100'"
+            == UNIX_EPOCH + Duration::new(1_546_405_820, 115_100_000),
+        "TIMESTAMP '2019-01-02 00:00:00'" == UNIX_EPOCH + Duration::from_secs(1_546_387_200),
+        // before the UNIX epoch
+        "TIMESTAMP '1969-07-20 20:17:40.5'" == UNIX_EPOCH - Duration::from_millis(14_182_939_500)
+    ));
+
+    test_type!(duration(
+        MySql,
+        Duration,
+        "TIME '00:00:00'" == Duration::from_secs(0),
+        "TIME '26:03:04.000005'" == Duration::new(93_784, 5_000),
+        "TIME '838:59:59'" == Duration::from_secs(3_020_399)
+    ));
+
+    #[cfg_attr(feature = "runtime-async-std", async_std::test)]
+    #[cfg_attr(feature = "runtime-tokio", tokio::test)]
+    async fn it_fails_to_encode_values_out_of_range() -> anyhow::Result<()> {
+        use sqlx::mysql::MySqlQueryAs;
+
+        let mut conn = new::<MySql>().await?;
+
+        let err = sqlx::query("SELECT ?")
+            .bind(Duration::from_secs(3_020_400))
+            .execute(&mut conn)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, sqlx::Error::Encode(_)), "{}", err);
+
+        // 65536-01-01 00:00:00
+        let err = sqlx::query("SELECT ?")
+            .bind(UNIX_EPOCH + Duration::from_secs(2_005_949_145_600))
+            .execute(&mut conn)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, sqlx::Error::Encode(_)), "{}", err);
+
+        // nothing was sent
+        let (one,): (i32,) = sqlx::query_as("SELECT 1").fetch_one(&mut conn).await?;
+
+        assert_eq!(one, 1);
+
+        Ok(())
+    }
+}
+
 #[cfg(feature = "chrono")]
 mod chrono {
     use super::*;
@@ -1,5 +1,9 @@
 use futures::TryStreamExt;
-use sqlx::{mysql::MySqlQueryAs, Connection, Executor, MySql, MySqlPool};
+use sqlx::types::TypeInfo;
+use sqlx::{
+    mysql::{MySqlQueryAs, MySqlRow},
+    Connection, Either, Executor, MySql, MySqlConnection, MySqlPool, Row,
+};
 use sqlx_test::new;
 use std::time::Duration;
 
@@ -133,6 +137,28 @@ async fn test_describe() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_describes_parameters() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let describe = conn.describe("select ? + 1, cast(? as unsigned)").await?;
+
+    assert_eq!(describe.param_types.len(), 2);
+    assert_eq!(*describe.param_names, [None, None]);
+
+    assert_eq!(
+        describe.result_columns[1]
+            .type_info
+            .as_ref()
+            .unwrap()
+            .name(),
+        "BIGINT UNSIGNED"
+    );
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "runtime-async-std", async_std::test)]
 #[cfg_attr(feature = "runtime-tokio", tokio::test)]
 async fn pool_immediately_fails_with_db_error() -> anyhow::Result<()> {
@@ -232,3 +258,351 @@ async fn test_fetch_one_and_ping() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_connects_with_a_provided_stream() -> anyhow::Result<()> {
+    #[cfg(feature = "runtime-tokio")]
+    use tokio::net::TcpStream;
+
+    #[cfg(feature = "runtime-async-std")]
+    use async_std::net::TcpStream;
+
+    let stream = TcpStream::connect(sqlx_test::database_addr(3306)?).await?;
+    let mut conn =
+        MySqlConnection::connect_with_stream(stream, &*dotenv::var("DATABASE_URL")?).await?;
+
+    let (value,): (i64,) = sqlx::query_as("SELECT 1 + 1").fetch_one(&mut conn).await?;
+
+    assert_eq!(value, 2);
+
+    conn.close().await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_counts_the_rows_matched_by_a_batch_of_statements() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute(
+        "CREATE TEMPORARY TABLE batch (id INT PRIMARY KEY AUTO_INCREMENT, value TEXT NOT NULL)",
+    )
+    .await?;
+
+    // the connection asks for `CLIENT_FOUND_ROWS` so an UPDATE counts the rows it matched,
+    // including those it leaves as they were, and the counts of the statements are added up
+    let affected = conn
+        .execute(
+            r#"
+INSERT INTO batch (value) VALUES ('a');
+INSERT INTO batch (value) VALUES ('b');
+UPDATE batch SET value = value;
+            "#,
+        )
+        .await?;
+
+    assert_eq!(affected, 4);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_fetches_the_rows_and_affected_counts_of_every_statement() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let results: Vec<Either<u64, i32>> = conn
+        .fetch_many(
+            r#"
+CREATE TEMPORARY TABLE fetch_many (id INTEGER PRIMARY KEY);
+INSERT INTO fetch_many (id) VALUES (1), (2);
+SELECT id FROM fetch_many ORDER BY id;
+UPDATE fetch_many SET id = id;
+            "#,
+            |row: MySqlRow| row.try_get(0),
+        )
+        .map_ok(|result| result.map_left(|result| result.rows_affected()))
+        .try_collect()
+        .await?;
+
+    assert_eq!(
+        results,
+        [
+            Either::Left(0),
+            Either::Left(2),
+            Either::Right(1),
+            Either::Right(2),
+            Either::Left(0),
+            // `CLIENT_FOUND_ROWS` counts the rows matched, even if they are left as they were
+            Either::Left(2),
+        ]
+    );
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_stops_fetching_many_at_the_first_error() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE fetch_many (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    let mut results = conn.fetch_many(
+        r#"
+INSERT INTO fetch_many (id) VALUES (1);
+INSERT INTO fetch_many (id) VALUES (1);
+INSERT INTO fetch_many (id) VALUES (2);
+        "#,
+        |row: MySqlRow| row.try_get::<i32, _>(0),
+    );
+
+    let result = results.try_next().await?.and_then(Either::left);
+
+    assert_eq!(result.map(|result| result.rows_affected()), Some(1));
+    assert!(results.try_next().await.is_err());
+    assert!(results.try_next().await?.is_none());
+
+    drop(results);
+
+    // with autocommit, the INSERT before the one that failed is kept while those after it are
+    // not run; the connection can still be used
+    let ids: Vec<(i32,)> = sqlx::query_as("SELECT id FROM fetch_many")
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(ids, [(1,)]);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_discards_the_rows_after_the_first_one() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let query = "SELECT 1 AS n UNION ALL SELECT 2 UNION ALL SELECT 3";
+
+    let first = sqlx::query(query)
+        .map(|row: MySqlRow| row.get::<i64, _>(0))
+        .fetch_optional(&mut conn)
+        .await?;
+
+    assert_eq!(first, Some(1));
+
+    let (first,): (i64,) = sqlx::query_as(&format!("SELECT n + ? FROM ({}) t", query))
+        .bind(10_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(first, 11);
+
+    let (text,): (String,) = sqlx::query_as("SELECT 'unrelated'")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(text, "unrelated");
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_calls_a_procedure_with_out_params() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute(
+        r#"
+DROP PROCEDURE IF EXISTS sqlx_totals;
+CREATE PROCEDURE sqlx_totals(IN base INT, OUT n INT, OUT label TEXT)
+BEGIN
+    SELECT base + 1 AS value UNION ALL SELECT base + 2;
+    SELECT 'second' AS name;
+    SET n = base * 10;
+    SET label = 'done';
+END;
+        "#,
+    )
+    .await?;
+
+    let mut call = conn
+        .call_procedure("sqlx_totals")
+        .bind(4_i32)
+        .bind_out()
+        .bind_out()
+        .fetch_many();
+
+    let mut values = Vec::new();
+    let mut names = Vec::new();
+
+    while let Some(result) = call.next_many().await? {
+        if let Either::Right(row) = result {
+            match row.statement().columns()[0].name.as_deref() {
+                Some("value") => values.push(row.get::<i64, _>(0)),
+                _ => names.push(row.get::<String, _>(0)),
+            }
+        }
+    }
+
+    assert_eq!(values, [5, 6]);
+    assert_eq!(names, ["second"]);
+
+    let (n, label): (i64, String) = call.out_params().await?;
+
+    assert_eq!(n, 40);
+    assert_eq!(label, "done");
+
+    // the result sets can be skipped over as well
+    let (n, label): (i64, String) = conn
+        .call_procedure("sqlx_totals")
+        .bind(7_i32)
+        .bind_out()
+        .bind_out()
+        .out_params()
+        .await?;
+
+    assert_eq!(n, 70);
+    assert_eq!(label, "done");
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_can_be_used_after_a_procedure_fails() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute(
+        r#"
+DROP PROCEDURE IF EXISTS sqlx_fails;
+CREATE PROCEDURE sqlx_fails(OUT n INT)
+BEGIN
+    SELECT 1 AS value UNION ALL SELECT 2;
+    SIGNAL SQLSTATE '45000' SET MESSAGE_TEXT = 'sqlx_fails';
+    SET n = 1;
+END;
+        "#,
+    )
+    .await?;
+
+    let res = conn
+        .call_procedure("sqlx_fails")
+        .bind_out()
+        .out_params::<(i64,)>()
+        .await;
+
+    assert!(res.unwrap_err().to_string().contains("sqlx_fails"));
+
+    let (text,): (String,) = sqlx::query_as("SELECT 'unrelated'")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(text, "unrelated");
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_fails_to_get_the_out_params_of_a_call_without_any() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute(
+        r#"
+DROP PROCEDURE IF EXISTS sqlx_no_out;
+CREATE PROCEDURE sqlx_no_out()
+BEGIN
+    SELECT 1 AS value;
+END;
+        "#,
+    )
+    .await?;
+
+    let res = conn
+        .call_procedure("sqlx_no_out")
+        .out_params::<(i64,)>()
+        .await;
+
+    assert!(matches!(
+        res,
+        Err(sqlx::Error::ColumnIndexOutOfBounds { index: 0, len: 0 })
+    ));
+
+    conn.call_procedure("sqlx_no_out")
+        .fetch_many()
+        .finish()
+        .await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_discards_the_results_of_a_procedure_that_are_not_read() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute(
+        r#"
+DROP PROCEDURE IF EXISTS sqlx_selects;
+CREATE PROCEDURE sqlx_selects()
+BEGIN
+    SELECT 1 AS value UNION ALL SELECT 2;
+    SELECT 3 AS value UNION ALL SELECT 4;
+END;
+        "#,
+    )
+    .await?;
+
+    let mut call = conn.call_procedure("sqlx_selects").fetch_many();
+
+    assert!(call.next_many().await?.is_some());
+
+    drop(call);
+
+    let (text,): (String,) = sqlx::query_as("SELECT 'unrelated'")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(text, "unrelated");
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_cancels_a_running_query() -> anyhow::Result<()> {
+    #[cfg(feature = "runtime-tokio")]
+    use tokio::{task::spawn, time::delay_for as sleep};
+
+    #[cfg(feature = "runtime-async-std")]
+    use async_std::task::{sleep, spawn};
+
+    let mut conn = new::<MySql>().await?;
+
+    let (query, handle) = sqlx::query("SELECT SLEEP(30), ?").bind(1_i32).cancellable();
+
+    spawn(async move {
+        sleep(Duration::from_millis(200)).await;
+        handle.cancel();
+    });
+
+    let started = std::time::Instant::now();
+
+    let res = query.execute(&mut conn).await;
+
+    assert!(matches!(res, Err(sqlx::Error::Cancelled)), "{:?}", res);
+
+    // mysql was told to kill the query instead of letting it run to the end
+    assert!(started.elapsed() < Duration::from_secs(10));
+
+    let (text,): (String,) = sqlx::query_as("SELECT 'unrelated'")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(text, "unrelated");
+
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use sqlx::{postgres::PgQueryAs, Connection, Cursor, Executor, FromRow, Postgres};
-use sqlx_test::{new, test_type};
+use sqlx_test::{new, test_type, Priority};
 use std::fmt::Debug;
 
 // Transparent types are rust-side wrappers over DB types
@@ -325,3 +325,72 @@ async fn test_from_row_with_rename() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg(feature = "macros")]
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_from_row_with_conversions() -> anyhow::Result<()> {
+    #[derive(Debug, sqlx::FromRow)]
+    struct Task {
+        #[sqlx(try_from = "i16")]
+        priority: Priority,
+
+        #[sqlx(try_from = "i16")]
+        progress: u8,
+
+        #[sqlx(rename = "tags", with = "sqlx_test::comma_separated")]
+        tag_ids: Vec<i32>,
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    let task: Task = sqlx::query_as(
+        "SELECT * from (VALUES (1::int2, 42::int2, '1,2,3')) tasks(priority, progress, tags)",
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(task.priority, Priority::High);
+    assert_eq!(task.progress, 42);
+    assert_eq!(task.tag_ids, vec![1, 2, 3]);
+
+    // a value out of range of the field fails the conversion
+    let err = sqlx::query_as::<_, Task>(
+        "SELECT * from (VALUES (1::int2, 300::int2, '1,2,3')) tasks(priority, progress, tags)",
+    )
+    .fetch_one(&mut conn)
+    .await
+    .unwrap_err();
+
+    assert!(matches!(err, sqlx::Error::ColumnDecode { .. }), "{:?}", err);
+    assert_eq!(
+        err.to_string(),
+        "error decoding column \"progress\": out of range integral type conversion attempted\n\nin the results of: SELECT * from (VALUES (1::int2, 300::int2, '1,2,3')) tasks(priority, progress, tags)"
+    );
+
+    let err = sqlx::query_as::<_, Task>(
+        "SELECT * from (VALUES (7::int2, 42::int2, '1,2,3')) tasks(priority, progress, tags)",
+    )
+    .fetch_one(&mut conn)
+    .await
+    .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "error decoding column \"priority\": invalid priority: 7\n\nin the results of: SELECT * from (VALUES (7::int2, 42::int2, '1,2,3')) tasks(priority, progress, tags)"
+    );
+
+    let err = sqlx::query_as::<_, Task>(
+        "SELECT * from (VALUES (1::int2, 42::int2, '1,x')) tasks(priority, progress, tags)",
+    )
+    .fetch_one(&mut conn)
+    .await
+    .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "error decoding column \"tags\" (field `tag_ids`): invalid digit found in string\n\nin the results of: SELECT * from (VALUES (1::int2, 42::int2, '1,x')) tasks(priority, progress, tags)"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,178 @@
+use std::time::Duration;
+
+use log::{Level, LevelFilter};
+use sqlx::postgres::PgRow;
+use sqlx::{Connection, Cursor, Executor, LogSettings, Postgres};
+use sqlx_test::{capture_logs, logged, new};
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_logs_statements() -> anyhow::Result<()> {
+    capture_logs();
+
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("SELECT 'it_logs_statements'").await?;
+
+    sqlx::query("SELECT 'it_logs_statements', $1")
+        .bind(1_i32)
+        .execute(&mut conn)
+        .await?;
+
+    assert_eq!(
+        logged("it_logs_statements"),
+        vec![Level::Debug, Level::Debug]
+    );
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_does_not_log_queries_with_no_logging() -> anyhow::Result<()> {
+    capture_logs();
+
+    let mut conn = new::<Postgres>().await?;
+
+    sqlx::query("SELECT 'it_does_not_log_queries_with_no_logging', $1")
+        .bind(1_i32)
+        .no_logging()
+        .execute(&mut conn)
+        .await?;
+
+    sqlx::query("SELECT 'it_does_not_log_queries_with_no_logging'")
+        .map(|_: PgRow| ())
+        .no_logging()
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert!(logged("it_does_not_log_queries_with_no_logging").is_empty());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_logs_queries_at_their_log_level() -> anyhow::Result<()> {
+    capture_logs();
+
+    let mut conn = new::<Postgres>().await?;
+
+    sqlx::query("SELECT 'it_logs_queries_at_their_log_level'")
+        .log_level(Some(LevelFilter::Info))
+        .execute(&mut conn)
+        .await?;
+
+    assert_eq!(
+        logged("it_logs_queries_at_their_log_level"),
+        vec![Level::Info]
+    );
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_only_logs_statements_allowed_by_the_filter() -> anyhow::Result<()> {
+    capture_logs();
+
+    let mut conn = new::<Postgres>().await?;
+
+    conn.set_log_settings(
+        LogSettings::default()
+            .log_statements(LevelFilter::Trace)
+            .filter(|sql| !sql.contains("heartbeat")),
+    );
+
+    conn.execute("SELECT 'it_only_logs_statements_allowed_by_the_filter'")
+        .await?;
+
+    conn.execute("SELECT 'it_only_logs_statements_allowed_by_the_filter (heartbeat)'")
+        .await?;
+
+    // the filter applies to prepared statements as well
+    sqlx::query("SELECT 'it_only_logs_statements_allowed_by_the_filter (heartbeat)', $1")
+        .bind(1_i32)
+        .execute(&mut conn)
+        .await?;
+
+    assert_eq!(
+        logged("it_only_logs_statements_allowed_by_the_filter"),
+        vec![Level::Trace]
+    );
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_logs_slow_statements() -> anyhow::Result<()> {
+    capture_logs();
+
+    let mut conn = new::<Postgres>().await?;
+
+    conn.set_log_settings(
+        LogSettings::default().log_slow_statements(LevelFilter::Warn, Duration::from_millis(50)),
+    );
+
+    conn.execute("SELECT 'it_logs_slow_statements'").await?;
+
+    conn.execute("SELECT 'it_logs_slow_statements' FROM pg_sleep(0.1)")
+        .await?;
+
+    assert_eq!(
+        logged("it_logs_slow_statements"),
+        vec![Level::Debug, Level::Warn]
+    );
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_logs_slow_statements_of_a_cursor_once_it_is_exhausted() -> anyhow::Result<()> {
+    capture_logs();
+
+    let mut conn = new::<Postgres>().await?;
+
+    conn.set_log_settings(
+        LogSettings::default().log_slow_statements(LevelFilter::Warn, Duration::from_millis(50)),
+    );
+
+    let mut cursor = conn.fetch(
+        "SELECT 'it_logs_slow_statements_of_a_cursor_once_it_is_exhausted' FROM pg_sleep(0.1)",
+    );
+
+    assert!(logged("it_logs_slow_statements_of_a_cursor").is_empty());
+
+    while cursor.next().await?.is_some() {}
+
+    assert_eq!(
+        logged("it_logs_slow_statements_of_a_cursor"),
+        vec![Level::Warn]
+    );
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_applies_the_log_settings_of_a_pool() -> anyhow::Result<()> {
+    capture_logs();
+
+    let pool = sqlx::PgPool::builder()
+        .log_settings(LogSettings::default().log_statements(LevelFilter::Info))
+        .build(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    (&pool)
+        .execute("SELECT 'it_applies_the_log_settings_of_a_pool'")
+        .await?;
+
+    assert_eq!(
+        logged("it_applies_the_log_settings_of_a_pool"),
+        vec![Level::Info]
+    );
+
+    Ok(())
+}
@@ -2,6 +2,7 @@ use sqlx::Postgres;
 use sqlx_test::new;
 
 use futures::TryStreamExt;
+use std::num::NonZeroI64;
 
 #[cfg_attr(feature = "runtime-async-std", async_std::test)]
 #[cfg_attr(feature = "runtime-tokio", tokio::test)]
@@ -200,6 +201,57 @@ async fn test_nullable_err() -> anyhow::Result<()> {
     panic!("expected `UnexpectedNullError`, got {}", err)
 }
 
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_query_as_non_zero() -> anyhow::Result<()> {
+    #[derive(Debug)]
+    struct Account {
+        id: NonZeroI64,
+        parent_id: Option<NonZeroI64>,
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    let id = NonZeroI64::new(1).unwrap();
+
+    let account = sqlx::query_as!(
+        Account,
+        "SELECT * from (VALUES (1::int8, null::int8)) accounts(id, parent_id) where id = $1",
+        id as NonZeroI64
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(account.id, id);
+    assert_eq!(account.parent_id, None);
+
+    let err = sqlx::query_as!(
+        Account,
+        "SELECT * from (VALUES (0::int8, null::int8)) accounts(id, parent_id)"
+    )
+    .fetch_one(&mut conn)
+    .await
+    .unwrap_err();
+
+    assert!(err.to_string().contains("NonZeroI64"), "{}", err);
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(
+    expected = "the type of param #1 is overridden as `i64` (SQL type INT8), which is not compatible with its SQL type INT4"
+)]
+fn test_query_panics_on_an_incompatible_type_override() {
+    let id = 1_i64;
+
+    // the query is never run; building it is enough to check the type of `id`
+    let _ = sqlx::query!(
+        "SELECT * from (VALUES (1::int4)) accounts(id) where id = $1",
+        id as i64
+    );
+}
+
 #[cfg_attr(feature = "runtime-async-std", async_std::test)]
 #[cfg_attr(feature = "runtime-tokio", tokio::test)]
 async fn test_many_args() -> anyhow::Result<()> {
@@ -264,3 +316,63 @@ async fn fetch_is_usable_issue_224() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+// `SystemTime` and `Duration` are only picked when no date/time crate is enabled
+#[cfg(not(any(feature = "chrono", feature = "time")))]
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_std_time() -> anyhow::Result<()> {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    let mut conn = new::<Postgres>().await?;
+
+    let at = UNIX_EPOCH + Duration::from_millis(1_546_405_820_115);
+
+    let rec = sqlx::query!(
+        "SELECT $1::timestamptz as at, $1::timestamptz + $2::interval as later, $2::interval as step",
+        at,
+        Duration::from_secs(90)
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    let later: Option<SystemTime> = rec.later;
+    let step: Option<Duration> = rec.step;
+
+    assert_eq!(rec.at, Some(at));
+    assert_eq!(later, Some(at + Duration::from_secs(90)));
+    assert_eq!(step, Some(Duration::from_secs(90)));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_unspecified_string_args() -> anyhow::Result<()> {
+    use sqlx::postgres::types::PgUnknown;
+
+    let mut conn = new::<Postgres>().await?;
+
+    // `$1` is described as an `INT4` yet any string is accepted for it
+    let value = String::from("41");
+
+    let rec = sqlx::query!("SELECT coalesce($1, 0) + 1 as value", PgUnknown(&value))
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(rec.value, Some(42));
+
+    // the `?` operator of `jsonb` is left alone
+    let rec = sqlx::query!(
+        r#"SELECT '{"a": 1}'::jsonb ? $1 as has_a, '{"a": 1}'::jsonb ? $2 as has_b"#,
+        PgUnknown("a"),
+        PgUnknown(String::from("b"))
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(rec.has_a, Some(true));
+    assert_eq!(rec.has_b, Some(false));
+
+    Ok(())
+}
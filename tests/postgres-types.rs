@@ -99,6 +99,41 @@ test_type!(bytea(
         == vec![0_u8, 0, 0, 0, 0x52]
 ));
 
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_unprepared_bytea_output() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let expected: Vec<u8> = vec![0, b'\\', b'x', 0x7F, 0x80, 0xFF, b' ', b'\'', 0];
+
+    for format in &["hex", "escape"] {
+        conn.execute(&*format!("SET bytea_output = '{}'", format))
+            .await?;
+
+        // without parameters this uses the simple query protocol, which returns text
+        let mut cursor = conn.fetch("SELECT '\\x005c787f80ff202700'::bytea AS bytes");
+        let row = cursor.next().await?.unwrap();
+
+        assert_eq!(row.try_get::<Vec<u8>, _>("bytes")?, expected, "{}", format);
+    }
+
+    // the server only sends well-formed BYTEA, so the text of one with an odd number of hex
+    // digits is decoded without checking its type
+    let mut cursor = conn.fetch("SELECT '\\x5c7'::text AS bytes");
+    let row = cursor.next().await?.unwrap();
+    let err = row.try_get_unchecked::<Vec<u8>, _>("bytes").unwrap_err();
+    let message = err.to_string();
+
+    assert!(message.contains("\"bytes\""), "{}", message);
+    assert!(
+        message.contains("invalid BYTEA in the hex format"),
+        "{}",
+        message
+    );
+
+    Ok(())
+}
+
 // PgNumeric only works on the wire protocol
 test_prepared_type!(numeric(
     Postgres,
@@ -259,6 +294,134 @@ array_macro_test!(ipnetwork(
         ]
 ));
 
+mod non_zero {
+    use std::num::{NonZeroI16, NonZeroI32, NonZeroI64, NonZeroU32};
+
+    use super::*;
+
+    test_type!(non_zero_i16(
+        Postgres,
+        NonZeroI16,
+        "821::smallint" == NonZeroI16::new(821).unwrap()
+    ));
+
+    test_type!(non_zero_i32(
+        Postgres,
+        NonZeroI32,
+        "-94101::int" == NonZeroI32::new(-94101).unwrap()
+    ));
+
+    test_type!(non_zero_u32(
+        Postgres,
+        NonZeroU32,
+        "94101::oid" == NonZeroU32::new(94101).unwrap()
+    ));
+
+    test_type!(non_zero_i64(
+        Postgres,
+        NonZeroI64,
+        "9358295312::bigint" == NonZeroI64::new(9358295312).unwrap()
+    ));
+
+    #[cfg_attr(feature = "runtime-async-std", async_std::test)]
+    #[cfg_attr(feature = "runtime-tokio", tokio::test)]
+    async fn it_rejects_zero_for_non_zero_integers() -> anyhow::Result<()> {
+        let mut conn = new::<Postgres>().await?;
+
+        let (id,): (Option<NonZeroI64>,) = sqlx::query_as("SELECT NULL::bigint")
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(id, None);
+
+        let err = sqlx::query_as::<_, (NonZeroI64,)>("SELECT 0::bigint")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap_err();
+
+        assert!(err.to_string().contains("NonZeroI64"), "{}", err);
+
+        Ok(())
+    }
+}
+
+mod std_time {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use super::*;
+
+    test_type!(system_time(
+        Postgres,
+        SystemTime,
+        "TIMESTAMPTZ '2019-01-02 05:10:20.115100+00'"
+            == UNIX_EPOCH + Duration::new(1_546_405_820, 115_100_000),
+        "TIMESTAMPTZ '2019-01-02 07:10:20+02'" == UNIX_EPOCH + Duration::from_secs(1_546_405_820),
+        // before the UNIX epoch
+        "TIMESTAMPTZ '1969-07-20 20:17:40.5+00'"
+            == UNIX_EPOCH - Duration::from_millis(14_182_939_500)
+    ));
+
+    test_type!(duration(
+        Postgres,
+        Duration,
+        "INTERVAL '0'" == Duration::from_secs(0),
+        "INTERVAL '1 day 02:03:04.000005'" == Duration::new(93_784, 5_000),
+        "INTERVAL '838:59:59.5'" == Duration::from_millis(3_020_399_500)
+    ));
+
+    #[cfg_attr(feature = "runtime-async-std", async_std::test)]
+    #[cfg_attr(feature = "runtime-tokio", tokio::test)]
+    async fn it_rejects_intervals_with_months() -> anyhow::Result<()> {
+        let mut conn = new::<Postgres>().await?;
+
+        let err = sqlx::query_as::<_, (Duration,)>("SELECT INTERVAL '1 month'")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap_err();
+
+        assert!(err.to_string().contains("months"), "{}", err);
+
+        let err = sqlx::query_as::<_, (Duration,)>("SELECT INTERVAL '-1 second'")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap_err();
+
+        assert!(err.to_string().contains("negative"), "{}", err);
+
+        Ok(())
+    }
+
+    #[cfg_attr(feature = "runtime-async-std", async_std::test)]
+    #[cfg_attr(feature = "runtime-tokio", tokio::test)]
+    async fn it_fails_to_encode_values_out_of_range() -> anyhow::Result<()> {
+        let mut conn = new::<Postgres>().await?;
+
+        let err = sqlx::query("SELECT $1")
+            .bind(Duration::from_secs(u64::MAX))
+            .execute(&mut conn)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, sqlx::Error::Encode(_)), "{}", err);
+
+        // more than `i64::MAX` microseconds after 2000-01-01
+        let err = sqlx::query("SELECT $1")
+            .bind(UNIX_EPOCH + Duration::from_secs(9_223_372_036_855 + 946_684_800))
+            .execute(&mut conn)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, sqlx::Error::Encode(_)), "{}", err);
+
+        // nothing was sent
+        let (one,): (i32,) = sqlx::query_as("SELECT 1").fetch_one(&mut conn).await?;
+
+        assert_eq!(one, 1);
+
+        Ok(())
+    }
+}
+
 #[cfg(feature = "chrono")]
 mod chrono {
     use sqlx::types::chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
@@ -620,6 +783,63 @@ END $$;
     Ok(())
 }
 
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_decodes_a_type_from_every_type_it_is_compatible_with() -> anyhow::Result<()> {
+    use sqlx::value::RawValue;
+
+    // Any integer, which Postgres does not consider compatible with each other
+    #[derive(Debug, PartialEq)]
+    struct AnyInt(i64);
+
+    impl Type<Postgres> for AnyInt {
+        fn type_info() -> PgTypeInfo {
+            <i64 as Type<Postgres>>::type_info()
+        }
+
+        fn compatible(ty: &PgTypeInfo) -> bool {
+            <i16 as Type<Postgres>>::compatible(ty)
+                || <i32 as Type<Postgres>>::compatible(ty)
+                || <i64 as Type<Postgres>>::compatible(ty)
+        }
+    }
+
+    impl<'de> Decode<'de, Postgres> for AnyInt {
+        fn decode(value: PgValue<'de>) -> sqlx::Result<Self> {
+            match value.type_info() {
+                Some(ty) if <i16 as Type<Postgres>>::compatible(&ty) => {
+                    <i16 as Decode<Postgres>>::decode(value).map(|int| AnyInt(int.into()))
+                }
+
+                Some(ty) if <i32 as Type<Postgres>>::compatible(&ty) => {
+                    <i32 as Decode<Postgres>>::decode(value).map(|int| AnyInt(int.into()))
+                }
+
+                _ => <i64 as Decode<Postgres>>::decode(value).map(AnyInt),
+            }
+        }
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    let ints: (AnyInt, AnyInt, AnyInt) = sqlx::query_as("SELECT 1::int2, 2::int4, $1::int8")
+        .bind(3_i64)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(ints, (AnyInt(1), AnyInt(2), AnyInt(3)));
+
+    let mut cursor = conn.fetch("SELECT 'one'::text");
+    let row = cursor.next().await?.unwrap();
+
+    assert!(matches!(
+        row.try_get::<AnyInt, _>(0),
+        Err(sqlx::Error::ColumnDecode { .. })
+    ));
+
+    Ok(())
+}
+
 //
 // JSON
 //
@@ -1,7 +1,13 @@
-use futures::TryStreamExt;
-use sqlx::postgres::{PgPool, PgQueryAs, PgRow};
-use sqlx::{Connection, Cursor, Executor, Postgres, Row};
-use sqlx_test::new;
+use futures::{StreamExt, TryStreamExt};
+use sqlx::columnar::ColumnValues;
+use sqlx::pool::PoolEvent;
+use sqlx::postgres::{PgConnectOptions, PgError, PgPool, PgQueryAs, PgRow};
+use sqlx::statement::SqlRedaction;
+use sqlx::types::TypeInfo;
+use sqlx::{Connect, Connection, Cursor, Either, Executor, PgConnection, Postgres, Row};
+use sqlx_test::{new, PG_STARTUP_OK};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
 
 // TODO: As soon as I tried to deserialize a json value in a function, inferance for this test stopped working. I am at a loss as to how to resolve this.
@@ -107,7 +113,7 @@ async fn it_can_work_with_transactions() -> anyhow::Result<()> {
         .execute(&mut tx)
         .await?;
 
-    conn = tx.rollback().await?;
+    tx.rollback().await?;
 
     let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_1922")
         .fetch_one(&mut conn)
@@ -124,7 +130,7 @@ async fn it_can_work_with_transactions() -> anyhow::Result<()> {
         .execute(&mut tx)
         .await?;
 
-    conn = tx.commit().await?;
+    tx.commit().await?;
 
     let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_1922")
         .fetch_one(&mut conn)
@@ -143,7 +149,14 @@ async fn it_can_work_with_transactions() -> anyhow::Result<()> {
             .await?;
     }
 
-    conn = new::<Postgres>().await?;
+    // the connection rolls back before running anything else
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_1922")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 1);
+
+    let mut conn = new::<Postgres>().await?;
 
     let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_1922")
         .fetch_one(&mut conn)
@@ -174,16 +187,16 @@ async fn it_can_work_with_nested_transactions() -> anyhow::Result<()> {
         .await?;
 
     // begin once more
-    let mut tx = tx.begin().await?;
+    let mut tx2 = tx.begin().await?;
 
     // insert another user
     sqlx::query("INSERT INTO _sqlx_users_2523 (id) VALUES ($1)")
         .bind(10_i32)
-        .execute(&mut tx)
+        .execute(&mut tx2)
         .await?;
 
     // never mind, rollback
-    let mut tx = tx.rollback().await?;
+    tx2.rollback().await?;
 
     // did we really?
     let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_2523")
@@ -193,7 +206,7 @@ async fn it_can_work_with_nested_transactions() -> anyhow::Result<()> {
     assert_eq!(count, 1);
 
     // actually, commit
-    let mut conn = tx.commit().await?;
+    tx.commit().await?;
 
     // did we really?
     let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_2523")
@@ -224,20 +237,29 @@ async fn it_can_rollback_nested_transactions() -> anyhow::Result<()> {
         .execute(&mut tx)
         .await?;
 
-    // begin once more
-    let mut tx = tx.begin().await?;
+    {
+        // begin once more
+        let mut tx2 = tx.begin().await?;
 
-    // insert another user
-    sqlx::query("INSERT INTO _sqlx_users_512412 (id) VALUES ($1)")
-        .bind(10_i32)
-        .execute(&mut tx)
+        // insert another user
+        sqlx::query("INSERT INTO _sqlx_users_512412 (id) VALUES ($1)")
+            .bind(10_i32)
+            .execute(&mut tx2)
+            .await?;
+
+        // dropped, which rolls back to the save point
+    }
+
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_512412")
+        .fetch_one(&mut tx)
         .await?;
 
+    assert_eq!(count, 1);
+
     // stop the phone, drop the entire transaction
-    tx.close().await?;
+    drop(tx);
 
     // did we really?
-    let mut conn = new::<Postgres>().await?;
     let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_512412")
         .fetch_one(&mut conn)
         .await?;
@@ -247,6 +269,140 @@ async fn it_can_rollback_nested_transactions() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_rolls_back_a_dropped_transaction_after_an_unfinished_query() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TABLE IF NOT EXISTS _sqlx_users_3915 (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    conn.execute("TRUNCATE _sqlx_users_3915").await?;
+
+    {
+        let mut tx = conn.begin().await?;
+
+        sqlx::query("INSERT INTO _sqlx_users_3915 (id) VALUES ($1)")
+            .bind(1_i32)
+            .execute(&mut tx)
+            .await?;
+
+        // leave the connection waiting on the rest of a query
+        let mut cursor = sqlx::query("SELECT generate_series(1, 100)").fetch(&mut tx);
+        cursor.next().await?;
+    }
+
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_3915")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 0);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_can_begin_a_transaction_on_a_borrowed_pool_connection() -> anyhow::Result<()> {
+    struct Context {
+        conn: sqlx::pool::PoolConnection<PgConnection>,
+    }
+
+    let pool = PgPool::builder()
+        .max_size(1)
+        .build(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let mut context = Context {
+        conn: pool.acquire().await?,
+    };
+
+    {
+        let mut tx = context.conn.begin().await?;
+
+        sqlx::query("CREATE TEMPORARY TABLE _sqlx_users_4821 (id INTEGER PRIMARY KEY)")
+            .execute(&mut tx)
+            .await?;
+
+        tx.commit().await?;
+    }
+
+    let tx = context.conn.begin().await?;
+    drop(tx);
+
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_4821")
+        .fetch_one(&mut context.conn)
+        .await?;
+
+    assert_eq!(count, 0);
+
+    // a transaction of the pool returns its connection once it is finished
+    drop(context);
+
+    let mut tx = pool.begin().await?;
+
+    sqlx::query("INSERT INTO _sqlx_users_4821 (id) VALUES ($1)")
+        .bind(1_i32)
+        .execute(&mut tx)
+        .await?;
+
+    drop(tx);
+
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_4821")
+        .fetch_one(&pool)
+        .await?;
+
+    assert_eq!(count, 0);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_releases_the_locks_of_a_dropped_pool_transaction() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute(
+        r#"
+CREATE TABLE IF NOT EXISTS _sqlx_locks_7305 (id INTEGER PRIMARY KEY);
+INSERT INTO _sqlx_locks_7305 (id) VALUES (1) ON CONFLICT DO NOTHING;
+        "#,
+    )
+    .await?;
+
+    let pool = PgPool::builder()
+        .max_size(1)
+        .build(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let mut tx = pool.begin().await?;
+
+    sqlx::query("SELECT id FROM _sqlx_locks_7305 WHERE id = 1 FOR UPDATE")
+        .execute(&mut tx)
+        .await?;
+
+    drop(tx);
+
+    // the ROLLBACK is sent as soon as the connection is returned to the pool, not once it is next
+    // acquired; until then this would wait on the lock held by the transaction
+    conn.execute("SET lock_timeout = '5s'").await?;
+
+    let (id,): (i32,) = sqlx::query_as("SELECT id FROM _sqlx_locks_7305 WHERE id = 1 FOR UPDATE")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(id, 1);
+
+    // the connection was kept, and the result of the ROLLBACK is read before it is next used
+    assert_eq!(pool.size(), 1);
+
+    let (value,): (i32,) = sqlx::query_as("SELECT 1").fetch_one(&pool).await?;
+
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
 // run with `cargo test --features postgres -- --ignored --nocapture pool_smoke_test`
 #[ignore]
 #[cfg_attr(feature = "runtime-async-std", async_std::test)]
@@ -383,3 +539,1392 @@ async fn test_describe() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_describes_parameters() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // `MONEY` is not a builtin type and must be looked up
+    let describe = conn
+        .describe("select $1::int8, $2::text[], $3::uuid, $4::money")
+        .await?;
+
+    let names = describe
+        .param_types
+        .iter()
+        .map(|ty| ty.as_ref().map(|ty| ty.name().into_owned()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        names,
+        [
+            Some("INT8".to_owned()),
+            Some("TEXT[]".to_owned()),
+            Some("UUID".to_owned()),
+            Some("MONEY".to_owned())
+        ]
+    );
+
+    assert_eq!(*describe.param_names, [None, None, None, None]);
+
+    // the same as for the result columns
+    assert_eq!(
+        describe.result_columns[3]
+            .type_info
+            .as_ref()
+            .unwrap()
+            .name(),
+        "MONEY"
+    );
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_fetches_columnar() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let result = sqlx::query(
+        r#"
+SELECT i AS id, i::float8 / 2 AS half, CASE WHEN i % 2 = 0 THEN NULL ELSE i::int2 END AS odd,
+       i % 3 = 0 AS fizz, 'row ' || i AS name, NULL::bytea AS data
+FROM generate_series(1, $1) AS i
+        "#,
+    )
+    .bind(5_i32)
+    .fetch_columnar(&mut conn)
+    .await?;
+
+    assert_eq!(result.len(), 5);
+
+    let names: Vec<_> = result.columns().iter().map(|c| c.name()).collect();
+    assert_eq!(
+        names,
+        vec![
+            Some("id"),
+            Some("half"),
+            Some("odd"),
+            Some("fizz"),
+            Some("name"),
+            Some("data")
+        ]
+    );
+
+    match result.columns()[0].values() {
+        ColumnValues::Int(ids) => assert_eq!(ids.values(), &[1, 2, 3, 4, 5]),
+        values => panic!("unexpected values for `id`: {:?}", values),
+    }
+
+    match result.columns()[1].values() {
+        ColumnValues::Float(halves) => assert_eq!(halves.values(), &[0.5, 1.0, 1.5, 2.0, 2.5]),
+        values => panic!("unexpected values for `half`: {:?}", values),
+    }
+
+    match result.columns()[2].values() {
+        ColumnValues::Int(odd) => {
+            let odd: Vec<_> = (0..odd.len()).map(|i| odd.get(i)).collect();
+            assert_eq!(odd, vec![Some(1), None, Some(3), None, Some(5)]);
+        }
+
+        values => panic!("unexpected values for `odd`: {:?}", values),
+    }
+
+    match result.columns()[3].values() {
+        ColumnValues::Bool(fizz) => {
+            assert_eq!(fizz.values(), &[false, false, true, false, false])
+        }
+
+        values => panic!("unexpected values for `fizz`: {:?}", values),
+    }
+
+    match result.columns()[4].values() {
+        ColumnValues::Text(names) => assert_eq!(names[4].as_deref(), Some("row 5")),
+        values => panic!("unexpected values for `name`: {:?}", values),
+    }
+
+    // a column of only NULLs has no values to determine its type from
+    assert_eq!(result.columns()[5].values(), &ColumnValues::Null(5));
+
+    // types without a columnar representation are an error naming the column
+    let err = sqlx::query("SELECT 1 AS id, now() AS created_at")
+        .fetch_columnar(&mut conn)
+        .await
+        .unwrap_err()
+        .to_string();
+
+    assert!(err.contains("\"created_at\""), "{}", err);
+    assert!(err.contains("TIMESTAMPTZ"), "{}", err);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_connects_with_a_provided_stream() -> anyhow::Result<()> {
+    #[cfg(feature = "runtime-tokio")]
+    use tokio::net::TcpStream;
+
+    #[cfg(feature = "runtime-async-std")]
+    use async_std::net::TcpStream;
+
+    let stream = TcpStream::connect(sqlx_test::database_addr(5432)?).await?;
+    let mut conn =
+        PgConnection::connect_with_stream(stream, &*dotenv::var("DATABASE_URL")?).await?;
+
+    let (value,): (i32,) = sqlx::query_as("SELECT 1 + 1").fetch_one(&mut conn).await?;
+
+    assert_eq!(value, 2);
+
+    conn.close().await?;
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_handshakes_with_a_scripted_server() -> anyhow::Result<()> {
+    #[cfg(feature = "runtime-tokio")]
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::UnixStream,
+    };
+
+    #[cfg(feature = "runtime-async-std")]
+    use async_std::{io::prelude::*, os::unix::net::UnixStream};
+
+    let (client, mut server) = UnixStream::pair()?;
+
+    let server = async move {
+        // StartupMessage: length, protocol version and then the parameters
+        let mut len = [0; 4];
+        server.read_exact(&mut len).await?;
+
+        let mut startup = vec![0; u32::from_be_bytes(len) as usize - 4];
+        server.read_exact(&mut startup).await?;
+
+        server.write_all(PG_STARTUP_OK).await?;
+
+        // Terminate
+        let mut terminate = [0; 5];
+        server.read_exact(&mut terminate).await?;
+
+        anyhow::Result::<_>::Ok((startup, terminate))
+    };
+
+    let client = async {
+        let conn = PgConnection::connect_with_stream(
+            client,
+            "postgres://fake@localhost/scripted?sslmode=disable",
+        )
+        .await?;
+
+        conn.close().await?;
+
+        anyhow::Result::<_>::Ok(())
+    };
+
+    let ((startup, terminate), ()) = futures::try_join!(server, client)?;
+
+    assert_eq!(&startup[..4], &[0, 3, 0, 0]);
+    assert!(std::str::from_utf8(&startup[4..])?.contains("user\0fake\0database\0scripted\0"));
+    assert_eq!(terminate, *b"X\0\0\0\x04");
+
+    Ok(())
+}
+
+/// A pool builder whose connections are each served by an in-process fake server.
+///
+/// Once the client hangs up, each server sends everything it received after the startup
+/// handshake to `received`.
+#[cfg(unix)]
+fn scripted_pool_builder(
+    received: futures::channel::mpsc::UnboundedSender<Vec<u8>>,
+) -> sqlx::pool::Builder<PgConnection> {
+    #[cfg(feature = "runtime-tokio")]
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::UnixStream,
+        spawn,
+    };
+
+    #[cfg(feature = "runtime-async-std")]
+    use async_std::{io::prelude::*, os::unix::net::UnixStream, task::spawn};
+
+    PgPool::builder()
+        .max_size(2)
+        .test_on_acquire(false)
+        .connector(move |url| {
+            let url = url.to_owned();
+            let received = received.clone();
+
+            Box::pin(async move {
+                let (client, mut server) = UnixStream::pair()?;
+
+                spawn(async move {
+                    let mut len = [0; 4];
+                    server.read_exact(&mut len).await?;
+
+                    let mut startup = vec![0; u32::from_be_bytes(len) as usize - 4];
+                    server.read_exact(&mut startup).await?;
+
+                    server.write_all(PG_STARTUP_OK).await?;
+
+                    let mut rest = Vec::new();
+                    server.read_to_end(&mut rest).await?;
+
+                    let _ = received.unbounded_send(rest);
+
+                    std::io::Result::Ok(())
+                });
+
+                PgConnection::connect_with_stream(client, &*url).await
+            })
+        })
+}
+
+#[cfg(unix)]
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_terminates_idle_connections_when_the_pool_is_dropped() -> anyhow::Result<()> {
+    let (received, mut receiver) = futures::channel::mpsc::unbounded();
+
+    let pool = scripted_pool_builder(received)
+        .build("postgres://fake@localhost/scripted?sslmode=disable")
+        .await?;
+
+    // open two connections and return both to the pool
+    let a = pool.acquire().await?;
+    let b = pool.acquire().await?;
+
+    drop((a, b));
+    assert_eq!(pool.idle(), 2);
+
+    // without `.close()`
+    drop(pool);
+
+    for _ in 0..2 {
+        assert_eq!(receiver.next().await.as_deref(), Some(&b"X\0\0\0\x04"[..]));
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_terminates_connections_returned_after_the_pool_is_closed() -> anyhow::Result<()> {
+    let (received, mut receiver) = futures::channel::mpsc::unbounded();
+
+    let pool = scripted_pool_builder(received)
+        .build("postgres://fake@localhost/scripted?sslmode=disable")
+        .await?;
+
+    let idle = pool.acquire().await?;
+    let checked_out = pool.acquire().await?;
+
+    drop(idle);
+
+    // the idle connection is closed before this resolves
+    pool.close().await;
+    assert_eq!(receiver.next().await.as_deref(), Some(&b"X\0\0\0\x04"[..]));
+    assert_eq!(pool.size(), 1);
+
+    // and the other one as soon as it is returned, instead of being kept idle
+    drop(checked_out);
+    assert_eq!(receiver.next().await.as_deref(), Some(&b"X\0\0\0\x04"[..]));
+    assert_eq!(pool.size(), 0);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_opens_pooled_connections_with_a_custom_connector() -> anyhow::Result<()> {
+    #[cfg(feature = "runtime-tokio")]
+    use tokio::net::TcpStream;
+
+    #[cfg(feature = "runtime-async-std")]
+    use async_std::net::TcpStream;
+
+    let addr = sqlx_test::database_addr(5432)?;
+    let dialed = Arc::new(AtomicUsize::new(0));
+
+    let pool = PgPool::builder()
+        .max_size(2)
+        .connector({
+            let dialed = Arc::clone(&dialed);
+
+            move |url| {
+                let url = url.to_owned();
+                let addr = addr.clone();
+
+                dialed.fetch_add(1, Ordering::SeqCst);
+
+                Box::pin(async move {
+                    let stream = TcpStream::connect(&*addr).await?;
+
+                    PgConnection::connect_with_stream(stream, &*url).await
+                })
+            }
+        })
+        .build(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let mut a = pool.acquire().await?;
+    let mut b = pool.acquire().await?;
+
+    let (x,): (i32,) = sqlx::query_as("SELECT 1").fetch_one(&mut a).await?;
+    let (y,): (i32,) = sqlx::query_as("SELECT 2").fetch_one(&mut b).await?;
+
+    assert_eq!((x, y), (1, 2));
+    assert_eq!(dialed.load(Ordering::SeqCst), 2);
+
+    Ok(())
+}
+
+fn error_response(code: &str, message: &str) -> Vec<u8> {
+    let mut fields = Vec::new();
+
+    for (ty, value) in &[
+        (b'S', "FATAL"),
+        (b'V', "FATAL"),
+        (b'C', code),
+        (b'M', message),
+    ] {
+        fields.push(*ty);
+        fields.extend_from_slice(value.as_bytes());
+        fields.push(0);
+    }
+
+    fields.push(0);
+
+    let mut response = vec![b'E'];
+    response.extend_from_slice(&(fields.len() as u32 + 4).to_be_bytes());
+    response.extend_from_slice(&fields);
+    response
+}
+
+/// A fake server on a port of localhost that is not ready until `delay` has passed; until then,
+/// it hangs up on every connection right after the startup message.
+///
+/// Once it is ready, the `n`th connection is answered with `responses[n]` after the startup
+/// message, or the last of `responses` once those run out. Returns the port and the number of
+/// connections answered so far.
+fn spawn_late_server(delay: Duration, responses: Vec<Vec<u8>>) -> (u16, Arc<AtomicUsize>) {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::time::Instant;
+
+    // the listener stays bound for the whole test, so no one else can take the port
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let ready_at = Instant::now() + delay;
+
+    let accepted = Arc::new(AtomicUsize::new(0));
+
+    std::thread::spawn({
+        let accepted = Arc::clone(&accepted);
+
+        move || -> std::io::Result<()> {
+            for stream in listener.incoming() {
+                let mut stream = stream?;
+
+                let mut len = [0; 4];
+                stream.read_exact(&mut len)?;
+
+                let mut startup = vec![0; u32::from_be_bytes(len) as usize - 4];
+                stream.read_exact(&mut startup)?;
+
+                if Instant::now() < ready_at {
+                    continue;
+                }
+
+                let n = accepted.fetch_add(1, Ordering::SeqCst);
+
+                stream.write_all(&responses[n.min(responses.len() - 1)])?;
+
+                // wait for the client to hang up
+                let _ = stream.read_to_end(&mut Vec::new());
+            }
+
+            Ok(())
+        }
+    });
+
+    (port, accepted)
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_retries_connecting_until_the_server_accepts() -> anyhow::Result<()> {
+    let (port, accepted) =
+        spawn_late_server(Duration::from_millis(300), vec![PG_STARTUP_OK.to_vec()]);
+
+    let mut conn = PgConnection::connect(format!(
+        "postgres://fake@127.0.0.1:{}/scripted?sslmode=disable&connect_retries=10&connect_retry_backoff=50",
+        port
+    ))
+    .await?;
+
+    assert_eq!(accepted.load(Ordering::SeqCst), 1);
+
+    conn.close().await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_retries_opening_pooled_connections() -> anyhow::Result<()> {
+    let (port, accepted) =
+        spawn_late_server(Duration::from_millis(300), vec![PG_STARTUP_OK.to_vec()]);
+
+    let pool = PgPool::builder()
+        .test_on_acquire(false)
+        .build(&format!(
+            "postgres://fake@127.0.0.1:{}/scripted?sslmode=disable&connect_retries=10&connect_retry_backoff=50",
+            port
+        ))
+        .await?;
+
+    let conn = pool.acquire().await?;
+
+    assert_eq!(accepted.load(Ordering::SeqCst), 1);
+
+    drop(conn);
+    pool.close().await;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_retries_connecting_as_the_connect_options_say() -> anyhow::Result<()> {
+    let (port, accepted) = spawn_late_server(
+        Duration::from_millis(300),
+        vec![PG_STARTUP_OK.to_vec(), PG_STARTUP_OK.to_vec()],
+    );
+
+    // the options take precedence over the URL, which would not retry at all
+    let options = PgConnectOptions::new(&format!(
+        "postgres://fake@127.0.0.1:{}/scripted?sslmode=disable&connect_retries=0",
+        port
+    ))
+    .connect_retries(10)
+    .connect_retry_backoff(Duration::from_millis(50));
+
+    let mut conn = options.connect().await?;
+
+    assert_eq!(accepted.load(Ordering::SeqCst), 1);
+
+    conn.close().await?;
+
+    let pool = PgPool::builder()
+        .test_on_acquire(false)
+        .build_with(options)
+        .await?;
+
+    let conn = pool.acquire().await?;
+
+    assert_eq!(accepted.load(Ordering::SeqCst), 2);
+
+    drop(conn);
+    pool.close().await;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_retries_while_the_server_is_starting_up() -> anyhow::Result<()> {
+    let starting_up = error_response("57P03", "the database system is starting up");
+
+    let (port, accepted) = spawn_late_server(
+        Duration::from_secs(0),
+        vec![starting_up.clone(), starting_up, PG_STARTUP_OK.to_vec()],
+    );
+
+    let mut conn = PgConnection::connect(format!(
+        "postgres://fake@127.0.0.1:{}/scripted?sslmode=disable&connect_retries=5&connect_retry_backoff=10",
+        port
+    ))
+    .await?;
+
+    assert_eq!(accepted.load(Ordering::SeqCst), 3);
+
+    conn.close().await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_does_not_retry_authentication_failures() -> anyhow::Result<()> {
+    let (port, accepted) = spawn_late_server(
+        Duration::from_secs(0),
+        vec![error_response(
+            "28P01",
+            "password authentication failed for user \"fake\"",
+        )],
+    );
+
+    let res = PgConnection::connect(format!(
+        "postgres://fake@127.0.0.1:{}/scripted?sslmode=disable&connect_retries=5&connect_retry_backoff=10",
+        port
+    ))
+    .await;
+
+    match res {
+        Err(sqlx::Error::Database(err)) => assert_eq!(err.code(), Some("28P01")),
+        res => panic!(
+            "expected an authentication error; got {:?}",
+            res.map(|_| ())
+        ),
+    }
+
+    assert_eq!(accepted.load(Ordering::SeqCst), 1);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_reports_the_attempts_after_exhausting_retries() -> anyhow::Result<()> {
+    // a server that is never ready
+    let (port, accepted) =
+        spawn_late_server(Duration::from_secs(3600), vec![PG_STARTUP_OK.to_vec()]);
+
+    let res = PgConnection::connect(format!(
+        "postgres://fake@127.0.0.1:{}/scripted?sslmode=disable&connect_retries=2&connect_retry_backoff=10",
+        port
+    ))
+    .await;
+
+    match res {
+        Err(sqlx::Error::ConnectRetriesExhausted { attempts, error }) => {
+            assert_eq!(attempts, 3);
+            assert!(
+                matches!(&*error, sqlx::Error::Io(err) if err.kind() == std::io::ErrorKind::ConnectionAborted),
+                "{:?}",
+                error
+            );
+        }
+
+        res => panic!(
+            "expected retries to be exhausted; got {:?}",
+            res.map(|_| ())
+        ),
+    }
+
+    assert_eq!(accepted.load(Ordering::SeqCst), 0);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_rolls_back_a_batch_of_statements_that_fails() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE batch (id SERIAL PRIMARY KEY, value TEXT NOT NULL)")
+        .await?;
+
+    // a batch is sent as one simple query, which Postgres runs in an implicit transaction;
+    // the statements see the effects of those before them but none of them are kept if one fails
+    let res = conn
+        .execute(
+            r#"
+INSERT INTO batch (value) VALUES ('a');
+UPDATE batch SET value = value || '!' WHERE value = 'a';
+SELECT 1 / 0;
+            "#,
+        )
+        .await;
+
+    assert!(matches!(res, Err(sqlx::Error::Database(_))));
+
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM batch")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 0);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_executes_queries_concurrently_on_a_pool() -> anyhow::Result<()> {
+    let pool = PgPool::builder()
+        .max_size(2)
+        .build(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    // unlike a connection, a pool can be shared by queries in flight at the same time;
+    // while one is still sleeping the other one is given a connection (a backend) of its own
+    let ((a,), (b,)): ((i32,), (i32,)) = futures::try_join!(
+        sqlx::query_as("SELECT pg_backend_pid() FROM pg_sleep(0.2)").fetch_one(&pool),
+        sqlx::query_as("SELECT pg_backend_pid() FROM pg_sleep(0.2)").fetch_one(&pool),
+    )?;
+
+    assert_ne!(a, b);
+
+    Ok(())
+}
+
+/// A pool builder whose connections each take `delay` to be opened.
+fn slow_pool_builder(delay: Duration) -> anyhow::Result<sqlx::pool::Builder<PgConnection>> {
+    #[cfg(feature = "runtime-tokio")]
+    use tokio::{net::TcpStream, time::delay_for as sleep};
+
+    #[cfg(feature = "runtime-async-std")]
+    use async_std::{net::TcpStream, task::sleep};
+
+    let addr = sqlx_test::database_addr(5432)?;
+
+    Ok(PgPool::builder()
+        .connect_timeout(Duration::from_secs(30))
+        .connector(move |url| {
+            let url = url.to_owned();
+            let addr = addr.clone();
+
+            Box::pin(async move {
+                sleep(delay).await;
+
+                let stream = TcpStream::connect(&*addr).await?;
+
+                PgConnection::connect_with_stream(stream, &*url).await
+            })
+        }))
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_bounds_connecting_by_the_acquire_timeout() -> anyhow::Result<()> {
+    let pool = slow_pool_builder(Duration::from_secs(5))?
+        .build(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let start = std::time::Instant::now();
+    let res = pool.acquire_timeout(Duration::from_millis(200)).await;
+
+    assert!(start.elapsed() < Duration::from_secs(2));
+
+    match res {
+        Err(err @ sqlx::Error::PoolTimedOut(Some(_))) => {
+            assert_eq!(
+                err.to_string(),
+                "timed out while establishing a new connection: timed out"
+            );
+        }
+
+        res => panic!("expected to time out connecting; got {:?}", res.map(|_| ())),
+    }
+
+    // the abandoned connection attempt does not count towards the size of the pool
+    assert_eq!(pool.size(), 0);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_times_out_waiting_for_a_free_connection() -> anyhow::Result<()> {
+    let pool = slow_pool_builder(Duration::from_millis(50))?
+        .max_size(1)
+        .build(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let held = pool.acquire_timeout(Duration::from_secs(5)).await?;
+
+    let start = std::time::Instant::now();
+    let res = pool.acquire_timeout(Duration::from_millis(200)).await;
+
+    assert!(start.elapsed() >= Duration::from_millis(200));
+    assert!(start.elapsed() < Duration::from_secs(2));
+
+    match res {
+        Err(sqlx::Error::PoolTimedOut(None)) => {}
+        res => panic!("expected to time out waiting; got {:?}", res.map(|_| ())),
+    }
+
+    // once the connection is returned it can be had again, within the builder's timeout
+    drop(held);
+    pool.acquire().await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_copies_rows_in_and_out() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE copies (id INT8 NOT NULL, name TEXT, score FLOAT8)")
+        .await?;
+
+    let mut copy = conn
+        .copy_in("COPY copies (id, name, score) FROM STDIN WITH (FORMAT binary)")
+        .await?;
+
+    // enough rows to be sent in several chunks
+    for id in 0..10_000_i64 {
+        let name = if id % 3 == 0 {
+            None
+        } else {
+            Some(format!("row {}", id))
+        };
+
+        copy.append_row((id, name, id as f64 / 2.0)).await?;
+    }
+
+    assert_eq!(copy.finish().await?, 10_000);
+
+    let mut copy = conn
+        .copy_out::<(i64, Option<String>, f64)>("SELECT * FROM copies ORDER BY id")
+        .await?;
+
+    let mut rows = 0;
+
+    while let Some((id, name, score)) = copy.next().await? {
+        assert_eq!(id, rows);
+        assert_eq!(name.is_none(), id % 3 == 0);
+        assert_eq!(score, id as f64 / 2.0);
+
+        rows += 1;
+    }
+
+    assert_eq!(rows, 10_000);
+
+    // the connection is left ready for the next query
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM copies")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 10_000);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_reports_the_row_that_could_not_be_copied() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE copies (id INT8 NOT NULL)")
+        .await?;
+
+    let mut copy = conn
+        .copy_in("COPY copies (id) FROM STDIN WITH (FORMAT binary)")
+        .await?;
+
+    copy.append_row((1_i64,)).await?;
+
+    // an INT4 is not a valid binary INT8
+    copy.append_row((2_i32,)).await?;
+    copy.append_row((3_i64,)).await?;
+
+    match copy.finish().await {
+        Err(sqlx::Error::Database(err)) => {
+            assert_eq!(err.message(), "insufficient data left in message");
+            assert_eq!(err.downcast_ref::<PgError>().copy_row(), Some(2));
+        }
+
+        res => panic!("expected the copy to fail; got {:?}", res),
+    }
+
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM copies")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 0);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_abandons_a_copy_that_is_not_finished() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE copies (id INT8 NOT NULL)")
+        .await?;
+
+    let mut copy = conn
+        .copy_in("COPY copies (id) FROM STDIN WITH (FORMAT binary)")
+        .await?;
+
+    copy.append_row((1_i64,)).await?;
+    copy.abort().await?;
+
+    let mut copy = conn
+        .copy_in("COPY copies (id) FROM STDIN WITH (FORMAT binary)")
+        .await?;
+
+    copy.append_row((2_i64,)).await?;
+    drop(copy);
+
+    // neither copy left any rows and the connection can go on
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM copies")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 0);
+
+    // as can it after a copy out that was not read to the end
+    let mut copy = conn
+        .copy_out::<(i32,)>("SELECT * FROM generate_series(1, 10000)")
+        .await?;
+
+    assert_eq!(copy.next().await?, Some((1,)));
+    drop(copy);
+
+    let (value,): (i32,) = sqlx::query_as("SELECT 1").fetch_one(&mut conn).await?;
+
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_rejects_copying_in_text() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE copies (id INT8 NOT NULL)")
+        .await?;
+
+    match conn.copy_in("COPY copies (id) FROM STDIN").await {
+        Err(sqlx::Error::Io(err)) => assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput),
+
+        res => panic!(
+            "expected the copy to be rejected; got {:?}",
+            res.map(|_| ())
+        ),
+    }
+
+    let (value,): (i32,) = sqlx::query_as("SELECT 1").fetch_one(&mut conn).await?;
+
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_stops_tasks_holding_a_weak_pool_once_the_pool_is_dropped() -> anyhow::Result<()> {
+    #[cfg(feature = "runtime-tokio")]
+    use tokio::{task::spawn, time::delay_for as sleep, time::timeout};
+
+    #[cfg(feature = "runtime-async-std")]
+    use async_std::{future::timeout, task::sleep, task::spawn};
+
+    let pool = PgPool::new(&dotenv::var("DATABASE_URL")?).await?;
+    let weak = pool.downgrade();
+    let runs = Arc::new(AtomicUsize::new(0));
+
+    let task = spawn({
+        let runs = Arc::clone(&runs);
+
+        async move {
+            while let Some(pool) = weak.upgrade() {
+                let _ = sqlx::query("SELECT 1").execute(&pool).await;
+                runs.fetch_add(1, Ordering::SeqCst);
+
+                drop(pool);
+                sleep(Duration::from_millis(10)).await;
+            }
+        }
+    });
+
+    while runs.load(Ordering::SeqCst) < 3 {
+        sleep(Duration::from_millis(10)).await;
+    }
+
+    // the task does not keep the pool alive
+    let clone = pool.clone();
+    drop((pool, clone));
+
+    // and stops by itself
+    let _ = timeout(Duration::from_secs(5), task).await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_fails_pending_acquires_once_the_pool_is_closed() -> anyhow::Result<()> {
+    #[cfg(feature = "runtime-tokio")]
+    use tokio::{task::spawn, time::delay_for as sleep};
+
+    #[cfg(feature = "runtime-async-std")]
+    use async_std::{task::sleep, task::spawn};
+
+    let pool = PgPool::builder()
+        .max_size(1)
+        .connect_timeout(Duration::from_secs(30))
+        .build(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let held = pool.acquire().await?;
+
+    // waiting for the connection that is held, through an upgraded handle
+    let weak = pool.downgrade();
+    let waiting = spawn(async move {
+        let pool = weak.upgrade().unwrap();
+        let start = std::time::Instant::now();
+
+        (pool.acquire().await.map(|_| ()), start.elapsed())
+    });
+
+    sleep(Duration::from_millis(100)).await;
+    pool.close().await;
+
+    #[cfg(feature = "runtime-tokio")]
+    let (res, elapsed) = waiting.await?;
+
+    #[cfg(feature = "runtime-async-std")]
+    let (res, elapsed) = waiting.await;
+
+    assert!(matches!(res, Err(sqlx::Error::PoolClosed)), "{:?}", res);
+    assert!(elapsed < Duration::from_secs(5), "{:?}", elapsed);
+
+    // and a closed pool cannot be had for a weak handle
+    assert!(pool.downgrade().upgrade().is_none());
+
+    drop(held);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_binds_strings_of_an_unspecified_type() -> anyhow::Result<()> {
+    use sqlx::postgres::types::PgUnknown;
+
+    let mut conn = new::<Postgres>().await?;
+
+    // as `TEXT`, `$1` could not be mixed with an integer
+    let res = sqlx::query("SELECT coalesce($1, 0)")
+        .bind("41")
+        .execute(&mut conn)
+        .await;
+
+    assert!(res.is_err());
+
+    let (value,): (i32,) = sqlx::query_as("SELECT coalesce($1, 0) + $2")
+        .bind(PgUnknown("41"))
+        .bind(1_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, 42);
+
+    // NULL works too
+    let (value,): (i32,) = sqlx::query_as("SELECT coalesce($1, 0) + $2")
+        .bind(None::<PgUnknown<&str>>)
+        .bind(1_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, 1);
+
+    // the `?` operator of `jsonb` is sent as is
+    let (has_a, has_b): (bool, bool) =
+        sqlx::query_as(r#"SELECT '{"a": 1}'::jsonb ? $1, '{"a": 1}'::jsonb ? $2"#)
+            .bind(PgUnknown("a"))
+            .bind(PgUnknown(String::from("b")))
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert!(has_a);
+    assert!(!has_b);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_counts_statement_cache_hits_and_misses() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.reset_statement_cache_metrics();
+
+    for i in 0..3_i32 {
+        let (value,): (i32,) = sqlx::query_as("SELECT $1::int4")
+            .bind(i)
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(value, i);
+    }
+
+    let metrics = conn.statement_cache_metrics();
+
+    assert_eq!(metrics.misses, 1);
+    assert_eq!(metrics.hits, 2);
+    assert_eq!(metrics.evictions, 0);
+    // preparing the statement takes a round trip of its own
+    assert_eq!(metrics.round_trips, 4);
+    assert_eq!(metrics.hit_rate(), Some(2.0 / 3.0));
+
+    // a query without arguments is not prepared
+    conn.execute("SELECT 1").await?;
+
+    let metrics = conn.statement_cache_metrics();
+
+    assert_eq!((metrics.hits, metrics.misses), (2, 1));
+    assert_eq!(metrics.round_trips, 5);
+
+    conn.reset_statement_cache_metrics();
+
+    assert_eq!(conn.statement_cache_metrics(), Default::default());
+    assert_eq!(conn.statement_cache_metrics().hit_rate(), None);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_adds_up_statement_cache_metrics_of_the_pool() -> anyhow::Result<()> {
+    let released = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+    let pool = PgPool::builder()
+        .max_size(1)
+        .event_handler({
+            let released = Arc::clone(&released);
+
+            move |event| {
+                if let PoolEvent::Released {
+                    statement_cache_metrics,
+                } = *event
+                {
+                    released.lock().unwrap().push(statement_cache_metrics);
+                }
+            }
+        })
+        .build(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    for _ in 0..2 {
+        let mut conn = pool.acquire().await?;
+
+        // only what happens while the connection is checked out is counted
+        assert_eq!(conn.statement_cache_metrics(), Default::default());
+
+        sqlx::query("SELECT $1::int4")
+            .bind(1_i32)
+            .execute(&mut conn)
+            .await?;
+
+        // not added to the pool before the connection is returned
+        assert_eq!(pool.statement_cache_metrics().hits, 0);
+    }
+
+    let metrics = pool.statement_cache_metrics();
+
+    assert_eq!(metrics.misses, 1);
+    assert_eq!(metrics.hits, 1);
+
+    // each connection is reported as it is returned, with what it did while checked out
+    let released = released.lock().unwrap().clone();
+
+    assert_eq!(released.len(), 2);
+    assert_eq!((released[0].hits, released[0].misses), (0, 1));
+    assert_eq!((released[1].hits, released[1].misses), (1, 0));
+
+    pool.reset_statement_cache_metrics();
+
+    assert_eq!(pool.statement_cache_metrics(), Default::default());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_fetches_the_rows_and_affected_counts_of_every_statement() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let results: Vec<Either<u64, i32>> = conn
+        .fetch_many(
+            r#"
+CREATE TEMPORARY TABLE fetch_many (id INTEGER PRIMARY KEY);
+INSERT INTO fetch_many (id) VALUES (1), (2) RETURNING id;
+SELECT id FROM fetch_many ORDER BY id;
+UPDATE fetch_many SET id = id + 10;
+            "#,
+            |row: PgRow| row.try_get(0),
+        )
+        .map_ok(|result| result.map_left(|result| result.rows_affected()))
+        .try_collect()
+        .await?;
+
+    assert_eq!(
+        results,
+        [
+            Either::Left(0),
+            // an INSERT .. RETURNING both returns rows and affects them
+            Either::Right(1),
+            Either::Right(2),
+            Either::Left(2),
+            // while the tag of a SELECT counts the rows it returned, none were affected
+            Either::Right(1),
+            Either::Right(2),
+            Either::Left(0),
+            Either::Left(2),
+        ]
+    );
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_stops_fetching_many_at_the_first_error() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE fetch_many (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    let mut results = conn.fetch_many(
+        r#"
+INSERT INTO fetch_many (id) VALUES (1);
+INSERT INTO fetch_many (id) VALUES (1);
+INSERT INTO fetch_many (id) VALUES (2);
+        "#,
+        |row: PgRow| row.try_get::<i32, _>(0),
+    );
+
+    let result = results.try_next().await?.and_then(Either::left);
+
+    assert_eq!(result.map(|result| result.rows_affected()), Some(1));
+    assert!(results.try_next().await.is_err());
+    assert!(results.try_next().await?.is_none());
+
+    drop(results);
+
+    // the statements run in the implicit transaction of a simple query, so the INSERT that
+    // succeeded is rolled back along with the one that failed; the connection can still be used
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM fetch_many")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 0);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_exposes_the_statement_of_each_row() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let sql = "SELECT 1 AS id, $1::text AS name";
+
+    let statement = |row: PgRow| Arc::clone(row.statement());
+
+    let first = sqlx::query(sql)
+        .bind("sqlx")
+        .map(statement)
+        .fetch_one(&mut conn)
+        .await?;
+
+    let second = sqlx::query(sql)
+        .bind("sqlx")
+        .map(statement)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(first.sql(), sql);
+    assert_eq!(
+        first
+            .columns()
+            .iter()
+            .map(|column| column.name.as_deref())
+            .collect::<Vec<_>>(),
+        [Some("id"), Some("name")]
+    );
+
+    // rows of the same statement share its information
+    assert!(Arc::ptr_eq(&first, &second));
+
+    let other = sqlx::query("SELECT 2 AS id")
+        .map(statement)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(other.sql(), "SELECT 2 AS id");
+    assert_ne!(other.id(), first.id());
+
+    // an error decoding a column names the statement as well
+    let err = sqlx::query(sql)
+        .bind("sqlx")
+        .try_map(|row: PgRow| row.try_get::<i32, _>("name"))
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, sqlx::Error::ColumnDecode { .. }), "{:?}", err);
+    assert!(
+        err.to_string()
+            .starts_with("error decoding column \"name\": "),
+        "{}",
+        err
+    );
+    assert!(err.to_string().ends_with(sql), "{}", err);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_redacts_the_sql_of_statements() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.set_sql_redaction(SqlRedaction::Hash);
+
+    let sql = "SELECT 'it_redacts_the_sql_of_statements' AS secret, $1::int4 AS id";
+
+    let err = sqlx::query(sql)
+        .bind(1_i32)
+        .try_map(|row: PgRow| row.try_get::<i32, _>("secret"))
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+
+    let message = err.to_string();
+    let statement = sqlx::query(sql)
+        .bind(1_i32)
+        .map(|row: PgRow| Arc::clone(row.statement()))
+        .fetch_one(&mut conn)
+        .await?;
+
+    // only the (stable) id of the statement is left
+    assert_eq!(
+        statement.sql(),
+        format!("<SQL with the id {:#018x}>", statement.id())
+    );
+    assert!(message.ends_with(statement.sql()), "{}", message);
+    assert!(!message.contains("it_redacts"), "{}", message);
+
+    // a pool applies its redaction to every connection
+    let pool = PgPool::builder()
+        .max_size(1)
+        .sql_redaction(SqlRedaction::Truncate(6))
+        .build(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let statement = sqlx::query(sql)
+        .bind(1_i32)
+        .map(|row: PgRow| Arc::clone(row.statement()))
+        .fetch_one(&pool)
+        .await?;
+
+    assert_eq!(
+        statement.sql(),
+        format!("SELECT ... ({} more bytes)", sql.len() - 6)
+    );
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_discards_the_rows_after_the_first_one() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let first = sqlx::query("SELECT n FROM generate_series(1, 3) n WHERE n > $1")
+        .bind(0_i32)
+        .map(|row: PgRow| row.get::<i32, _>(0))
+        .fetch_optional(&mut conn)
+        .await?;
+
+    assert_eq!(first, Some(1));
+
+    let (first,): (i32,) = sqlx::query_as("SELECT n FROM generate_series(3, 1, -1) n")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(first, 3);
+
+    // an error raised for a later row is returned as well
+    let res = sqlx::query_as::<_, (i32,)>("SELECT 1 / (3 - n) FROM generate_series(1, 3) n")
+        .fetch_one(&mut conn)
+        .await;
+
+    assert!(res.is_err());
+
+    let (text,): (String,) = sqlx::query_as("SELECT 'unrelated'")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(text, "unrelated");
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_cancels_a_running_query() -> anyhow::Result<()> {
+    #[cfg(feature = "runtime-tokio")]
+    use tokio::{task::spawn, time::delay_for as sleep};
+
+    #[cfg(feature = "runtime-async-std")]
+    use async_std::task::{sleep, spawn};
+
+    let mut conn = new::<Postgres>().await?;
+
+    let (query, handle) = sqlx::query(
+        "SELECT n FROM generate_series(1, 3) n CROSS JOIN (SELECT pg_sleep(30)) s WHERE n > $1",
+    )
+    .bind(0_i32)
+    .cancellable();
+
+    spawn(async move {
+        sleep(Duration::from_millis(200)).await;
+        handle.cancel();
+    });
+
+    let started = std::time::Instant::now();
+
+    let res = query
+        .map(|row: PgRow| row.get::<i32, _>(0))
+        .fetch_all(&mut conn)
+        .await;
+
+    assert!(matches!(res, Err(sqlx::Error::Cancelled)), "{:?}", res);
+
+    // postgres was asked to cancel the query instead of letting it run to the end
+    assert!(started.elapsed() < Duration::from_secs(10));
+
+    let (text,): (String,) = sqlx::query_as("SELECT 'unrelated'")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(text, "unrelated");
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_does_not_cancel_a_query_that_completed() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let (query, handle) = sqlx::query_as::<_, (i32,)>("SELECT 1").cancellable();
+
+    let (one,) = query.fetch_one(&mut conn).await?;
+
+    assert_eq!(one, 1);
+
+    handle.cancel();
+
+    let (two,): (i32,) = sqlx::query_as("SELECT 2").fetch_one(&mut conn).await?;
+
+    assert_eq!(two, 2);
+
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use sqlx::Sqlite;
-use sqlx_test::test_type;
+use sqlx_test::{test_type, Priority};
 use std::fmt::Debug;
 
 // Transparent types are rust-side wrappers over DB types
@@ -41,3 +41,65 @@ test_type!(weak_enum(
 ));
 
 test_type!(strong_color_enum(Sqlite, Color, "'green'" == Color::Green));
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_from_row_with_conversions() -> anyhow::Result<()> {
+    use sqlx::sqlite::SqliteQueryAs;
+
+    #[derive(Debug, sqlx::FromRow)]
+    struct Task {
+        #[sqlx(try_from = "i32")]
+        priority: Priority,
+
+        #[sqlx(try_from = "i32")]
+        progress: u8,
+
+        #[sqlx(rename = "tags", with = "sqlx_test::comma_separated")]
+        tag_ids: Vec<i32>,
+    }
+
+    let mut conn = sqlx_test::new::<Sqlite>().await?;
+
+    let task: Task = sqlx::query_as("SELECT 0 AS priority, 42 AS progress, '1,2,3' AS tags")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(task.priority, Priority::Low);
+    assert_eq!(task.progress, 42);
+    assert_eq!(task.tag_ids, vec![1, 2, 3]);
+
+    // a value out of range of the field fails the conversion
+    let err = sqlx::query_as::<_, Task>("SELECT 0 AS priority, -1 AS progress, '1,2,3' AS tags")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, sqlx::Error::ColumnDecode { .. }), "{:?}", err);
+    assert_eq!(
+        err.to_string(),
+        "error decoding column \"progress\": out of range integral type conversion attempted\n\nin the results of: SELECT 0 AS priority, -1 AS progress, '1,2,3' AS tags"
+    );
+
+    let err = sqlx::query_as::<_, Task>("SELECT 2 AS priority, 42 AS progress, '1,2,3' AS tags")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "error decoding column \"priority\": invalid priority: 2\n\nin the results of: SELECT 2 AS priority, 42 AS progress, '1,2,3' AS tags"
+    );
+
+    let err = sqlx::query_as::<_, Task>("SELECT 0 AS priority, 42 AS progress, '1,x' AS tags")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "error decoding column \"tags\" (field `tag_ids`): invalid digit found in string\n\nin the results of: SELECT 0 AS priority, 42 AS progress, '1,x' AS tags"
+    );
+
+    Ok(())
+}
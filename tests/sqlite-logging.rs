@@ -0,0 +1,185 @@
+use std::time::Duration;
+
+use log::{Level, LevelFilter};
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Connection, Cursor, Executor, LogSettings, Sqlite};
+use sqlx_test::{capture_logs, logged, new};
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_logs_statements() -> anyhow::Result<()> {
+    capture_logs();
+
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute("SELECT 'it_logs_statements'").await?;
+
+    assert_eq!(logged("it_logs_statements"), vec![Level::Debug]);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_does_not_log_queries_with_no_logging() -> anyhow::Result<()> {
+    capture_logs();
+
+    let mut conn = new::<Sqlite>().await?;
+
+    sqlx::query("SELECT 'it_does_not_log_queries_with_no_logging', ?")
+        .bind(1_i32)
+        .no_logging()
+        .execute(&mut conn)
+        .await?;
+
+    sqlx::query("SELECT 'it_does_not_log_queries_with_no_logging'")
+        .map(|_: SqliteRow| ())
+        .no_logging()
+        .fetch_one(&mut conn)
+        .await?;
+
+    sqlx::query!(
+        "SELECT name FROM accounts WHERE name <> 'it_does_not_log_queries_with_no_logging'"
+    )
+    .no_logging()
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert!(logged("it_does_not_log_queries_with_no_logging").is_empty());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_logs_queries_at_their_log_level() -> anyhow::Result<()> {
+    capture_logs();
+
+    let mut conn = new::<Sqlite>().await?;
+
+    sqlx::query("SELECT 'it_logs_queries_at_their_log_level'")
+        .log_level(Some(LevelFilter::Info))
+        .execute(&mut conn)
+        .await?;
+
+    // back to the default of the connection
+    sqlx::query("SELECT 'it_logs_queries_at_their_log_level'")
+        .log_level(Some(LevelFilter::Info))
+        .log_level(None)
+        .execute(&mut conn)
+        .await?;
+
+    assert_eq!(
+        logged("it_logs_queries_at_their_log_level"),
+        vec![Level::Info, Level::Debug]
+    );
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_only_logs_statements_allowed_by_the_filter() -> anyhow::Result<()> {
+    capture_logs();
+
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.set_log_settings(
+        LogSettings::default()
+            .log_statements(LevelFilter::Trace)
+            .filter(|sql| !sql.contains("heartbeat")),
+    );
+
+    conn.execute("SELECT 'it_only_logs_statements_allowed_by_the_filter'")
+        .await?;
+
+    conn.execute("SELECT 'it_only_logs_statements_allowed_by_the_filter (heartbeat)'")
+        .await?;
+
+    assert_eq!(
+        logged("it_only_logs_statements_allowed_by_the_filter"),
+        vec![Level::Trace]
+    );
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_applies_the_log_settings_of_a_pool() -> anyhow::Result<()> {
+    capture_logs();
+
+    let pool = sqlx::SqlitePool::builder()
+        .log_settings(LogSettings::default().log_statements(LevelFilter::Info))
+        .build(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    (&pool)
+        .execute("SELECT 'it_applies_the_log_settings_of_a_pool'")
+        .await?;
+
+    assert_eq!(
+        logged("it_applies_the_log_settings_of_a_pool"),
+        vec![Level::Info]
+    );
+
+    Ok(())
+}
+
+// Counts to a few million, which takes well over the 10ms that the slow statement tests use
+const SLOW_STATEMENT: &str = "WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c \
+                              WHERE x < 2000000) SELECT count(*) FROM c";
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_logs_slow_statements() -> anyhow::Result<()> {
+    capture_logs();
+
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.set_log_settings(
+        LogSettings::default().log_slow_statements(LevelFilter::Warn, Duration::from_millis(10)),
+    );
+
+    conn.execute("SELECT 'it_logs_slow_statements'").await?;
+
+    conn.execute(&*format!("{} -- it_logs_slow_statements", SLOW_STATEMENT))
+        .await?;
+
+    assert_eq!(
+        logged("it_logs_slow_statements"),
+        vec![Level::Debug, Level::Warn]
+    );
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_logs_slow_statements_of_a_cursor_once_it_is_exhausted() -> anyhow::Result<()> {
+    capture_logs();
+
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.set_log_settings(
+        LogSettings::default().log_slow_statements(LevelFilter::Warn, Duration::from_millis(10)),
+    );
+
+    let sql = format!(
+        "{} -- it_logs_slow_statements_of_a_cursor_once_it_is_exhausted",
+        SLOW_STATEMENT
+    );
+
+    let mut cursor = conn.fetch(&*sql);
+
+    assert!(logged("it_logs_slow_statements_of_a_cursor").is_empty());
+
+    while cursor.next().await?.is_some() {}
+
+    assert_eq!(
+        logged("it_logs_slow_statements_of_a_cursor"),
+        vec![Level::Warn]
+    );
+
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use sqlx::Sqlite;
 use sqlx_test::new;
+use std::num::NonZeroI32;
 
 #[cfg_attr(feature = "runtime-async-std", async_std::test)]
 #[cfg_attr(feature = "runtime-tokio", tokio::test)]
@@ -75,3 +76,30 @@ async fn macro_select_from_view() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_query_as_non_zero() -> anyhow::Result<()> {
+    #[derive(Debug)]
+    struct Account {
+        id: NonZeroI32,
+        name: String,
+    }
+
+    let mut conn = new::<Sqlite>().await?;
+
+    let id = NonZeroI32::new(1).unwrap();
+
+    let account = sqlx::query_as!(
+        Account,
+        "SELECT id, name from accounts where id = ?",
+        id as NonZeroI32
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(account.id, id);
+    assert_eq!(account.name, "Herp Derpinson");
+
+    Ok(())
+}
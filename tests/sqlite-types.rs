@@ -1,5 +1,5 @@
 use sqlx::Sqlite;
-use sqlx_test::test_type;
+use sqlx_test::{new, test_type};
 
 test_type!(null(
     Sqlite,
@@ -44,3 +44,81 @@ test_type!(bytes(
     "X'0000000052'"
         == vec![0_u8, 0, 0, 0, 0x52]
 ));
+
+mod non_zero {
+    use std::num::{NonZeroI32, NonZeroI64};
+
+    use sqlx::sqlite::SqliteQueryAs;
+
+    use super::*;
+
+    test_type!(non_zero_i32(
+        Sqlite,
+        NonZeroI32,
+        "94101" == NonZeroI32::new(94101).unwrap()
+    ));
+
+    test_type!(non_zero_i64(
+        Sqlite,
+        NonZeroI64,
+        "-9358295312" == NonZeroI64::new(-9358295312).unwrap()
+    ));
+
+    #[cfg_attr(feature = "runtime-async-std", async_std::test)]
+    #[cfg_attr(feature = "runtime-tokio", tokio::test)]
+    async fn it_rejects_zero_for_non_zero_integers() -> anyhow::Result<()> {
+        let mut conn = new::<Sqlite>().await?;
+
+        let (id,): (Option<NonZeroI64>,) =
+            sqlx::query_as("SELECT NULL").fetch_one(&mut conn).await?;
+
+        assert_eq!(id, None);
+
+        let err = sqlx::query_as::<_, (NonZeroI64,)>("SELECT 0")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap_err();
+
+        assert!(err.to_string().contains("NonZeroI64"), "{}", err);
+
+        Ok(())
+    }
+}
+
+mod std_time {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use super::*;
+
+    test_type!(system_time(
+        Sqlite,
+        SystemTime,
+        "'2019-01-02 05:10:20.1151'" == UNIX_EPOCH + Duration::new(1_546_405_820, 115_100_000),
+        "'2019-01-02 05:10:20.000000001'" == UNIX_EPOCH + Duration::new(1_546_405_820, 1),
+        // before the UNIX epoch
+        "'1969-07-20 20:17:40.5'" == UNIX_EPOCH - Duration::from_millis(14_182_939_500)
+    ));
+
+    #[cfg_attr(feature = "runtime-async-std", async_std::test)]
+    #[cfg_attr(feature = "runtime-tokio", tokio::test)]
+    async fn it_fails_to_encode_a_time_out_of_range() -> anyhow::Result<()> {
+        use sqlx::sqlite::SqliteQueryAs;
+
+        let mut conn = new::<Sqlite>().await?;
+
+        // the year is far beyond that of an `i32`
+        let err = sqlx::query("SELECT ?")
+            .bind(UNIX_EPOCH + Duration::from_secs(i64::MAX as u64))
+            .execute(&mut conn)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, sqlx::Error::Encode(_)), "{}", err);
+
+        let (one,): (i32,) = sqlx::query_as("SELECT 1").fetch_one(&mut conn).await?;
+
+        assert_eq!(one, 1);
+
+        Ok(())
+    }
+}
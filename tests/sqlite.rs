@@ -1,6 +1,13 @@
 use futures::TryStreamExt;
-use sqlx::{sqlite::SqliteQueryAs, Connect, Connection, Executor, Sqlite, SqliteConnection};
+use sqlx::columnar::ColumnValues;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteQueryAs, SqliteRow};
+use sqlx::types::TypeInfo;
+use sqlx::{
+    Connect, Connection, Cursor, Either, Executor, Row, Sqlite, SqliteConnection, SqlitePool,
+};
 use sqlx_test::new;
+use std::sync::Arc;
+use std::time::SystemTime;
 
 #[cfg_attr(feature = "runtime-async-std", async_std::test)]
 #[cfg_attr(feature = "runtime-tokio", tokio::test)]
@@ -208,5 +215,667 @@ CREATE TEMPORARY TABLE describe_test (
     // Expressions can not be described
     assert!(describe.result_columns[8].type_info.is_none());
 
+    // The name is the type as declared
+    assert_eq!(
+        describe.result_columns[5]
+            .type_info
+            .as_ref()
+            .unwrap()
+            .name(),
+        "varchar(255)"
+    );
+
+    let describe = conn
+        .describe("select * from describe_test where _1 = :id and _2 = ? and _8 = ?3")
+        .await?;
+
+    assert_eq!(
+        *describe.param_names,
+        [Some(":id".into()), None, Some("?3".into())]
+    );
+
+    for ty in describe.param_types.iter() {
+        assert_eq!(ty.as_ref().map(|ty| ty.name()).as_deref(), Some("ANY"));
+    }
+
+    Ok(())
+}
+
+fn reverse_nocase_options() -> anyhow::Result<SqliteConnectOptions> {
+    Ok(SqliteConnectOptions::new(&dotenv::var("DATABASE_URL")?)
+        .collation("REVERSE_NOCASE", |a: &str, b: &str| {
+            b.to_lowercase().cmp(&a.to_lowercase())
+        }))
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_orders_by_custom_collation() -> anyhow::Result<()> {
+    let mut conn = reverse_nocase_options()?.connect().await?;
+
+    let _ = conn
+        .execute(
+            r#"
+CREATE TEMPORARY TABLE fruits (name TEXT NOT NULL COLLATE REVERSE_NOCASE);
+INSERT INTO fruits (name) VALUES ('banana'), ('Apple'), ('cherry');
+            "#,
+        )
+        .await?;
+
+    let names: Vec<(String,)> = sqlx::query_as("SELECT name FROM fruits ORDER BY name")
+        .fetch_all(&mut conn)
+        .await?;
+
+    let names: Vec<_> = names.into_iter().map(|(name,)| name).collect();
+
+    assert_eq!(names, vec!["cherry", "banana", "Apple"]);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_registers_collations_on_every_pooled_connection() -> anyhow::Result<()> {
+    let pool = SqlitePool::builder()
+        .max_size(2)
+        .build_with(reverse_nocase_options()?)
+        .await?;
+
+    let mut a = pool.acquire().await?;
+    let mut b = pool.acquire().await?;
+
+    for conn in &mut [&mut a, &mut b] {
+        let (first,): (String,) = sqlx::query_as(
+            "SELECT column1 FROM (VALUES ('a'), ('B'), ('c')) ORDER BY column1 COLLATE REVERSE_NOCASE",
+        )
+        .fetch_one(&mut **conn)
+        .await?;
+
+        assert_eq!(first, "c");
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_catches_panics_in_collations() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.create_collation("PANIC", |_, _| panic!("collation panicked"))?;
+
+    let rows: Vec<(String,)> = sqlx::query_as(
+        "SELECT column1 FROM (VALUES ('a'), ('b'), ('c')) ORDER BY column1 COLLATE PANIC",
+    )
+    .fetch_all(&mut conn)
+    .await?;
+
+    // the order is unspecified but the panic must not cross into SQLite
+    assert_eq!(rows.len(), 3);
+
+    let (value,): (i32,) = sqlx::query_as("SELECT 1").fetch_one(&mut conn).await?;
+
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_decodes_text_from_utf16_database() -> anyhow::Result<()> {
+    let mut conn = SqliteConnection::connect("sqlite://tests/fixtures/sqlite-utf16.sqlite").await?;
+
+    let (encoding,): (String,) = sqlx::query_as("PRAGMA encoding")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(encoding, "UTF-16le");
+
+    let greetings: Vec<(String, String)> =
+        sqlx::query_as("SELECT language, text FROM greetings ORDER BY id")
+            .fetch_all(&mut conn)
+            .await?;
+
+    assert_eq!(
+        greetings,
+        vec![
+            ("en".to_owned(), "Hello, world!".to_owned()),
+            ("ja".to_owned(), "こんにちは世界".to_owned()),
+            ("de".to_owned(), "Grüß Gott".to_owned()),
+            ("emoji".to_owned(), "👋🌍".to_owned()),
+        ]
+    );
+
+    let (text,): (String,) = sqlx::query_as("SELECT text FROM greetings WHERE text = ?")
+        .bind("Grüß Gott")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(text, "Grüß Gott");
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_reprepares_statements_after_schema_change() -> anyhow::Result<()> {
+    let path = std::env::temp_dir().join("sqlx-it-reprepares-statements.db");
+    let _ = std::fs::remove_file(&path);
+
+    let url = format!("sqlite://{}", path.display());
+
+    let mut conn = SqliteConnection::connect(&*url).await?;
+    let mut other = SqliteConnection::connect(&*url).await?;
+
+    conn.execute("CREATE TABLE items (id INTEGER PRIMARY KEY); INSERT INTO items (id) VALUES (1)")
+        .await?;
+
+    // prepare (and cache) the statement
+    let rows: Vec<(i32,)> = sqlx::query_as("SELECT * FROM items WHERE id = ?")
+        .bind(1_i32)
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(rows, vec![(1,)]);
+
+    // change the schema from a different connection
+    other
+        .execute("ALTER TABLE items ADD COLUMN name TEXT NOT NULL DEFAULT 'first'")
+        .await?;
+
+    // executing the cached statement again must see the new schema; SQLite re-prepares it by
+    // itself here, so this checks that its columns are loaded again
+    let mut cursor = sqlx::query("SELECT * FROM items WHERE id = ?")
+        .bind(1_i32)
+        .fetch(&mut conn);
+
+    let row = cursor.next().await?.unwrap();
+
+    assert_eq!(row.len(), 2);
+    assert_eq!(row.try_get::<i32, _>("id")?, 1);
+    assert_eq!(row.try_get::<String, _>("name")?, "first");
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_detects_external_writes_with_data_version() -> anyhow::Result<()> {
+    let path = std::env::temp_dir().join("sqlx-it-detects-external-writes.db");
+    let _ = std::fs::remove_file(&path);
+
+    let url = format!("sqlite://{}", path.display());
+
+    let mut conn = SqliteConnection::connect(&*url).await?;
+    let mut other = SqliteConnection::connect(&*url).await?;
+
+    let before = conn.data_version().await?;
+
+    // our own writes do not change the data version
+    conn.execute("CREATE TABLE items (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    assert_eq!(conn.data_version().await?, before);
+
+    other.execute("INSERT INTO items (id) VALUES (1)").await?;
+
+    assert_ne!(conn.data_version().await?, before);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_recycles_stale_pooled_connections() -> anyhow::Result<()> {
+    let path = std::env::temp_dir().join("sqlx-it-recycles-stale-connections.db");
+    let _ = std::fs::remove_file(&path);
+
+    let url = format!("sqlite://{}", path.display());
+
+    let pool = SqlitePool::builder()
+        .max_size(1)
+        .recycle_after_data_version_changes(1)
+        .build(&url)
+        .await?;
+
+    let mut other = SqliteConnection::connect(&*url).await?;
+
+    {
+        let mut conn = pool.acquire().await?;
+        conn.execute("CREATE TEMPORARY TABLE marker (id INTEGER)")
+            .await?;
+    }
+
+    // nothing was written by anyone else; the same connection is handed out again
+    {
+        let mut conn = pool.acquire().await?;
+        conn.execute("SELECT * FROM marker").await?;
+    }
+
+    other
+        .execute("CREATE TABLE items (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    // the connection has gone stale so the pool replaces it with a fresh one
+    {
+        let mut conn = pool.acquire().await?;
+        assert!(conn.execute("SELECT * FROM marker").await.is_err());
+    }
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "the threshold must be at least 1")]
+fn it_rejects_recycling_after_no_data_version_changes() {
+    let _ = SqlitePool::builder().recycle_after_data_version_changes(0);
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_fetches_columnar() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let result = sqlx::query(
+        "SELECT column1 AS id, column2 AS score, column3 AS name, column4 AS data \
+         FROM (VALUES (1, 1.5, 'one', x'01'), (2, NULL, NULL, x'0203'), (NULL, 3.5, 'three', NULL))",
+    )
+    .fetch_columnar(&mut conn)
+    .await?;
+
+    assert_eq!(result.len(), 3);
+    assert_eq!(result.columns().len(), 4);
+
+    match result.column("id").map(|column| column.values()) {
+        Some(ColumnValues::Int(ids)) => {
+            assert_eq!(ids.get(0), Some(1));
+            assert_eq!(ids.get(1), Some(2));
+            assert_eq!(ids.get(2), None);
+            assert_eq!(ids.validity().count_unset(), 1);
+        }
+
+        values => panic!("unexpected values for `id`: {:?}", values),
+    }
+
+    match result.column("score").map(|column| column.values()) {
+        Some(ColumnValues::Float(scores)) => {
+            assert_eq!(scores.get(0), Some(1.5));
+            assert_eq!(scores.get(1), None);
+            assert_eq!(scores.get(2), Some(3.5));
+        }
+
+        values => panic!("unexpected values for `score`: {:?}", values),
+    }
+
+    assert_eq!(
+        result.column("name").map(|column| column.values()),
+        Some(&ColumnValues::Text(vec![
+            Some("one".to_owned()),
+            None,
+            Some("three".to_owned())
+        ]))
+    );
+
+    assert_eq!(
+        result.column("data").map(|column| column.values()),
+        Some(&ColumnValues::Bytes(vec![
+            Some(vec![1]),
+            Some(vec![2, 3]),
+            None
+        ]))
+    );
+
+    // SQLite is dynamically typed; a column that changes type is an error
+    let err = sqlx::query("SELECT column1 AS value FROM (VALUES (1), ('two'))")
+        .fetch_columnar(&mut conn)
+        .await
+        .unwrap_err()
+        .to_string();
+
+    assert!(err.contains("\"value\""), "{}", err);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_executes_a_batch_of_statements_in_order() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE batch (id INTEGER PRIMARY KEY, value TEXT NOT NULL)")
+        .await?;
+
+    // the statements are sent at once and each one sees the effects of those before it; the
+    // rows changed by the last INSERT, UPDATE or DELETE are returned, here those of the UPDATE
+    let affected = conn
+        .execute(
+            r#"
+INSERT INTO batch (value) VALUES ('a');
+INSERT INTO batch (value) VALUES ('b');
+UPDATE batch SET value = value || '!' WHERE value = 'b';
+SELECT * FROM batch;
+            "#,
+        )
+        .await?;
+
+    assert_eq!(affected, 1);
+
+    let values: Vec<(String,)> = sqlx::query_as("SELECT value FROM batch ORDER BY id")
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(values, [("a".to_owned(),), ("b!".to_owned(),)]);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_counts_statement_cache_hits_and_misses() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    for i in 0..3_i32 {
+        let (value,): (i32,) = sqlx::query_as("SELECT ?")
+            .bind(i)
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(value, i);
+    }
+
+    // a query without arguments is not prepared
+    conn.execute("SELECT 1").await?;
+
+    let metrics = conn.statement_cache_metrics();
+
+    assert_eq!(metrics.misses, 1);
+    assert_eq!(metrics.hits, 2);
+    assert_eq!(metrics.evictions, 0);
+    assert_eq!(metrics.round_trips, 0);
+
+    conn.reset_statement_cache_metrics();
+
+    assert_eq!(conn.statement_cache_metrics(), Default::default());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_can_work_with_transactions() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    let mut tx = conn.begin().await?;
+
+    sqlx::query("INSERT INTO users (id) VALUES (?)")
+        .bind(1_i32)
+        .execute(&mut tx)
+        .await?;
+
+    {
+        let mut tx = tx.begin().await?;
+
+        sqlx::query("INSERT INTO users (id) VALUES (?)")
+            .bind(2_i32)
+            .execute(&mut tx)
+            .await?;
+
+        // dropped, which rolls back to the save point
+    }
+
+    tx.commit().await?;
+
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM users")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 1);
+
+    {
+        let mut tx = conn.begin().await?;
+
+        sqlx::query("DELETE FROM users").execute(&mut tx).await?;
+    }
+
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM users")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 1);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_fetches_the_rows_and_affected_counts_of_every_statement() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    // read with the cursor itself, the rows are those of the connection
+    let mut cursor = conn.fetch(
+        r#"
+CREATE TEMPORARY TABLE fetch_many (id INTEGER PRIMARY KEY);
+INSERT INTO fetch_many (id) VALUES (1), (2);
+UPDATE fetch_many SET id = id + 10;
+SELECT id FROM fetch_many ORDER BY id;
+        "#,
+    );
+
+    let mut results = Vec::new();
+
+    while let Some(result) = cursor.next_many().await? {
+        results.push(match result {
+            Either::Left(result) => Either::Left(result.rows_affected()),
+            Either::Right(row) => Either::Right(row.get::<i32, _>(0)),
+        });
+    }
+
+    assert_eq!(
+        results,
+        [
+            Either::Left(0),
+            Either::Left(2),
+            Either::Left(2),
+            Either::Right(11),
+            Either::Right(12),
+            // `sqlite3_changes` still holds the 2 rows of the UPDATE before it
+            Either::Left(0),
+        ]
+    );
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_stops_fetching_many_at_the_first_error() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE fetch_many (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    let mut results = conn.fetch_many(
+        r#"
+INSERT INTO fetch_many (id) VALUES (1);
+INSERT INTO fetch_many (id) VALUES (1);
+INSERT INTO fetch_many (id) VALUES (2);
+        "#,
+        |row: SqliteRow| row.try_get::<i32, _>(0),
+    );
+
+    let result = results.try_next().await?.and_then(Either::left);
+
+    assert_eq!(result.map(|result| result.rows_affected()), Some(1));
+    assert!(results.try_next().await.is_err());
+    assert!(results.try_next().await?.is_none());
+
+    drop(results);
+
+    // each statement runs on its own, so the INSERT before the one that failed is kept
+    // while those after it are not run; the connection can still be used
+    let ids: Vec<(i32,)> = sqlx::query_as("SELECT id FROM fetch_many")
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(ids, [(1,)]);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_exposes_the_statement_of_each_row() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let sql = "SELECT 1 AS id, ? AS name";
+
+    let statement = |row: SqliteRow| Arc::clone(row.statement());
+
+    let first = sqlx::query(sql)
+        .bind("sqlx")
+        .map(statement)
+        .fetch_one(&mut conn)
+        .await?;
+
+    let second = sqlx::query(sql)
+        .bind("sqlx")
+        .map(statement)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(first.sql(), sql);
+    assert_eq!(
+        first
+            .columns()
+            .iter()
+            .map(|column| column.name.as_deref())
+            .collect::<Vec<_>>(),
+        [Some("id"), Some("name")]
+    );
+
+    // rows of the same statement share its information
+    assert!(Arc::ptr_eq(&first, &second));
+
+    let other = sqlx::query("SELECT 2 AS id")
+        .map(statement)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(other.sql(), "SELECT 2 AS id");
+    assert_ne!(other.id(), first.id());
+
+    // an error decoding a column names the statement as well
+    let err = sqlx::query(sql)
+        .bind("sqlx")
+        .try_map(|row: SqliteRow| row.try_get::<SystemTime, _>("name"))
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, sqlx::Error::ColumnDecode { .. }), "{:?}", err);
+    assert!(
+        err.to_string()
+            .starts_with("error decoding column \"name\": "),
+        "{}",
+        err
+    );
+    assert!(err.to_string().ends_with(sql), "{}", err);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_discards_the_rows_after_the_first_one() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    let mut tx = conn.begin().await?;
+
+    sqlx::query("INSERT INTO users (id) VALUES (1), (2), (3)")
+        .execute(&mut tx)
+        .await?;
+
+    let first = sqlx::query("SELECT id FROM users WHERE id > ? ORDER BY id")
+        .bind(0_i32)
+        .map(|row: SqliteRow| row.get::<i32, _>(0))
+        .fetch_optional(&mut tx)
+        .await?;
+
+    assert_eq!(first, Some(1));
+
+    // the statement has finished, so it does not get in the way of the transaction
+    tx.commit().await?;
+
+    let (first,): (i32,) = sqlx::query_as("SELECT id FROM users ORDER BY id DESC")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(first, 3);
+
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM users")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 3);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_cancels_a_running_query() -> anyhow::Result<()> {
+    #[cfg(feature = "runtime-tokio")]
+    use tokio::{task::spawn, time::delay_for as sleep};
+
+    #[cfg(feature = "runtime-async-std")]
+    use async_std::task::{sleep, spawn};
+
+    let mut conn = new::<Sqlite>().await?;
+
+    // counts forever
+    let (query, handle) = sqlx::query_as::<_, (i64,)>(
+        "WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c) SELECT COUNT(*) FROM c",
+    )
+    .cancellable();
+
+    spawn(async move {
+        sleep(std::time::Duration::from_millis(200)).await;
+        handle.cancel();
+    });
+
+    let res = query.fetch_all(&mut conn).await;
+
+    assert!(matches!(res, Err(sqlx::Error::Cancelled)), "{:?}", res);
+
+    // a query that is cancelled between two of its rows
+    let (query, handle) = sqlx::query("SELECT 1 UNION ALL SELECT 2 UNION ALL SELECT 3")
+        .map(|row: SqliteRow| row.get::<i32, _>(0))
+        .cancellable();
+
+    let mut cursor = query.fetch(&mut conn);
+
+    assert_eq!(cursor.try_next().await?, Some(1));
+
+    handle.cancel();
+
+    assert!(matches!(
+        cursor.try_next().await,
+        Err(sqlx::Error::Cancelled)
+    ));
+
+    drop(cursor);
+
+    let (text,): (String,) = sqlx::query_as("SELECT 'unrelated'")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(text, "unrelated");
+
     Ok(())
 }
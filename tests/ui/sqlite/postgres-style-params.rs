@@ -0,0 +1,3 @@
+fn main() {
+    let _ = sqlx::query!("select $1::int4 as id", 1i32);
+}